@@ -4,18 +4,19 @@ use crate::commands::classified::{
     StreamNext,
 };
 use crate::commands::plugin::JsonRpc;
-use crate::commands::plugin::{PluginCommand, PluginSink};
+use crate::commands::plugin::{config_answer, PluginCommand, PluginRequest, PluginSink};
 use crate::commands::whole_stream_command;
 use crate::context::Context;
 use crate::data::config;
-use crate::data::Value;
 pub(crate) use crate::errors::ShellError;
+use crate::evaluate::Scope;
 use crate::fuzzysearch::{interactive_fuzzy_search, SelectionResult};
-use crate::git::current_branch;
 use crate::parser::registry::Signature;
 use crate::parser::{hir, CallNode, Pipeline, PipelineElement, TokenNode};
 use crate::prelude::*;
+use crate::prompt;
 
+use language_reporting::termcolor::{ColorChoice, StandardStream};
 use log::{debug, trace};
 use rustyline::error::ReadlineError;
 use rustyline::{self, config::Configurer, config::EditMode, ColorMode, Config, Editor};
@@ -59,7 +60,21 @@ fn load_plugin(path: &std::path::Path, context: &mut Context) -> Result<(), Shel
     let path = dunce::canonicalize(path)?;
 
     let mut input = String::new();
-    let result = match reader.read_line(&mut input) {
+    let read = loop {
+        input.clear();
+        let read = reader.read_line(&mut input);
+
+        // The plugin may ask for the config keys it wants before answering with its signature
+        match serde_json::from_str::<PluginRequest>(&input) {
+            Ok(PluginRequest::config_get { params: key }) => {
+                let answer = JsonRpc::new("config_value", config_answer(&key));
+                stdin.write_all(format!("{}\n", serde_json::to_string(&answer)?).as_bytes())?;
+            }
+            Err(_) => break read,
+        }
+    };
+
+    let result = match read {
         Ok(count) => {
             trace!("processing response ({} bytes)", count);
             trace!("response: {}", input);
@@ -222,18 +237,76 @@ fn load_plugins(context: &mut Context) -> Result<(), ShellError> {
 pub struct History;
 
 impl History {
+    const DEFAULT_MAX_SIZE: usize = 100;
+
+    /// Where history is kept: the `history_path` config setting, or history.txt in the
+    /// user data directory.
     pub fn path() -> PathBuf {
         const FNAME: &str = "history.txt";
-        config::user_data()
-            .map(|mut p| {
-                p.push(FNAME);
-                p
+
+        let configured = config::config(Tag::unknown())
+            .ok()
+            .and_then(|config| config.get("history_path").cloned())
+            .and_then(|path| path.as_string().ok())
+            .map(|path| PathBuf::from(shellexpand::tilde(&path).as_ref()));
+
+        match configured {
+            Some(path) => path,
+            None => config::user_data()
+                .map(|mut p| {
+                    p.push(FNAME);
+                    p
+                })
+                .unwrap_or(PathBuf::from(FNAME)),
+        }
+    }
+
+    /// How many entries to keep, from the `history_size` config setting.
+    pub fn max_size() -> usize {
+        config::config(Tag::unknown())
+            .ok()
+            .and_then(|config| config.get("history_size").cloned())
+            .and_then(|size| match size.item {
+                Value::Primitive(Primitive::Int(size)) => size.to_usize(),
+                _ => None,
             })
-            .unwrap_or(PathBuf::from(FNAME))
+            .unwrap_or(Self::DEFAULT_MAX_SIZE)
+    }
+
+    /// The lines saved so far, oldest first.
+    pub fn entries() -> Result<Vec<String>, ShellError> {
+        let path = Self::path();
+
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+
+        let file = std::fs::File::open(path)?;
+
+        Ok(BufReader::new(file)
+            .lines()
+            .filter_map(Result::ok)
+            .filter(|line| !line.is_empty())
+            .collect())
     }
 }
 
-pub async fn cli() -> Result<(), Box<dyn Error>> {
+/// Pick the color mode for the line editor. An explicit `--color`/`--no-color` flag always wins,
+/// followed by the `color` config setting, and finally the `NO_COLOR` environment variable.
+pub(crate) fn resolve_color_mode(
+    flag: Option<bool>,
+    config_setting: Option<bool>,
+    no_color_env: bool,
+) -> ColorMode {
+    match flag.or(config_setting) {
+        Some(true) => ColorMode::Forced,
+        Some(false) => ColorMode::Disabled,
+        None if no_color_env => ColorMode::Disabled,
+        None => ColorMode::Forced,
+    }
+}
+
+fn create_default_context() -> Result<Context, Box<dyn Error>> {
     let mut context = Context::basic()?;
 
     {
@@ -245,68 +318,144 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             whole_stream_command(CD),
             whole_stream_command(Size),
             whole_stream_command(Nth),
+            whole_stream_command(Range),
             whole_stream_command(Next),
             whole_stream_command(Previous),
             whole_stream_command(Debug),
+            whole_stream_command(Describe),
             whole_stream_command(Lines),
             whole_stream_command(Shells),
             whole_stream_command(SplitColumn),
             whole_stream_command(SplitRow),
+            whole_stream_command(Parse),
+            whole_stream_command(Format),
+            whole_stream_command(DetectColumns),
             whole_stream_command(Lines),
             whole_stream_command(Reject),
+            whole_stream_command(Compact),
+            whole_stream_command(Default),
             whole_stream_command(Reverse),
+            whole_stream_command(FlattenStream),
+            whole_stream_command(Flatten),
             whole_stream_command(Trim),
             whole_stream_command(ToBSON),
+            whole_stream_command(ToCBOR),
+            whole_stream_command(ToMsgpack),
+            whole_stream_command(ToRON),
             whole_stream_command(ToCSV),
+            whole_stream_command(ToHTML),
+            whole_stream_command(ToMarkdown),
             whole_stream_command(ToJSON),
             whole_stream_command(ToSQLite),
             whole_stream_command(ToDB),
             whole_stream_command(ToTOML),
             whole_stream_command(ToTSV),
             whole_stream_command(ToURL),
+            whole_stream_command(ToBase64),
+            whole_stream_command(UrlEncode),
             whole_stream_command(ToYAML),
             whole_stream_command(SortBy),
+            whole_stream_command(Uniq),
+            whole_stream_command(GroupBy),
             whole_stream_command(Tags),
             whole_stream_command(First),
             whole_stream_command(Last),
+            whole_stream_command(Drop),
             whole_stream_command(Env),
             whole_stream_command(FromCSV),
             whole_stream_command(FromTSV),
+            whole_stream_command(FromSSV),
+            whole_stream_command(FromICS),
+            whole_stream_command(FromVCF),
+            whole_stream_command(FromEML),
+            whole_stream_command(FromHTML),
             whole_stream_command(FromINI),
             whole_stream_command(FromBSON),
+            whole_stream_command(FromCBOR),
+            whole_stream_command(FromMsgpack),
+            whole_stream_command(FromRON),
+            whole_stream_command(FromPlist),
             whole_stream_command(FromJSON),
             whole_stream_command(FromDB),
             whole_stream_command(FromSQLite),
+            whole_stream_command(Query),
             whole_stream_command(FromTOML),
             whole_stream_command(FromURL),
+            whole_stream_command(FromBase64),
+            whole_stream_command(UrlDecode),
+            whole_stream_command(FromODS),
+            whole_stream_command(FromParquet),
+            whole_stream_command(FromXLSX),
             whole_stream_command(FromXML),
             whole_stream_command(FromYAML),
             whole_stream_command(FromYML),
             whole_stream_command(Pick),
+            whole_stream_command(Append),
+            whole_stream_command(Prepend),
+            whole_stream_command(Update),
+            whole_stream_command(Insert),
+            whole_stream_command(FormatFilesize),
+            whole_stream_command(FormatDuration),
             whole_stream_command(Get),
             per_item_command(Remove),
             per_item_command(Fetch),
             per_item_command(Open),
-            per_item_command(Post),
-            per_item_command(Where),
+            whole_stream_command(Post),
+            whole_stream_command(Where),
+            whole_stream_command(If),
             per_item_command(Echo),
+            whole_stream_command(Each),
+            whole_stream_command(Reduce),
             whole_stream_command(Config),
+            whole_stream_command(Alias),
+            whole_stream_command(Let),
+            whole_stream_command(Start),
+            whole_stream_command(Jobs),
+            whole_stream_command(Fg),
+            whole_stream_command(KillJob),
             whole_stream_command(SkipWhile),
+            whole_stream_command(SkipUntil),
             per_item_command(Enter),
             per_item_command(Help),
             whole_stream_command(Exit),
             whole_stream_command(Autoview),
             whole_stream_command(Pivot),
+            whole_stream_command(Headers),
+            whole_stream_command(Hash),
+            whole_stream_command(Wrap),
+            whole_stream_command(Merge),
+            whole_stream_command(Join),
+            whole_stream_command(Seq),
+            whole_stream_command(SeqDate),
+            whole_stream_command(Random),
             per_item_command(Cpy),
             whole_stream_command(Date),
             per_item_command(Mkdir),
+            per_item_command(Du),
             per_item_command(Move),
             whole_stream_command(Save),
             whole_stream_command(Table),
             whole_stream_command(Version),
             whole_stream_command(Which),
+            whole_stream_command(crate::commands::History),
+            whole_stream_command(Watch),
         ]);
 
+        #[cfg(feature = "ps")]
+        {
+            context.add_commands(vec![whole_stream_command(PS)]);
+        }
+
+        #[cfg(feature = "sys")]
+        {
+            context.add_commands(vec![whole_stream_command(Sys)]);
+        }
+
+        #[cfg(feature = "db")]
+        {
+            context.add_commands(vec![whole_stream_command(Db)]);
+        }
+
         #[cfg(feature = "clipboard")]
         {
             context.add_commands(vec![whole_stream_command(
@@ -316,7 +465,163 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
     }
     let _ = load_plugins(&mut context);
 
-    let config = Config::builder().color_mode(ColorMode::Forced).build();
+    Ok(context)
+}
+
+/// Why a line can't be run yet, so that more input is read to finish it off.
+#[derive(Debug, PartialEq)]
+enum Incomplete {
+    String,
+    Group,
+    Pipe,
+    Backslash,
+}
+
+const CONTINUATION_PROMPT: &str = "::: ";
+
+/// A line is incomplete when it ends inside a string or an unclosed `{`, `(` or `[`, after a
+/// pipe, or with a ` \`. A quote only opens a string at the start of a word, so `echo don't`
+/// runs as it is, and a backslash only continues the line after whitespace, so paths like
+/// `C:\` do too. Unbalanced closing brackets are left for the parser to report.
+fn incomplete(line: &str) -> Option<Incomplete> {
+    let mut closers = vec![];
+    let mut quote: Option<char> = None;
+    let mut previous: Option<char> = None;
+
+    for c in line.chars() {
+        let token_start = match previous {
+            None => true,
+            Some(p) => p.is_whitespace() || "|{([".contains(p),
+        };
+        previous = Some(c);
+
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' if token_start => quote = Some(c),
+                '{' => closers.push('}'),
+                '(' => closers.push(')'),
+                '[' => closers.push(']'),
+                '}' | ')' | ']' => {
+                    if closers.pop() != Some(c) {
+                        return None;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let trimmed = line.trim_end();
+
+    if quote.is_some() {
+        Some(Incomplete::String)
+    } else if !closers.is_empty() {
+        Some(Incomplete::Group)
+    } else if trimmed.ends_with('|') {
+        Some(Incomplete::Pipe)
+    } else if trimmed.ends_with(" \\") || trimmed.ends_with("\t\\") || trimmed == "\\" {
+        Some(Incomplete::Backslash)
+    } else {
+        None
+    }
+}
+
+/// Add the next line of input to an incomplete one. Newlines are only kept inside strings.
+fn join_continuation(line: &str, next: &str) -> String {
+    match incomplete(line) {
+        Some(Incomplete::String) => format!("{}\n{}", line, next),
+        Some(Incomplete::Backslash) => {
+            let line = line.trim_end();
+            format!("{} {}", line[..line.len() - 1].trim_end(), next.trim())
+        }
+        _ => format!("{} {}", line.trim_end(), next.trim()),
+    }
+}
+
+/// Group the lines of a script into complete pipelines.
+fn complete_lines(lines: Vec<String>) -> Vec<String> {
+    let mut complete = vec![];
+    let mut pending: Option<String> = None;
+
+    for line in lines {
+        let line = match pending.take() {
+            Some(pending) => join_continuation(&pending, &line),
+            None => line,
+        };
+
+        if incomplete(&line).is_some() {
+            pending = Some(line);
+        } else {
+            complete.push(line);
+        }
+    }
+
+    // Whatever is left is run anyway, so the parser can say what is missing
+    complete.extend(pending);
+    complete
+}
+
+/// Run each line without the line editor, as for `nu script.nu` or `nu -c`. Stops at the
+/// first line that fails, returning whether every line succeeded.
+pub async fn run_script(lines: Vec<String>, color: Option<bool>) -> Result<bool, Box<dyn Error>> {
+    let mut context = create_default_context()?;
+    context.fail_on_exit_code = true;
+
+    for line in complete_lines(lines) {
+        match process_line(Ok(line), &mut context).await {
+            LineResult::Success(_) => {}
+            LineResult::Error(line, err) => {
+                report_error(&mut context, line, err, color);
+                return Ok(false);
+            }
+            LineResult::CtrlC | LineResult::Break => break,
+        }
+    }
+
+    Ok(true)
+}
+
+/// Print an error along with the line it happened on. An explicit `--color` or `--no-color`
+/// overrides whether it's colored.
+fn report_error(context: &mut Context, mut line: String, err: ShellError, color: Option<bool>) {
+    let diag = err.to_diagnostic();
+    context.with_host(|host| {
+        let writer = match color {
+            Some(true) => StandardStream::stderr(ColorChoice::Always),
+            Some(false) => StandardStream::stderr(ColorChoice::Never),
+            None => host.err_termcolor(),
+        };
+        line.push_str(" ");
+        let files = crate::parser::Files::new(line);
+        let _ = std::panic::catch_unwind(move || {
+            let _ = language_reporting::emit(
+                &mut writer.lock(),
+                &files,
+                &diag,
+                &language_reporting::DefaultConfig,
+            );
+        });
+    })
+}
+
+pub async fn cli(color: Option<bool>) -> Result<(), Box<dyn Error>> {
+    let mut context = create_default_context()?;
+
+    let config_color = config::config(Tag::unknown())?.get("color").map(|s| {
+        match s.as_string().unwrap().as_ref() {
+            "false" => false,
+            _ => true,
+        }
+    });
+
+    let color_mode = resolve_color_mode(color, config_color, env::var_os("NO_COLOR").is_some());
+
+    let config = Config::builder()
+        .color_mode(color_mode)
+        .max_history_size(History::max_size())
+        .build();
     let mut rl: Editor<_> = Editor::with_config(config);
 
     #[cfg(windows)]
@@ -334,14 +639,13 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
     })
     .expect("Error setting Ctrl-C handler");
     let mut ctrlcbreak = false;
+    let mut last_exit_code = 0;
     loop {
         if ctrl_c.load(Ordering::SeqCst) {
             ctrl_c.store(false, Ordering::SeqCst);
             continue;
         }
 
-        let cwd = context.shell_manager.path();
-
         rl.set_helper(Some(crate::shell::Helper::new(
             context.shell_manager.clone(),
         )));
@@ -362,17 +666,16 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
         // https://github.com/nushell/nushell/issues/689
         #[cfg(all(not(windows), feature = "crossterm"))]
         rl.bind_sequence(rustyline::KeyPress::Ctrl('R'), rustyline::Cmd::EndOfFile);
+        // Without it, fall back to rustyline's own incremental reverse search
+        #[cfg(any(windows, not(feature = "crossterm")))]
+        rl.bind_sequence(
+            rustyline::KeyPress::Ctrl('R'),
+            rustyline::Cmd::ReverseSearchHistory,
+        );
         // Redefine Ctrl-D to same command as Ctrl-C
         rl.bind_sequence(rustyline::KeyPress::Ctrl('D'), rustyline::Cmd::Interrupt);
 
-        let prompt = &format!(
-            "{}{}> ",
-            cwd,
-            match current_branch() {
-                Some(s) => format!("({})", s),
-                None => "".to_string(),
-            }
-        );
+        let prompt = &prompt::prompt(&mut context, last_exit_code).await;
         let mut initial_command = Some(String::new());
         let mut readline = Err(ReadlineError::Eof);
         while let Some(ref cmd) = initial_command {
@@ -400,9 +703,24 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
             }
         }
 
+        // Keep reading while strings, blocks or pipes are left open
+        while let Ok(line) = &readline {
+            if incomplete(line).is_none() {
+                break;
+            }
+
+            let next = match rl.readline(CONTINUATION_PROMPT) {
+                Ok(next) => Ok(join_continuation(line, &next)),
+                Err(err) => Err(err),
+            };
+            readline = next;
+        }
+
         match process_line(readline, &mut context).await {
             LineResult::Success(line) => {
                 rl.add_history_entry(line.clone());
+                let _ = rl.save_history(&History::path());
+                last_exit_code = 0;
             }
 
             LineResult::CtrlC => {
@@ -428,22 +746,11 @@ pub async fn cli() -> Result<(), Box<dyn Error>> {
                 }
             }
 
-            LineResult::Error(mut line, err) => {
+            LineResult::Error(line, err) => {
                 rl.add_history_entry(line.clone());
-                let diag = err.to_diagnostic();
-                context.with_host(|host| {
-                    let writer = host.err_termcolor();
-                    line.push_str(" ");
-                    let files = crate::parser::Files::new(line);
-                    let _ = std::panic::catch_unwind(move || {
-                        let _ = language_reporting::emit(
-                            &mut writer.lock(),
-                            &files,
-                            &diag,
-                            &language_reporting::DefaultConfig,
-                        );
-                    });
-                })
+                let _ = rl.save_history(&History::path());
+                report_error(&mut context, line, err, color);
+                last_exit_code = 1;
             }
 
             LineResult::Break => {
@@ -466,133 +773,341 @@ enum LineResult {
     Break,
 }
 
+/// A line ending in a single `&` runs its external pipeline in the background as a job.
+fn background_command(line: &str) -> Option<&str> {
+    let line = line.trim_end();
+
+    if !line.ends_with('&') || line.ends_with("&&") {
+        return None;
+    }
+
+    let command = line[..line.len() - 1].trim();
+
+    if command.is_empty() {
+        None
+    } else {
+        Some(command)
+    }
+}
+
 async fn process_line(readline: Result<String, ReadlineError>, ctx: &mut Context) -> LineResult {
     match &readline {
         Ok(line) if line.trim() == "" => LineResult::Success(line.clone()),
 
-        Ok(line) => {
-            let result = match crate::parser::parse(&line, uuid::Uuid::nil()) {
-                Err(err) => {
-                    return LineResult::Error(line.clone(), err);
+        Ok(original) => match background_command(original) {
+            Some(command) => match ctx
+                .registry()
+                .jobs()
+                .spawn(command, &ctx.shell_manager.path())
+            {
+                Ok(id) => {
+                    ctx.with_host(|host| host.stdout(&format!("[{}] {}", id, command)));
+                    LineResult::Success(original.clone())
                 }
+                Err(err) => LineResult::Error(original.clone(), err),
+            },
+
+            None => {
+                // Errors are reported against the expanded line, since that is what spans point
+                // into
+                let (line, result) = match parse_with_aliases(original, ctx) {
+                    Err((line, err)) => {
+                        return LineResult::Error(line, err);
+                    }
 
-                Ok(val) => val,
-            };
+                    Ok(val) => val,
+                };
+                let line = &line;
 
-            debug!("=== Parsed ===");
-            debug!("{:#?}", result);
+                debug!("=== Parsed ===");
+                debug!("{:#?}", result);
 
-            let mut pipeline = match classify_pipeline(&result, ctx, &Text::from(line)) {
-                Ok(pipeline) => pipeline,
-                Err(err) => return LineResult::Error(line.clone(), err),
-            };
+                let pipeline = match classify_pipeline(&result, ctx, &Text::from(line)) {
+                    Ok(pipeline) => with_autoview(pipeline),
+                    Err(err) => return LineResult::Error(line.clone(), err),
+                };
 
-            match pipeline.commands.last() {
-                Some(ClassifiedCommand::External(_)) => {}
-                _ => pipeline
-                    .commands
-                    .push(ClassifiedCommand::Internal(InternalCommand {
-                        command: whole_stream_command(autoview::Autoview),
-                        name_tag: Tag::unknown(),
-                        args: hir::Call::new(
-                            Box::new(hir::Expression::synthetic_string("autoview")),
-                            None,
-                            None,
-                        ),
-                    })),
-            }
+                let output =
+                    match run_classified_pipeline(pipeline, line, ctx, StreamNext::Last).await {
+                        Ok(output) => output,
+                        Err(err) => return LineResult::Error(line.clone(), err),
+                    };
 
-            let mut input = ClassifiedInputStream::new();
+                // The stages stream into one another, so nothing runs until the end is drained
+                let _ = output.objects.into_vec().await;
 
-            let mut iter = pipeline.commands.into_iter().peekable();
-            let mut is_first_command = true;
+                match ctx.take_error() {
+                    None => LineResult::Success(original.clone()),
+                    Some(err) => LineResult::Error(line.clone(), err),
+                }
+            }
+        },
+        Err(ReadlineError::Interrupted) => LineResult::CtrlC,
+        Err(ReadlineError::Eof) => LineResult::Break,
+        Err(err) => {
+            println!("Error: {:?}", err);
+            LineResult::Break
+        }
+    }
+}
 
-            loop {
-                let item: Option<ClassifiedCommand> = iter.next();
-                let next: Option<&ClassifiedCommand> = iter.peek();
+/// View what a pipeline outputs, unless it ends in an external command that prints to the
+/// terminal itself.
+fn with_autoview(mut pipeline: ClassifiedPipeline) -> ClassifiedPipeline {
+    match pipeline.commands.last() {
+        Some(ClassifiedCommand::External(_)) => {}
+        _ => pipeline
+            .commands
+            .push(ClassifiedCommand::Internal(InternalCommand {
+                command: whole_stream_command(autoview::Autoview),
+                name_tag: Tag::unknown(),
+                args: hir::Call::new(
+                    Box::new(hir::Expression::synthetic_string("autoview")),
+                    None,
+                    None,
+                ),
+            })),
+    }
 
-                input = match (item, next) {
-                    (None, _) => break,
+    pipeline
+}
 
-                    (Some(ClassifiedCommand::Expr(_)), _) => {
-                        return LineResult::Error(
-                            line.clone(),
-                            ShellError::unimplemented("Expression-only commands"),
-                        )
-                    }
+/// Run each command of a classified pipeline in turn, returning what the last one outputs.
+/// An external command at the end either prints to the terminal (`StreamNext::Last`) or has
+/// its output captured as lines (`StreamNext::Internal`).
+async fn run_classified_pipeline(
+    pipeline: ClassifiedPipeline,
+    line: &str,
+    ctx: &mut Context,
+    last_external: StreamNext,
+) -> Result<ClassifiedInputStream, ShellError> {
+    let mut input = ClassifiedInputStream::new();
 
-                    (_, Some(ClassifiedCommand::Expr(_))) => {
-                        return LineResult::Error(
-                            line.clone(),
-                            ShellError::unimplemented("Expression-only commands"),
-                        )
-                    }
+    let mut iter = pipeline.commands.into_iter().peekable();
+    let mut is_first_command = true;
 
-                    (
-                        Some(ClassifiedCommand::Internal(left)),
-                        Some(ClassifiedCommand::External(_)),
-                    ) => match left
-                        .run(ctx, input, Text::from(line), is_first_command)
-                        .await
-                    {
-                        Ok(val) => ClassifiedInputStream::from_input_stream(val),
-                        Err(err) => return LineResult::Error(line.clone(), err),
-                    },
+    loop {
+        let item: Option<ClassifiedCommand> = iter.next();
+        let next: Option<&ClassifiedCommand> = iter.peek();
 
-                    (Some(ClassifiedCommand::Internal(left)), Some(_)) => {
-                        match left
-                            .run(ctx, input, Text::from(line), is_first_command)
-                            .await
-                        {
-                            Ok(val) => ClassifiedInputStream::from_input_stream(val),
-                            Err(err) => return LineResult::Error(line.clone(), err),
-                        }
-                    }
+        input = match (item, next) {
+            (None, _) => break,
 
-                    (Some(ClassifiedCommand::Internal(left)), None) => {
-                        match left
-                            .run(ctx, input, Text::from(line), is_first_command)
-                            .await
-                        {
-                            Ok(val) => ClassifiedInputStream::from_input_stream(val),
-                            Err(err) => return LineResult::Error(line.clone(), err),
-                        }
-                    }
+            (Some(ClassifiedCommand::Expr(_)), _) | (_, Some(ClassifiedCommand::Expr(_))) => {
+                return Err(ShellError::unimplemented("Expression-only commands"))
+            }
 
-                    (
-                        Some(ClassifiedCommand::External(left)),
-                        Some(ClassifiedCommand::External(_)),
-                    ) => match left.run(ctx, input, StreamNext::External).await {
-                        Ok(val) => val,
-                        Err(err) => return LineResult::Error(line.clone(), err),
-                    },
+            (Some(ClassifiedCommand::Internal(left)), _) => {
+                let output = left
+                    .run(ctx, input, Text::from(line), is_first_command)
+                    .await?;
+                ClassifiedInputStream::from_input_stream(output)
+            }
 
-                    (Some(ClassifiedCommand::External(left)), Some(_)) => {
-                        match left.run(ctx, input, StreamNext::Internal).await {
-                            Ok(val) => val,
-                            Err(err) => return LineResult::Error(line.clone(), err),
-                        }
-                    }
+            (Some(ClassifiedCommand::External(left)), Some(ClassifiedCommand::External(_))) => {
+                left.run(ctx, input, StreamNext::External).await?
+            }
 
-                    (Some(ClassifiedCommand::External(left)), None) => {
-                        match left.run(ctx, input, StreamNext::Last).await {
-                            Ok(val) => val,
-                            Err(err) => return LineResult::Error(line.clone(), err),
-                        }
-                    }
-                };
+            (Some(ClassifiedCommand::External(left)), Some(_)) => {
+                left.run(ctx, input, StreamNext::Internal).await?
+            }
 
-                is_first_command = false;
+            (Some(ClassifiedCommand::External(left)), None) => {
+                return left.run(ctx, input, last_external).await;
             }
+        };
+
+        is_first_command = false;
+    }
+
+    Ok(input)
+}
+
+/// Run a line without viewing its output, as the prompt does. The output of external commands is
+/// captured too.
+pub(crate) async fn run_pipeline(
+    line: &str,
+    ctx: &mut Context,
+) -> Result<Vec<Tagged<Value>>, ShellError> {
+    let (line, result) = parse_with_aliases(line, ctx).map_err(|(_, err)| err)?;
+    let pipeline = classify_pipeline(&result, ctx, &Text::from(&line))?;
+
+    let values = run_to_values(pipeline, &line, ctx).await?;
+
+    Ok(values)
+}
+
+/// Run a command in parentheses, like `(date now)`, for its output. It runs in the shells of the
+/// line it was typed in and sees the `$it` and variables of the expression around it. Expressions
+/// are evaluated while that line's pipeline is already running on the executor, so the command
+/// is driven to the end on a thread of its own, which is joined before returning.
+pub(crate) fn run_invocation(
+    pipeline: &Tagged<Pipeline>,
+    registry: &CommandRegistry,
+    scope: &Scope,
+    source: &Text,
+) -> Result<Tagged<Value>, ShellError> {
+    let tag = pipeline.tag();
+    let pipeline = TokenNode::Pipeline(pipeline.clone());
+
+    let shell_manager = match registry.shell_manager() {
+        Some(shell_manager) => shell_manager,
+        None => ShellManager::basic(registry.clone()).map_err(|err| {
+            ShellError::labeled_error("Could not run command", err.to_string(), tag)
+        })?,
+    };
+    let mut ctx = Context::from_parts(registry.clone(), shell_manager).with_scope(scope.clone());
+    let source = source.clone();
+
+    let mut values = std::thread::spawn(move || {
+        let pipeline = classify_pipeline(&pipeline, &ctx, &source)?;
+
+        futures::executor::block_on(run_to_values(pipeline, &source, &mut ctx))
+    })
+    .join()
+    .map_err(|_| ShellError::labeled_error("Could not run command", "command failed", tag))??;
+
+    match values.len() {
+        0 => Ok(Value::nothing().tagged(tag)),
+        1 => Ok(values.remove(0)),
+        _ => Ok(Value::Table(values).tagged(tag)),
+    }
+}
+
+async fn run_to_values(
+    pipeline: ClassifiedPipeline,
+    source: &str,
+    ctx: &mut Context,
+) -> Result<Vec<Tagged<Value>>, ShellError> {
+    let output = run_classified_pipeline(pipeline, source, ctx, StreamNext::Internal).await?;
+    let values = output.objects.into_vec().await;
+
+    match ctx.take_error() {
+        None => Ok(values),
+        Some(err) => Err(err),
+    }
+}
+
+/// Run the pipeline a block was written as, viewing its output like that of a line typed at the
+/// prompt. `watch` does this each time files change.
+pub(crate) async fn view_block(block: &value::Block, ctx: &mut Context) -> Result<(), ShellError> {
+    let pipeline = match block_pipeline(block, ctx)? {
+        Some(pipeline) => with_autoview(pipeline),
+        None => return Ok(()),
+    };
+
+    let output = run_classified_pipeline(pipeline, &block.source, ctx, StreamNext::Last).await?;
+    let _ = output.objects.into_vec().await;
+
+    match ctx.take_error() {
+        None => Ok(()),
+        Some(err) => Err(err),
+    }
+}
+
+fn block_pipeline(
+    block: &value::Block,
+    ctx: &Context,
+) -> Result<Option<ClassifiedPipeline>, ShellError> {
+    match &block.pipeline {
+        Some(pipeline) => Ok(Some(classify_pipeline(
+            &TokenNode::Pipeline(pipeline.clone()),
+            ctx,
+            &block.source,
+        )?)),
+        None => Ok(None),
+    }
+}
+
+fn parse_with_aliases(
+    line: &str,
+    context: &Context,
+) -> Result<(String, TokenNode), (String, ShellError)> {
+    let source = expand_aliases(line, context, &mut vec![])?;
+
+    match crate::parser::parse(&source, uuid::Uuid::nil()) {
+        Ok(pipeline) => Ok((source, pipeline)),
+        Err(err) => Err((source, err)),
+    }
+}
+
+// Replace every aliased command in the pipeline with its expansion, expanding the aliases in
+// each body as well. `chain` holds the aliases being expanded around this one, so an alias may
+// refer to a command of the same name. Errors come with the text their spans point into.
+fn expand_aliases(
+    line: &str,
+    context: &Context,
+    chain: &mut Vec<String>,
+) -> Result<String, (String, ShellError)> {
+    let pipeline = match crate::parser::parse(line, uuid::Uuid::nil()) {
+        Ok(pipeline) => pipeline,
+        Err(err) => return Err((line.to_string(), err)),
+    };
+    let source = Text::from(line);
+
+    let Pipeline { parts, .. } = match pipeline.as_pipeline() {
+        Ok(pipeline) => pipeline,
+        Err(err) => return Err((line.to_string(), err)),
+    };
 
-            LineResult::Success(line.clone())
+    if is_alias_definition(&parts, &source) {
+        return Ok(line.to_string());
+    }
+
+    let mut expanded = String::new();
+    let mut copied = 0;
+
+    for part in parts.iter() {
+        let call = part.call();
+
+        if !call.head().is_bare() {
+            continue;
         }
-        Err(ReadlineError::Interrupted) => LineResult::CtrlC,
-        Err(ReadlineError::Eof) => LineResult::Break,
-        Err(err) => {
-            println!("Error: {:?}", err);
-            LineResult::Break
+
+        let name = call.head().source(&source);
+
+        if chain.iter().any(|outer| outer == name) {
+            continue;
         }
+
+        if let Some(alias) = context.registry().get_alias(name) {
+            let args: Vec<&str> = match call.children() {
+                Some(children) => children
+                    .iter()
+                    .filter_map(|child| match child {
+                        TokenNode::Whitespace(_) => None,
+                        other => Some(other.source(&source)),
+                    })
+                    .collect(),
+                None => vec![],
+            };
+
+            let body = match alias.expand(&args, call.head().tag()) {
+                Ok(body) => body,
+                Err(err) => return Err((line.to_string(), err)),
+            };
+
+            chain.push(name.to_string());
+            let body = expand_aliases(&body, context, chain);
+            chain.pop();
+
+            let span = call.tag().span;
+
+            expanded.push_str(&line[copied..span.start]);
+            expanded.push_str(&body?);
+            copied = span.end;
+        }
+    }
+
+    expanded.push_str(&line[copied..]);
+
+    Ok(expanded)
+}
+
+fn is_alias_definition(parts: &[PipelineElement], source: &Text) -> bool {
+    match parts.first() {
+        Some(part) => part.call().head().is_bare() && part.call().head().source(source) == "alias",
+        None => false,
     }
 }
 
@@ -605,6 +1120,25 @@ fn classify_pipeline(
 
     let Pipeline { parts, .. } = pipeline;
 
+    // An alias definition takes the rest of the line, pipes included, as its body
+    if is_alias_definition(&parts, source) && context.has_command("alias") {
+        let head = parts[0].call().head().tag();
+        let end = parts[parts.len() - 1].call().tag();
+        let definition = Tag::from((head.span.end, end.span.end, head.origin));
+
+        return Ok(ClassifiedPipeline {
+            commands: vec![ClassifiedCommand::Internal(InternalCommand {
+                command: context.get_command("alias"),
+                name_tag: head,
+                args: hir::Call::new(
+                    Box::new(hir::Expression::bare(head)),
+                    Some(vec![hir::Expression::string(definition, definition)]),
+                    None,
+                ),
+            })],
+        });
+    }
+
     let commands: Result<Vec<_>, ShellError> = parts
         .iter()
         .map(|item| classify_command(&item, context, &source))
@@ -693,3 +1227,87 @@ fn external_command(
         args: arg_list_strings,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        background_command, complete_lines, incomplete, join_continuation, resolve_color_mode,
+        Incomplete,
+    };
+    use rustyline::ColorMode;
+
+    #[test]
+    fn explicit_color_flag_wins_over_no_color_env() {
+        assert_eq!(
+            resolve_color_mode(Some(true), None, true),
+            ColorMode::Forced
+        );
+        assert_eq!(
+            resolve_color_mode(Some(true), Some(false), true),
+            ColorMode::Forced
+        );
+    }
+
+    #[test]
+    fn explicit_no_color_flag_wins_over_config() {
+        assert_eq!(
+            resolve_color_mode(Some(false), Some(true), false),
+            ColorMode::Disabled
+        );
+    }
+
+    #[test]
+    fn falls_back_to_no_color_env() {
+        assert_eq!(resolve_color_mode(None, None, true), ColorMode::Disabled);
+        assert_eq!(resolve_color_mode(None, None, false), ColorMode::Forced);
+    }
+
+    #[test]
+    fn trailing_ampersand_runs_line_in_background() {
+        assert_eq!(background_command("sleep 10 &"), Some("sleep 10"));
+        assert_eq!(
+            background_command("make && make test &  "),
+            Some("make && make test")
+        );
+        assert_eq!(background_command("make && make test"), None);
+        assert_eq!(background_command("true &&"), None);
+        assert_eq!(background_command(" & "), None);
+    }
+
+    #[test]
+    fn detects_incomplete_lines() {
+        assert_eq!(incomplete("ls | where size > 10"), None);
+        assert_eq!(incomplete("ls |"), Some(Incomplete::Pipe));
+        assert_eq!(incomplete("ls | each { echo"), Some(Incomplete::Group));
+        assert_eq!(incomplete("echo \"one"), Some(Incomplete::String));
+        assert_eq!(incomplete("echo \"{\""), None);
+        assert_eq!(incomplete("echo don't"), None);
+        assert_eq!(incomplete("echo 'one"), Some(Incomplete::String));
+        assert_eq!(incomplete("echo ('one"), Some(Incomplete::String));
+        assert_eq!(incomplete("open file.txt \\"), Some(Incomplete::Backslash));
+        assert_eq!(incomplete("cd C:\\"), None);
+        assert_eq!(incomplete("echo }"), None);
+    }
+
+    #[test]
+    fn joins_continued_lines() {
+        assert_eq!(join_continuation("ls |", "  get name"), "ls | get name");
+        assert_eq!(
+            join_continuation("open a.txt \\", "| lines"),
+            "open a.txt | lines"
+        );
+        assert_eq!(
+            join_continuation("echo \"one", "two\""),
+            "echo \"one\ntwo\""
+        );
+
+        assert_eq!(
+            complete_lines(vec![
+                "ls |".to_string(),
+                "get name".to_string(),
+                "pwd".to_string()
+            ]),
+            vec!["ls | get name".to_string(), "pwd".to_string()]
+        );
+    }
+}
@@ -1,73 +1,143 @@
 #[macro_use]
 pub(crate) mod macros;
 
+pub(crate) mod alias;
+pub(crate) mod append;
 pub(crate) mod args;
 pub(crate) mod autoview;
 pub(crate) mod cd;
 pub(crate) mod classified;
 pub(crate) mod clip;
 pub(crate) mod command;
+pub(crate) mod compact;
 pub(crate) mod config;
 pub(crate) mod cp;
 pub(crate) mod date;
+#[cfg(feature = "db")]
+pub(crate) mod db;
 pub(crate) mod debug;
+pub(crate) mod default;
+pub(crate) mod describe;
+pub(crate) mod detect_columns;
+pub(crate) mod drop;
+pub(crate) mod du;
+pub(crate) mod each;
 pub(crate) mod echo;
 pub(crate) mod enter;
 pub(crate) mod env;
 pub(crate) mod exit;
 pub(crate) mod fetch;
+pub(crate) mod fg;
 pub(crate) mod first;
+pub(crate) mod flatten;
+pub(crate) mod flatten_stream;
+pub(crate) mod format;
+pub(crate) mod format_duration;
+pub(crate) mod format_filesize;
+pub(crate) mod from_base64;
 pub(crate) mod from_bson;
+pub(crate) mod from_cbor;
 pub(crate) mod from_csv;
+pub(crate) mod from_eml;
+pub(crate) mod from_html;
+pub(crate) mod from_ics;
 pub(crate) mod from_ini;
 pub(crate) mod from_json;
+pub(crate) mod from_msgpack;
+pub(crate) mod from_ods;
+pub(crate) mod from_parquet;
+pub(crate) mod from_plist;
+pub(crate) mod from_ron;
 pub(crate) mod from_sqlite;
+pub(crate) mod from_ssv;
 pub(crate) mod from_toml;
 pub(crate) mod from_tsv;
 pub(crate) mod from_url;
+pub(crate) mod from_vcf;
+pub(crate) mod from_xlsx;
 pub(crate) mod from_xml;
 pub(crate) mod from_yaml;
 pub(crate) mod get;
+pub(crate) mod group_by;
+pub(crate) mod hash;
+pub(crate) mod headers;
 pub(crate) mod help;
+pub(crate) mod history;
+pub(crate) mod if_;
+pub(crate) mod insert;
+pub(crate) mod jobs;
+pub(crate) mod join;
+pub(crate) mod kill_job;
 pub(crate) mod last;
+pub(crate) mod let_;
 pub(crate) mod lines;
 pub(crate) mod ls;
+pub(crate) mod merge;
 pub(crate) mod mkdir;
 pub(crate) mod mv;
 pub(crate) mod next;
 pub(crate) mod nth;
 pub(crate) mod open;
+pub(crate) mod parse;
 pub(crate) mod pick;
 pub(crate) mod pivot;
 pub(crate) mod plugin;
 pub(crate) mod post;
+pub(crate) mod prepend;
 pub(crate) mod prev;
+#[cfg(feature = "ps")]
+pub(crate) mod ps;
 pub(crate) mod pwd;
+pub(crate) mod query;
+pub(crate) mod random;
+pub(crate) mod range;
+pub(crate) mod reduce;
 pub(crate) mod reject;
+pub(crate) mod remote;
 pub(crate) mod reverse;
 pub(crate) mod rm;
 pub(crate) mod save;
+pub(crate) mod seq;
+pub(crate) mod seq_date;
 pub(crate) mod shells;
 pub(crate) mod size;
+pub(crate) mod skip_until;
 pub(crate) mod skip_while;
 pub(crate) mod sort_by;
 pub(crate) mod split_column;
 pub(crate) mod split_row;
+pub(crate) mod start;
+#[cfg(feature = "sys")]
+pub(crate) mod sys;
 pub(crate) mod table;
 pub(crate) mod tags;
+pub(crate) mod to_base64;
 pub(crate) mod to_bson;
+pub(crate) mod to_cbor;
 pub(crate) mod to_csv;
+pub(crate) mod to_html;
 pub(crate) mod to_json;
+pub(crate) mod to_md;
+pub(crate) mod to_msgpack;
+pub(crate) mod to_ron;
 pub(crate) mod to_sqlite;
 pub(crate) mod to_toml;
 pub(crate) mod to_tsv;
 pub(crate) mod to_url;
 pub(crate) mod to_yaml;
 pub(crate) mod trim;
+pub(crate) mod uniq;
+pub(crate) mod update;
+pub(crate) mod url_decode;
+pub(crate) mod url_encode;
 pub(crate) mod version;
+pub(crate) mod watch;
 pub(crate) mod where_;
 pub(crate) mod which_;
+pub(crate) mod wrap;
 
+pub(crate) use alias::Alias;
+pub(crate) use append::Append;
 pub(crate) use autoview::Autoview;
 pub(crate) use cd::CD;
 pub(crate) use command::{
@@ -75,58 +145,117 @@ pub(crate) use command::{
     UnevaluatedCallInfo, WholeStreamCommand,
 };
 
+pub(crate) use compact::Compact;
 pub(crate) use config::Config;
 pub(crate) use cp::Cpy;
 pub(crate) use date::Date;
+#[cfg(feature = "db")]
+pub(crate) use db::Db;
 pub(crate) use debug::Debug;
+pub(crate) use default::Default;
+pub(crate) use describe::Describe;
+pub(crate) use detect_columns::DetectColumns;
+pub(crate) use drop::Drop;
+pub(crate) use du::Du;
+pub(crate) use each::Each;
 pub(crate) use echo::Echo;
 pub(crate) use enter::Enter;
 pub(crate) use env::Env;
 pub(crate) use exit::Exit;
 pub(crate) use fetch::Fetch;
+pub(crate) use fg::Fg;
 pub(crate) use first::First;
+pub(crate) use flatten::Flatten;
+pub(crate) use flatten_stream::FlattenStream;
+pub(crate) use format::Format;
+pub(crate) use format_duration::FormatDuration;
+pub(crate) use format_filesize::FormatFilesize;
+pub(crate) use from_base64::FromBase64;
 pub(crate) use from_bson::FromBSON;
+pub(crate) use from_cbor::FromCBOR;
 pub(crate) use from_csv::FromCSV;
+pub(crate) use from_eml::FromEML;
+pub(crate) use from_html::FromHTML;
+pub(crate) use from_ics::FromICS;
 pub(crate) use from_ini::FromINI;
 pub(crate) use from_json::FromJSON;
+pub(crate) use from_msgpack::FromMsgpack;
+pub(crate) use from_ods::FromODS;
+pub(crate) use from_parquet::FromParquet;
+pub(crate) use from_plist::FromPlist;
+pub(crate) use from_ron::FromRON;
 pub(crate) use from_sqlite::FromDB;
 pub(crate) use from_sqlite::FromSQLite;
+pub(crate) use from_ssv::FromSSV;
 pub(crate) use from_toml::FromTOML;
 pub(crate) use from_tsv::FromTSV;
 pub(crate) use from_url::FromURL;
+pub(crate) use from_vcf::FromVCF;
+pub(crate) use from_xlsx::FromXLSX;
 pub(crate) use from_xml::FromXML;
 pub(crate) use from_yaml::FromYAML;
 pub(crate) use from_yaml::FromYML;
 pub(crate) use get::Get;
+pub(crate) use group_by::GroupBy;
+pub(crate) use hash::Hash;
+pub(crate) use headers::Headers;
 pub(crate) use help::Help;
+pub(crate) use history::History;
+pub(crate) use if_::If;
+pub(crate) use insert::Insert;
+pub(crate) use jobs::Jobs;
+pub(crate) use join::Join;
+pub(crate) use kill_job::KillJob;
 pub(crate) use last::Last;
+pub(crate) use let_::Let;
 pub(crate) use lines::Lines;
 pub(crate) use ls::LS;
+pub(crate) use merge::Merge;
 pub(crate) use mkdir::Mkdir;
 pub(crate) use mv::Move;
 pub(crate) use next::Next;
 pub(crate) use nth::Nth;
 pub(crate) use open::Open;
+pub(crate) use parse::Parse;
 pub(crate) use pick::Pick;
 pub(crate) use pivot::Pivot;
 pub(crate) use post::Post;
+pub(crate) use prepend::Prepend;
 pub(crate) use prev::Previous;
+#[cfg(feature = "ps")]
+pub(crate) use ps::PS;
 pub(crate) use pwd::PWD;
+pub(crate) use query::Query;
+pub(crate) use random::Random;
+pub(crate) use range::Range;
+pub(crate) use reduce::Reduce;
 pub(crate) use reject::Reject;
 pub(crate) use reverse::Reverse;
 pub(crate) use rm::Remove;
 pub(crate) use save::Save;
+pub(crate) use seq::Seq;
+pub(crate) use seq_date::SeqDate;
 pub(crate) use shells::Shells;
 pub(crate) use size::Size;
+pub(crate) use skip_until::SkipUntil;
 pub(crate) use skip_while::SkipWhile;
 pub(crate) use sort_by::SortBy;
 pub(crate) use split_column::SplitColumn;
 pub(crate) use split_row::SplitRow;
+pub(crate) use start::Start;
+#[cfg(feature = "sys")]
+pub(crate) use sys::Sys;
 pub(crate) use table::Table;
 pub(crate) use tags::Tags;
+pub(crate) use to_base64::ToBase64;
 pub(crate) use to_bson::ToBSON;
+pub(crate) use to_cbor::ToCBOR;
 pub(crate) use to_csv::ToCSV;
+pub(crate) use to_html::ToHTML;
 pub(crate) use to_json::ToJSON;
+pub(crate) use to_md::ToMarkdown;
+pub(crate) use to_msgpack::ToMsgpack;
+pub(crate) use to_ron::ToRON;
 pub(crate) use to_sqlite::ToDB;
 pub(crate) use to_sqlite::ToSQLite;
 pub(crate) use to_toml::ToTOML;
@@ -134,6 +263,12 @@ pub(crate) use to_tsv::ToTSV;
 pub(crate) use to_url::ToURL;
 pub(crate) use to_yaml::ToYAML;
 pub(crate) use trim::Trim;
+pub(crate) use uniq::Uniq;
+pub(crate) use update::Update;
+pub(crate) use url_decode::UrlDecode;
+pub(crate) use url_encode::UrlEncode;
 pub(crate) use version::Version;
+pub(crate) use watch::Watch;
 pub(crate) use where_::Where;
 pub(crate) use which_::Which;
+pub(crate) use wrap::Wrap;
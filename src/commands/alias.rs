@@ -0,0 +1,178 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Alias;
+
+#[derive(Deserialize)]
+pub struct AliasArgs {
+    definition: Tagged<String>,
+}
+
+impl WholeStreamCommand for Alias {
+    fn name(&self) -> &str {
+        "alias"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("alias").required("definition", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Define an alias for a pipeline (eg. alias ll = ls | sort-by size). Parameters are substituted by name (eg. alias gco [branch] = git checkout $branch)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, alias)?.run()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AliasDefinition {
+    params: Vec<String>,
+    body: String,
+}
+
+impl AliasDefinition {
+    /// Parse `name [params] = body`, where the parameter list is optional.
+    pub(crate) fn parse(
+        definition: &Tagged<String>,
+    ) -> Result<(String, AliasDefinition), ShellError> {
+        let malformed =
+            |label: &str| ShellError::labeled_error("Malformed alias", label, definition.tag());
+
+        let mut halves = definition.item.splitn(2, '=');
+        let signature = halves.next().unwrap_or("").trim();
+        let body = match halves.next() {
+            Some(body) if !body.trim().is_empty() => body.trim().to_string(),
+            _ => return Err(malformed("expected name = pipeline")),
+        };
+
+        let (name, params) = match signature.find(char::is_whitespace) {
+            Some(idx) => (&signature[..idx], signature[idx..].trim()),
+            None => (signature, ""),
+        };
+
+        if name.is_empty() {
+            return Err(malformed("expected a name before ="));
+        }
+
+        let params = if params.is_empty() {
+            vec![]
+        } else if params.starts_with('[') && params.ends_with(']') {
+            params[1..params.len() - 1]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|param| !param.is_empty())
+                .map(|param| param.trim_start_matches('$').to_string())
+                .collect()
+        } else {
+            return Err(malformed("expected parameters in square brackets"));
+        };
+
+        Ok((name.to_string(), AliasDefinition { params, body }))
+    }
+
+    /// Substitute the given arguments for the alias parameters. Arguments beyond
+    /// the parameter list are appended to the expansion.
+    pub(crate) fn expand(&self, args: &[&str], tag: Tag) -> Result<String, ShellError> {
+        if args.len() < self.params.len() {
+            return Err(ShellError::labeled_error(
+                "Missing alias argument",
+                format!("expected a value for ${}", self.params[args.len()]),
+                tag,
+            ));
+        }
+
+        let mut expanded = String::new();
+        let mut rest = self.body.as_str();
+
+        // Only whole variables are substituted, so `$b` leaves `$branch` and `$b_name` alone
+        while let Some(dollar) = rest.find('$') {
+            expanded.push_str(&rest[..dollar]);
+            rest = &rest[dollar + 1..];
+
+            let end = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+                .unwrap_or_else(|| rest.len());
+            let name = &rest[..end];
+
+            match self.params.iter().position(|param| param == name) {
+                Some(idx) => expanded.push_str(args[idx]),
+                None => {
+                    expanded.push('$');
+                    expanded.push_str(name);
+                }
+            }
+
+            rest = &rest[end..];
+        }
+        expanded.push_str(rest);
+
+        for arg in &args[self.params.len()..] {
+            expanded.push(' ');
+            expanded.push_str(arg);
+        }
+
+        Ok(expanded)
+    }
+}
+
+pub fn alias(
+    AliasArgs { definition }: AliasArgs,
+    RunnableContext { commands, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let (name, alias) = AliasDefinition::parse(&definition)?;
+
+    commands.add_alias(name, alias);
+
+    Ok(OutputStream::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AliasDefinition;
+    use crate::prelude::*;
+
+    fn define(definition: &str) -> (String, AliasDefinition) {
+        AliasDefinition::parse(&definition.to_string().tagged_unknown()).unwrap()
+    }
+
+    #[test]
+    fn substitutes_parameters_and_appends_extra_arguments() {
+        let (name, alias) = define("gco [branch] = git checkout $branch");
+
+        assert_eq!(name, "gco");
+        assert_eq!(
+            alias
+                .expand(&["master", "--quiet"], Tag::unknown())
+                .unwrap(),
+            "git checkout master --quiet"
+        );
+        assert!(alias.expand(&[], Tag::unknown()).is_err());
+    }
+
+    #[test]
+    fn substitutes_only_whole_variables() {
+        let (_, alias) = define("br [b, branch] = echo $b $branch $bx $b_name $b");
+
+        assert_eq!(
+            alias.expand(&["one", "two"], Tag::unknown()).unwrap(),
+            "echo one two $bx $b_name one"
+        );
+    }
+
+    #[test]
+    fn keeps_pipelines_in_the_body() {
+        let (name, alias) = define("ll = ls | sort-by size");
+
+        assert_eq!(name, "ll");
+        assert_eq!(
+            alias.expand(&[], Tag::unknown()).unwrap(),
+            "ls | sort-by size"
+        );
+    }
+}
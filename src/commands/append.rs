@@ -0,0 +1,93 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Append;
+
+#[derive(Deserialize)]
+pub struct AppendArgs {
+    row: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Append {
+    fn name(&self) -> &str {
+        "append"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("append").required("row value", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Append the given row(s) to the table. A list adds each of its items as a row, and a block adds what it evaluates to."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, append)?.run()
+    }
+}
+
+/// The rows a block outputs. It's evaluated once, without a row, so a table it evaluates
+/// to (eg. `{ (open more.csv) }`) gives each of its rows, and any other value a single row.
+pub(crate) fn block_rows(
+    block: &value::Block,
+    registry: &CommandRegistry,
+) -> Result<Vec<Tagged<Value>>, ShellError> {
+    let value = block.invoke(&Value::nothing().tagged(block.tag), registry)?;
+
+    match value {
+        Tagged {
+            item: Value::Table(rows),
+            ..
+        } => Ok(rows),
+        other => Ok(vec![other]),
+    }
+}
+
+/// The rows an argument adds. A list adds each of its items, and a block whatever it outputs.
+pub(crate) fn rows_from(
+    value: Tagged<Value>,
+    registry: &CommandRegistry,
+) -> Result<VecDeque<Tagged<Value>>, ShellError> {
+    match value {
+        Tagged {
+            item: Value::Table(rows),
+            ..
+        } => Ok(rows.into_iter().collect()),
+        Tagged {
+            item: Value::Block(block),
+            ..
+        } => Ok(block_rows(&block, registry)?.into_iter().collect()),
+        other => Ok(VecDeque::from(vec![other])),
+    }
+}
+
+fn append(
+    AppendArgs { row }: AppendArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+
+        while let Some(value) = values.next().await {
+            yield ReturnSuccess::value(value);
+        }
+
+        match rows_from(row, &commands) {
+            Ok(after) => {
+                for row in after {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+            Err(err) => yield Err(err),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -1,11 +1,19 @@
 use crate::commands::{RawCommandArgs, WholeStreamCommand};
 use crate::errors::ShellError;
+use crate::format::EntriesView;
 use crate::prelude::*;
 
 pub struct Autoview;
 
+const DEFAULT_MAX_ROWS: u64 = 100;
+const HEX_BYTES_PER_LINE: usize = 16;
+
 #[derive(Deserialize)]
-pub struct AutoviewArgs {}
+pub struct AutoviewArgs {
+    #[serde(rename(deserialize = "max-rows"))]
+    max_rows: Option<Tagged<u64>>,
+    all: bool,
+}
 
 impl WholeStreamCommand for Autoview {
     fn name(&self) -> &str {
@@ -14,10 +22,12 @@ impl WholeStreamCommand for Autoview {
 
     fn signature(&self) -> Signature {
         Signature::build("autoview")
+            .named("max-rows", SyntaxShape::Int)
+            .switch("all")
     }
 
     fn usage(&self) -> &str {
-        "View the contents of the pipeline as a table or list."
+        "View the contents of the pipeline as a table or list. Long tables are cut off after --max-rows rows (the max_rows config, or 100), and binary data after a screen of hex, unless --all is given."
     }
 
     fn run(
@@ -30,10 +40,20 @@ impl WholeStreamCommand for Autoview {
 }
 
 pub fn autoview(
-    AutoviewArgs {}: AutoviewArgs,
+    AutoviewArgs { max_rows, all }: AutoviewArgs,
     mut context: RunnableContext,
     raw: RawCommandArgs,
 ) -> Result<OutputStream, ShellError> {
+    let max_rows = if all {
+        None
+    } else {
+        Some(
+            max_rows
+                .map(|rows| rows.item)
+                .unwrap_or_else(configured_max_rows) as usize,
+        )
+    };
+
     Ok(OutputStream::new(async_stream_block! {
         let input = context.input.drain_vec().await;
 
@@ -51,8 +71,17 @@ pub fn autoview(
                     for i in input {
                         match i.item {
                             Value::Primitive(Primitive::Binary(b)) => {
-                                use pretty_hex::*;
-                                println!("{:?}", b.hex_dump());
+                                let max_lines = if all { None } else { Some(screen_lines()) };
+                                let (lines, notice) = page_hex_dump(&b, max_lines);
+                                let mut host = context.host.lock().unwrap();
+
+                                for line in lines {
+                                    host.stdout(&line);
+                                }
+
+                                if let Some(notice) = notice {
+                                    host.stdout(&notice);
+                                }
                             }
                             _ => {}
                         }
@@ -82,16 +111,139 @@ pub fn autoview(
                         _ => {}
                     }
                 }
+            } else if is_single_row(&input) && prefers_vertical_single_row() {
+                let view = EntriesView::from_value(&input[0]);
+                let mut host = context.host.lock().unwrap();
+                handle_unexpected(&mut *host, |host| crate::format::print_view(&view, host));
             } else {
+                let (input, notice) = truncate_rows(input, max_rows);
                 let table = context.expect_command("table");
                 let result = table.run(raw.with_input(input), &context.commands, false);
                 result.collect::<Vec<_>>().await;
+
+                if let Some(notice) = notice {
+                    context.host.lock().unwrap().stdout(&notice);
+                }
             }
         }
     }))
 }
 
-fn is_single_text_value(input: &Vec<Tagged<Value>>) -> bool {
+fn configured_max_rows() -> u64 {
+    crate::data::config::config(Tag::unknown())
+        .ok()
+        .and_then(|config| config.get("max_rows").cloned())
+        .and_then(|rows| match rows.item {
+            Value::Primitive(Primitive::Int(rows)) => rows.to_u64(),
+            _ => None,
+        })
+        .unwrap_or(DEFAULT_MAX_ROWS)
+}
+
+/// Keep at most `max_rows` rows, along with a notice saying how many were left out.
+fn truncate_rows(
+    mut input: Vec<Tagged<Value>>,
+    max_rows: Option<usize>,
+) -> (Vec<Tagged<Value>>, Option<String>) {
+    match max_rows {
+        Some(max_rows) if input.len() > max_rows => {
+            let hidden = input.len() - max_rows;
+            input.truncate(max_rows);
+
+            let notice = format!(
+                "... and {} more {}",
+                with_thousands_separators(hidden),
+                if hidden == 1 { "row" } else { "rows" }
+            );
+
+            (input, Some(notice))
+        }
+        _ => (input, None),
+    }
+}
+
+/// Lay out bytes the way `xxd` does: the offset, sixteen bytes in hex, then the same bytes as
+/// text with anything unprintable shown as a dot.
+fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_BYTES_PER_LINE)
+        .enumerate()
+        .map(|(line, chunk)| {
+            let hex = chunk
+                .chunks(2)
+                .map(|pair| {
+                    pair.iter()
+                        .map(|b| format!("{:02x}", b))
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            let text: String = chunk
+                .iter()
+                .map(|b| {
+                    if b.is_ascii_graphic() || *b == b' ' {
+                        *b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+
+            format!("{:08x}: {:<39}  {}", line * HEX_BYTES_PER_LINE, hex, text)
+        })
+        .collect()
+}
+
+/// Keep at most `max_lines` lines of the hex dump, along with a notice saying how many bytes
+/// were left out.
+fn page_hex_dump(bytes: &[u8], max_lines: Option<usize>) -> (Vec<String>, Option<String>) {
+    // Only the lines that will be shown are formatted
+    let shown = match max_lines {
+        Some(max_lines) => std::cmp::min(bytes.len(), max_lines * HEX_BYTES_PER_LINE),
+        None => bytes.len(),
+    };
+
+    let lines = hex_dump(&bytes[..shown]);
+    let hidden = bytes.len() - shown;
+
+    if hidden == 0 {
+        return (lines, None);
+    }
+
+    let notice = format!(
+        "... and {} more {}",
+        with_thousands_separators(hidden),
+        if hidden == 1 { "byte" } else { "bytes" }
+    );
+
+    (lines, Some(notice))
+}
+
+/// How many lines of a hex dump fit on the screen, leaving room for the notice and the prompt.
+fn screen_lines() -> usize {
+    let height = term_size::dimensions()
+        .map(|(_, height)| height)
+        .unwrap_or(24);
+
+    std::cmp::max(height.saturating_sub(2), 1)
+}
+
+fn with_thousands_separators(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::new();
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+
+    out
+}
+
+fn is_single_text_value(input: &[Tagged<Value>]) -> bool {
     if input.len() != 1 {
         return false;
     }
@@ -106,7 +258,34 @@ fn is_single_text_value(input: &Vec<Tagged<Value>>) -> bool {
     }
 }
 
-fn is_single_origined_text_value(input: &Vec<Tagged<Value>>) -> bool {
+fn is_single_row(input: &[Tagged<Value>]) -> bool {
+    if input.len() != 1 {
+        return false;
+    }
+
+    if let Tagged {
+        item: Value::Row(_),
+        ..
+    } = input[0]
+    {
+        true
+    } else {
+        false
+    }
+}
+
+// A single row reads best as key/value pairs, unless `single_row_view` is set to "table"
+fn prefers_vertical_single_row() -> bool {
+    match crate::data::config::config(Tag::unknown()) {
+        Ok(config) => match config.get("single_row_view") {
+            Some(view) => view.as_string().map(|v| v != "table").unwrap_or(true),
+            None => true,
+        },
+        Err(_) => true,
+    }
+}
+
+fn is_single_origined_text_value(input: &[Tagged<Value>]) -> bool {
     if input.len() != 1 {
         return false;
     }
@@ -121,3 +300,53 @@ fn is_single_origined_text_value(input: &Vec<Tagged<Value>>) -> bool {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{hex_dump, page_hex_dump, truncate_rows};
+    use crate::prelude::*;
+
+    #[test]
+    fn truncates_long_tables_with_a_notice() {
+        let input: Vec<_> = (0..10000).map(|i| Value::int(i).tagged_unknown()).collect();
+
+        let (shown, notice) = truncate_rows(input, Some(100));
+
+        assert_eq!(shown.len(), 100);
+        assert_eq!(shown[99].item, Value::int(99));
+        assert_eq!(notice, Some("... and 9,900 more rows".to_string()));
+    }
+
+    #[test]
+    fn shows_everything_without_a_limit() {
+        let input: Vec<_> = (0..3).map(|i| Value::int(i).tagged_unknown()).collect();
+
+        let (shown, notice) = truncate_rows(input, None);
+
+        assert_eq!(shown.len(), 3);
+        assert_eq!(notice, None);
+    }
+
+    #[test]
+    fn dumps_binary_like_xxd() {
+        let bytes = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\x01";
+
+        assert_eq!(
+            hex_dump(bytes),
+            vec![
+                "00000000: 8950 4e47 0d0a 1a0a 0000 000d 4948 4452  .PNG........IHDR",
+                "00000010: 0001                                     ..",
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_long_hex_dumps_with_a_notice() {
+        let bytes = vec![0u8; 100];
+
+        let (shown, notice) = page_hex_dump(&bytes, Some(2));
+
+        assert_eq!(shown.len(), 2);
+        assert_eq!(notice, Some("... and 68 more bytes".to_string()));
+    }
+}
@@ -1,49 +1,11 @@
 use crate::commands::Command;
 use crate::parser::{hir, TokenNode};
 use crate::prelude::*;
-use bytes::{BufMut, BytesMut};
+use futures::io::AsyncBufReadExt;
 use futures::stream::StreamExt;
-use futures_codec::{Decoder, Encoder, Framed};
 use log::{log_enabled, trace};
-use std::io::{Error, ErrorKind};
 use std::sync::Arc;
-use subprocess::Exec;
-
-/// A simple `Codec` implementation that splits up data into lines.
-pub struct LinesCodec {}
-
-impl Encoder for LinesCodec {
-    type Item = String;
-    type Error = Error;
-
-    fn encode(&mut self, item: Self::Item, dst: &mut BytesMut) -> Result<(), Self::Error> {
-        dst.put(item);
-        Ok(())
-    }
-}
-
-impl Decoder for LinesCodec {
-    type Item = String;
-    type Error = Error;
-
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        match src.iter().position(|b| b == &b'\n') {
-            Some(pos) if !src.is_empty() => {
-                let buf = src.split_to(pos + 1);
-                String::from_utf8(buf.to_vec())
-                    .map(Some)
-                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))
-            }
-            _ if !src.is_empty() => {
-                let drained = src.take();
-                String::from_utf8(drained.to_vec())
-                    .map(Some)
-                    .map_err(|e| Error::new(ErrorKind::InvalidData, e))
-            }
-            _ => Ok(None),
-        }
-    }
-}
+use subprocess::{Exec, ExitStatus, Popen};
 
 pub(crate) struct ClassifiedInputStream {
     pub(crate) objects: InputStream,
@@ -119,70 +81,96 @@ impl InternalCommand {
 
         let result = trace_out_stream!(target: "nu::trace_stream::internal", source: &source, "output" = result);
         let mut result = result.values;
-
-        let mut stream = VecDeque::new();
-        while let Some(item) = result.next().await {
-            match item? {
-                ReturnSuccess::Action(action) => match action {
-                    CommandAction::ChangePath(path) => {
-                        context.shell_manager.set_path(path);
-                    }
-                    CommandAction::AddSpanSource(uuid, span_source) => {
-                        context.add_span_source(uuid, span_source);
-                    }
-                    CommandAction::Exit => std::process::exit(0), // TODO: save history.txt
-                    CommandAction::EnterHelpShell(value) => {
-                        match value {
-                            Tagged {
-                                item: Value::Primitive(Primitive::String(cmd)),
-                                tag,
-                            } => {
-                                context.shell_manager.insert_at_current(Box::new(
-                                    HelpShell::for_command(
-                                        Value::string(cmd).tagged(tag),
-                                        &context.registry(),
-                                    )?,
-                                ));
-                            }
-                            _ => {
-                                context.shell_manager.insert_at_current(Box::new(
-                                    HelpShell::index(&context.registry())?,
-                                ));
-                            }
-                        }
-                    }
-                    CommandAction::EnterValueShell(value) => {
-                        context
-                            .shell_manager
-                            .insert_at_current(Box::new(ValueShell::new(value)));
-                    }
-                    CommandAction::EnterShell(location) => {
-                        context.shell_manager.insert_at_current(Box::new(
-                            FilesystemShell::with_location(location, context.registry().clone())?,
-                        ));
-                    }
-                    CommandAction::PreviousShell => {
-                        context.shell_manager.prev();
-                    }
-                    CommandAction::NextShell => {
-                        context.shell_manager.next();
+        let mut context = context.clone();
+
+        // Values are handed on as they come, so the next stage can stop early. Actions are
+        // carried out on the way, and an error ends the stream and is reported once the
+        // pipeline has run.
+        let stream = async_stream_block! {
+            while let Some(item) = result.next().await {
+                let action = match item {
+                    Ok(ReturnSuccess::Value(v)) => {
+                        yield v;
+                        continue;
                     }
-                    CommandAction::LeaveShell => {
-                        context.shell_manager.remove_at_current();
-                        if context.shell_manager.is_empty() {
-                            std::process::exit(0); // TODO: save history.txt
-                        }
+                    Ok(ReturnSuccess::Action(action)) => action,
+                    Err(err) => {
+                        context.error(err);
+                        break;
                     }
-                },
+                };
 
-                ReturnSuccess::Value(v) => {
-                    stream.push_back(v);
+                if let Err(err) = perform(&mut context, action) {
+                    context.error(err);
+                    break;
                 }
             }
-        }
+        };
+
+        Ok(InputStream::from_stream(stream))
+    }
+}
 
-        Ok(stream.into())
+fn perform(context: &mut Context, action: CommandAction) -> Result<(), ShellError> {
+    match action {
+        CommandAction::ChangePath(path) => {
+            context.shell_manager.set_path(path);
+        }
+        CommandAction::AddSpanSource(uuid, span_source) => {
+            context.add_span_source(uuid, span_source);
+        }
+        CommandAction::Exit => std::process::exit(0), // TODO: save history.txt
+        CommandAction::EnterHelpShell(value) => match value {
+            Tagged {
+                item: Value::Primitive(Primitive::String(cmd)),
+                tag,
+            } => {
+                context
+                    .shell_manager
+                    .insert_at_current(Box::new(HelpShell::for_command(
+                        Value::string(cmd).tagged(tag),
+                        &context.registry(),
+                    )?));
+            }
+            _ => {
+                context
+                    .shell_manager
+                    .insert_at_current(Box::new(HelpShell::index(&context.registry())?));
+            }
+        },
+        CommandAction::EnterValueShell(value) => {
+            context
+                .shell_manager
+                .insert_at_current(Box::new(ValueShell::new(value)));
+        }
+        CommandAction::EnterRemoteShell(location) => {
+            context
+                .shell_manager
+                .insert_at_current(Box::new(RemoteShell::with_location(location)?));
+        }
+        CommandAction::EnterShell(location) => {
+            context
+                .shell_manager
+                .insert_at_current(Box::new(FilesystemShell::with_location(
+                    location,
+                    context.registry().clone(),
+                )?));
+        }
+        CommandAction::PreviousShell => {
+            context.shell_manager.prev();
+        }
+        CommandAction::NextShell => {
+            context.shell_manager.next();
+        }
+        CommandAction::LeaveShell => {
+            context.shell_manager.remove_at_current();
+            if context.shell_manager.is_empty() {
+                std::process::exit(0); // TODO: save history.txt
+            }
+        }
     }
+
+    Ok(())
 }
 
 pub(crate) struct ExternalCommand {
@@ -209,6 +197,11 @@ impl ExternalCommand {
         let inputs: Vec<Tagged<Value>> = input.objects.into_vec().await;
         let name_tag = self.name_tag.clone();
 
+        // Whatever fed this command has finished, so anything it failed with comes first
+        if let Some(err) = context.take_error() {
+            return Err(err);
+        }
+
         trace!(target: "nu::run::external", "-> {}", self.name);
         trace!(target: "nu::run::external", "inputs = {:?}", inputs);
 
@@ -273,7 +266,7 @@ impl ExternalCommand {
             }
         }
 
-        process = process.cwd(context.shell_manager.path());
+        process = process.cwd(context.shell_manager.filesystem_path());
 
         let mut process = match stream_next {
             StreamNext::Last => process,
@@ -291,35 +284,122 @@ impl ExternalCommand {
         match stream_next {
             StreamNext::Last => {
                 let _ = popen.detach();
-                loop {
+                let status = loop {
                     match popen.poll() {
                         None => {
                             let _ = std::thread::sleep(std::time::Duration::new(0, 100000000));
                         }
-                        _ => {
+                        Some(status) => {
                             let _ = popen.terminate();
-                            break;
+                            break status;
                         }
                     }
-                }
+                };
                 println!("");
+
+                wait_for_upstream(context);
+                if context.fail_on_exit_code {
+                    if let Some(err) = exit_error(status, name_tag) {
+                        context.error(err);
+                    }
+                }
+
                 Ok(ClassifiedInputStream::new())
             }
             StreamNext::External => {
                 let _ = popen.detach();
                 let stdout = popen.stdout.take().unwrap();
+
+                if context.fail_on_exit_code {
+                    context
+                        .upstream_externals
+                        .lock()
+                        .unwrap()
+                        .push((popen, name_tag));
+                }
+
                 Ok(ClassifiedInputStream::from_stdout(stdout))
             }
             StreamNext::Internal => {
                 let _ = popen.detach();
                 let stdout = popen.stdout.take().unwrap();
                 let file = futures::io::AllowStdIo::new(stdout);
-                let stream = Framed::new(file, LinesCodec {});
-                let stream = stream.map(move |line| Value::string(line.unwrap()).tagged(name_tag));
+                let mut context = context.clone();
+                // Each line is handed on as soon as it is read, so a downstream `first 5`
+                // stops reading without waiting for the rest of the output
+                let stream = async_stream_block! {
+                    let mut reader = futures::io::BufReader::new(file);
+
+                    loop {
+                        let mut bytes = vec![];
+
+                        let line = match reader.read_until(b'\n', &mut bytes).await {
+                            Ok(0) => break,
+                            Ok(_) => String::from_utf8(bytes).map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        };
+
+                        match line {
+                            Ok(line) => yield Value::string(line).tagged(name_tag),
+                            Err(reason) => {
+                                context.error(ShellError::labeled_error(
+                                    "Could not read the output of an external command",
+                                    reason,
+                                    name_tag,
+                                ));
+                                break;
+                            }
+                        }
+                    }
+
+                    wait_for_upstream(&mut context);
+                    if context.fail_on_exit_code {
+                        if let Some(err) = popen.wait().ok().and_then(|status| exit_error(status, name_tag)) {
+                            context.error(err);
+                        }
+                    }
+                };
                 Ok(ClassifiedInputStream::from_input_stream(
-                    stream.boxed() as BoxStream<'static, Tagged<Value>>
+                    InputStream::from_stream(stream),
                 ))
             }
         }
     }
 }
+
+/// Why a finished external command fails its line, if it does.
+fn exit_error(status: ExitStatus, name_tag: Tag) -> Option<ShellError> {
+    match status {
+        ExitStatus::Exited(0) => None,
+        ExitStatus::Exited(code) => Some(ShellError::labeled_error(
+            "External command failed",
+            format!("exited with code {}", code),
+            name_tag,
+        )),
+        _ => Some(ShellError::labeled_error(
+            "External command failed",
+            "did not exit normally",
+            name_tag,
+        )),
+    }
+}
+
+/// Wait for the external commands that fed the one that just finished, keeping an error for
+/// each that exited with one. Those stopped by a signal, like a `yes` whose reader went away,
+/// are left be.
+fn wait_for_upstream(context: &mut Context) {
+    let upstream: Vec<(Popen, Tag)> = context
+        .upstream_externals
+        .lock()
+        .unwrap()
+        .drain(..)
+        .collect();
+
+    for (mut popen, name_tag) in upstream {
+        if let Ok(status @ ExitStatus::Exited(_)) = popen.wait() {
+            if let Some(err) = exit_error(status, name_tag) {
+                context.error(err);
+            }
+        }
+    }
+}
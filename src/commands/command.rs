@@ -18,6 +18,9 @@ pub struct UnevaluatedCallInfo {
     pub args: hir::Call,
     pub source: Text,
     pub source_map: SourceMap,
+    /// The scope the pipeline runs in, like the block around `(echo $it)`
+    #[serde(skip)]
+    pub scope: Scope,
     pub name_tag: Tag,
 }
 
@@ -33,7 +36,8 @@ impl UnevaluatedCallInfo {
         registry: &registry::CommandRegistry,
         scope: &Scope,
     ) -> Result<CallInfo, ShellError> {
-        let args = self.args.evaluate(registry, scope, &self.source)?;
+        let scope = scope.clone().inside(&self.scope);
+        let args = self.args.evaluate(registry, &scope, &self.source)?;
 
         Ok(CallInfo {
             args,
@@ -381,6 +385,7 @@ pub enum CommandAction {
     EnterShell(String),
     EnterValueShell(Tagged<Value>),
     EnterHelpShell(Tagged<Value>),
+    EnterRemoteShell(String),
     PreviousShell,
     NextShell,
     LeaveShell,
@@ -401,6 +406,7 @@ impl ToDebug for CommandAction {
             CommandAction::EnterHelpShell(t) => {
                 write!(f, "action:enter-help-shell={:?}", t.debug())
             }
+            CommandAction::EnterRemoteShell(s) => write!(f, "action:enter-remote-shell={}", s),
             CommandAction::PreviousShell => write!(f, "action:previous-shell"),
             CommandAction::NextShell => write!(f, "action:next-shell"),
             CommandAction::LeaveShell => write!(f, "action:leave-shell"),
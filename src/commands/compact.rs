@@ -0,0 +1,56 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Compact;
+
+#[derive(Deserialize)]
+pub struct CompactArgs {
+    rest: Vec<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Compact {
+    fn name(&self) -> &str {
+        "compact"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("compact").rest(SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove empty values, or the rows where any of the given columns are missing or empty."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, compact)?.run()
+    }
+}
+
+fn is_compact(value: &Tagged<Value>, columns: &[Tagged<String>]) -> bool {
+    if columns.is_empty() {
+        return !value.is_empty();
+    }
+
+    columns.iter().all(
+        |column| match value.get_data_by_path(value.tag(), &column.item) {
+            Some(cell) => !cell.is_empty(),
+            None => false,
+        },
+    )
+}
+
+pub fn compact(
+    CompactArgs { rest: columns }: CompactArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = input
+        .values
+        .filter(move |value| futures::future::ready(is_compact(value, &columns)));
+
+    Ok(stream.from_input_stream())
+}
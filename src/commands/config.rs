@@ -1,5 +1,5 @@
 use crate::commands::WholeStreamCommand;
-use crate::data::{config, Value};
+use crate::data::{config, TaggedDictBuilder, Value};
 use crate::errors::ShellError;
 use crate::parser::hir::SyntaxShape;
 use crate::parser::registry::{self};
@@ -17,6 +17,7 @@ pub struct ConfigArgs {
     clear: Tagged<bool>,
     remove: Option<Tagged<String>>,
     path: Tagged<bool>,
+    check: Tagged<bool>,
 }
 
 impl WholeStreamCommand for Config {
@@ -32,6 +33,7 @@ impl WholeStreamCommand for Config {
             .named("remove", SyntaxShape::Any)
             .switch("clear")
             .switch("path")
+            .switch("check")
     }
 
     fn usage(&self) -> &str {
@@ -55,6 +57,7 @@ pub fn config(
         clear,
         remove,
         path,
+        check,
     }: ConfigArgs,
     RunnableContext { name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
@@ -113,6 +116,29 @@ pub fn config(
         return Ok(stream![Value::Primitive(Primitive::Path(path)).tagged(tag)].from_input_stream());
     }
 
+    if let Tagged { item: true, tag } = check {
+        let problems = config::check(&result);
+
+        if problems.is_empty() {
+            return Ok(
+                stream![Value::string("Configuration is valid").tagged(tag)].from_input_stream()
+            );
+        }
+
+        let rows: VecDeque<Tagged<Value>> = problems
+            .into_iter()
+            .map(|(key, severity, problem)| {
+                let mut row = TaggedDictBuilder::new(tag);
+                row.insert("key", Value::string(key));
+                row.insert("severity", Value::string(severity));
+                row.insert("problem", Value::string(problem));
+                row.into_tagged_value()
+            })
+            .collect();
+
+        return Ok(rows.from_input_stream());
+    }
+
     if let Some(v) = remove {
         let key = v.to_string();
 
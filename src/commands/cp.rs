@@ -12,6 +12,8 @@ pub struct CopyArgs {
     pub src: Tagged<PathBuf>,
     pub dst: Tagged<PathBuf>,
     pub recursive: Tagged<bool>,
+    #[serde(rename(deserialize = "dry-run"))]
+    pub dry_run: Tagged<bool>,
 }
 
 impl PerItemCommand for Cpy {
@@ -25,6 +27,7 @@ impl PerItemCommand for Cpy {
             .required("dst", SyntaxShape::Path)
             .named("file", SyntaxShape::Any)
             .switch("recursive")
+            .switch("dry-run")
     }
 
     fn usage(&self) -> &str {
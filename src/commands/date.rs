@@ -1,27 +1,40 @@
 use crate::data::{Dictionary, Value};
 use crate::errors::ShellError;
 use crate::prelude::*;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, FixedOffset, Local, Utc};
 
 use crate::commands::WholeStreamCommand;
 use crate::parser::registry::Signature;
+use chrono::format::{Item, StrftimeItems};
 use chrono::{Datelike, TimeZone, Timelike};
 use core::fmt::Display;
 use indexmap::IndexMap;
 
 pub struct Date;
 
+#[derive(Deserialize)]
+pub struct DateArgs {
+    action: Option<Tagged<String>>,
+    argument: Option<Tagged<String>>,
+    utc: bool,
+    local: bool,
+}
+
 impl WholeStreamCommand for Date {
     fn name(&self) -> &str {
         "date"
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("date").switch("utc").switch("local")
+        Signature::build("date")
+            .optional("action", SyntaxShape::String)
+            .optional("argument", SyntaxShape::String)
+            .switch("utc")
+            .switch("local")
     }
 
     fn usage(&self) -> &str {
-        "Get the current datetime."
+        "Get the current datetime, or the dates piped in, as a table (date to-table), a date (date now), text (date format \"%Y-%m-%d\") or in another timezone (date to-timezone \"+09:00\")."
     }
 
     fn run(
@@ -29,7 +42,7 @@ impl WholeStreamCommand for Date {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        date(args, registry)
+        args.process(registry, date)?.run()
     }
 }
 
@@ -55,21 +68,190 @@ where
     Value::Row(Dictionary::from(indexmap)).tagged(tag)
 }
 
-pub fn date(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zone {
+    Utc,
+    Local,
+    Fixed(FixedOffset),
+}
 
-    let mut date_out = VecDeque::new();
-    let tag = args.call_info.name_tag;
+/// Read a timezone written as `utc`, `local` or an offset like `+09:00` or `-0500`.
+fn parse_zone(zone: &str) -> Option<Zone> {
+    match zone.to_lowercase().as_str() {
+        "utc" | "z" => return Some(Zone::Utc),
+        "local" => return Some(Zone::Local),
+        _ => {}
+    }
 
-    let value = if args.has("utc") {
-        let utc: DateTime<Utc> = Utc::now();
-        date_to_value(utc, tag)
-    } else {
-        let local: DateTime<Local> = Local::now();
-        date_to_value(local, tag)
+    let sign = match zone.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
     };
 
-    date_out.push_back(value);
+    let digits: String = zone[1..].chars().filter(|c| *c != ':').collect();
+    if digits.len() != 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Zone::Fixed)
+}
+
+fn table_in(zone: Zone, date: DateTime<Utc>, tag: Tag) -> Tagged<Value> {
+    match zone {
+        Zone::Utc => date_to_value(date, tag),
+        Zone::Local => date_to_value(date.with_timezone(&Local), tag),
+        Zone::Fixed(offset) => date_to_value(date.with_timezone(&offset), tag),
+    }
+}
+
+fn format_in(zone: Zone, date: DateTime<Utc>, format: &str) -> String {
+    match zone {
+        Zone::Utc => date.format(format).to_string(),
+        Zone::Local => date.with_timezone(&Local).format(format).to_string(),
+        Zone::Fixed(offset) => date.with_timezone(&offset).format(format).to_string(),
+    }
+}
+
+enum Action {
+    Table(Zone),
+    Now,
+    Format(Zone, String),
+}
+
+impl Action {
+    fn apply(&self, date: DateTime<Utc>, tag: Tag) -> Tagged<Value> {
+        match self {
+            Action::Table(zone) => table_in(*zone, date, tag),
+            Action::Now => Value::Primitive(Primitive::Date(date)).tagged(tag),
+            Action::Format(zone, format) => {
+                Value::string(format_in(*zone, date, format)).tagged(tag)
+            }
+        }
+    }
+}
 
-    Ok(date_out.to_output_stream())
+fn action(
+    DateArgs {
+        action,
+        argument,
+        utc,
+        local,
+    }: DateArgs,
+    name: Tag,
+) -> Result<Action, ShellError> {
+    let zone = match (utc, local) {
+        (true, true) => {
+            return Err(ShellError::labeled_error(
+                "Can only use one timezone",
+                "use either --utc or --local",
+                name,
+            ))
+        }
+        (true, false) => Zone::Utc,
+        _ => Zone::Local,
+    };
+
+    let argument = |expected: &str| match &argument {
+        Some(argument) => Ok(argument.clone()),
+        None => Err(ShellError::labeled_error(
+            format!("date {} needs {}", action.as_ref().unwrap().item, expected),
+            format!("needs {}", expected),
+            action.as_ref().unwrap().tag(),
+        )),
+    };
+
+    let chosen = match &action {
+        Some(action) => action.item.as_str(),
+        None => "to-table",
+    };
+
+    match chosen {
+        "to-table" => Ok(Action::Table(zone)),
+        "now" => Ok(Action::Now),
+        "format" => {
+            let format = argument("a format like %Y-%m-%d")?;
+            if StrftimeItems::new(&format.item).any(|item| item == Item::Error) {
+                return Err(ShellError::labeled_error(
+                    "Invalid date format",
+                    "expected a format like %Y-%m-%d",
+                    format.tag(),
+                ));
+            }
+            Ok(Action::Format(zone, format.item))
+        }
+        "to-timezone" => {
+            let timezone = argument("a timezone like utc, local or +09:00")?;
+            match parse_zone(&timezone.item) {
+                Some(zone) => Ok(Action::Table(zone)),
+                None => Err(ShellError::labeled_error(
+                    "Invalid timezone",
+                    "expected utc, local or an offset like +09:00",
+                    timezone.tag(),
+                )),
+            }
+        }
+        _ => Err(ShellError::labeled_error(
+            "Unknown date action",
+            "expected now, format, to-timezone or to-table",
+            action.as_ref().map(|action| action.tag()).unwrap_or(name),
+        )),
+    }
+}
+
+pub fn date(args: DateArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
+    let name = context.name;
+    let action = action(args, name)?;
+    let input = context.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        // Without any dates piped in, the action applies to the current time
+        if values.is_empty() {
+            yield ReturnSuccess::value(action.apply(Utc::now(), name));
+        }
+
+        for value in values {
+            match value.item {
+                Value::Primitive(Primitive::Date(date)) => {
+                    yield ReturnSuccess::value(action.apply(date, value.tag));
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a date from pipeline",
+                    "requires date input",
+                    name,
+                    "value originates from here",
+                    value.tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_zone, Zone};
+    use chrono::FixedOffset;
+
+    #[test]
+    fn parses_named_zones_and_offsets() {
+        assert_eq!(parse_zone("UTC"), Some(Zone::Utc));
+        assert_eq!(parse_zone("local"), Some(Zone::Local));
+        assert_eq!(
+            parse_zone("+09:00"),
+            Some(Zone::Fixed(FixedOffset::east(9 * 3600)))
+        );
+        assert_eq!(
+            parse_zone("-0530"),
+            Some(Zone::Fixed(FixedOffset::west(5 * 3600 + 30 * 60)))
+        );
+        assert_eq!(parse_zone("tokyo"), None);
+        assert_eq!(parse_zone("+9"), None);
+    }
 }
@@ -0,0 +1,529 @@
+use crate::commands::from_json::from_json_string_to_value;
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+pub struct Db;
+
+#[derive(Deserialize)]
+pub struct DbArgs {
+    url: Tagged<String>,
+    sql: Tagged<String>,
+    rest: Vec<Tagged<Value>>,
+}
+
+impl WholeStreamCommand for Db {
+    fn name(&self) -> &str {
+        "db"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("db")
+            .required("url", SyntaxShape::String)
+            .required("sql", SyntaxShape::String)
+            .rest(SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Connect to a Postgres (postgres://) or MySQL (mysql://) database, run SQL and create a table of the results, with any further arguments bound to the $1, $2.. (Postgres) or ? (MySQL) parameters in order. Postgres connections require TLS unless the url sets an sslmode."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, db)?.run()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Backend {
+    Postgres,
+    MySql,
+}
+
+fn backend(url: &str) -> Option<Backend> {
+    let scheme = &url[..url.find("://")?];
+
+    match scheme.to_ascii_lowercase().as_str() {
+        "postgres" | "postgresql" => Some(Backend::Postgres),
+        "mysql" => Some(Backend::MySql),
+        _ => None,
+    }
+}
+
+/// A parameter as a plain value, before it is handed to either driver.
+#[derive(Debug, Clone, PartialEq)]
+enum Parameter {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Bytes(Vec<u8>),
+    Date(DateTime<Utc>),
+}
+
+fn db_parameter(value: &Tagged<Value>) -> Result<Parameter, ShellError> {
+    Ok(match value.item() {
+        Value::Primitive(Primitive::Nothing) => Parameter::Null,
+        Value::Primitive(Primitive::Boolean(b)) => Parameter::Bool(*b),
+        Value::Primitive(Primitive::Int(i)) => Parameter::Int(
+            i.tagged(value.tag)
+                .coerce_into("converting to an SQL parameter")?,
+        ),
+        Value::Primitive(Primitive::Decimal(d)) => match d.to_f64() {
+            Some(f) if f.is_finite() => Parameter::Float(f),
+            _ => {
+                return Err(ShellError::labeled_error(
+                    "Could not convert to an SQL parameter",
+                    "decimal is too large for a float parameter",
+                    value.tag,
+                ))
+            }
+        },
+        Value::Primitive(Primitive::Binary(b)) => Parameter::Bytes(b.clone()),
+        Value::Primitive(Primitive::Date(d)) => Parameter::Date(*d),
+        Value::Primitive(_) => Parameter::Text(value.as_string()?),
+        other => {
+            return Err(ShellError::type_error(
+                "a parameter that is a single value",
+                value.copy_tag(other.type_name()),
+            ))
+        }
+    })
+}
+
+fn text_or_binary(bytes: Vec<u8>) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(s) => Value::string(s),
+        Err(e) => Value::binary(e.into_bytes()),
+    }
+}
+
+/// Postgres urls without an `sslmode` are made to require TLS, so credentials never cross the
+/// network in the clear unless `sslmode=disable` (or `prefer`) is asked for.
+fn require_tls(url: &str) -> Result<String, String> {
+    let mut url = url::Url::parse(url).map_err(|e| format!("invalid postgres url: {}", e))?;
+
+    if !url.query_pairs().any(|(key, _)| key == "sslmode") {
+        url.query_pairs_mut().append_pair("sslmode", "require");
+    }
+
+    Ok(url.into_string())
+}
+
+/// A column value in Postgres' binary format, decoded by the column's type.
+struct PgCell(Value);
+
+impl<'a> postgres::types::FromSql<'a> for PgCell {
+    fn from_sql(
+        ty: &postgres::types::Type,
+        raw: &'a [u8],
+    ) -> Result<PgCell, Box<dyn std::error::Error + Sync + Send>> {
+        use chrono::NaiveDate;
+        use postgres::types::{FromSql, Type};
+
+        let value = match *ty {
+            Type::BOOL => Value::boolean(bool::from_sql(ty, raw)?),
+            Type::INT2 => Value::int(i16::from_sql(ty, raw)?),
+            Type::INT4 => Value::int(i32::from_sql(ty, raw)?),
+            Type::INT8 => Value::int(i64::from_sql(ty, raw)?),
+            Type::FLOAT4 => Value::Primitive(Primitive::from(f64::from(f32::from_sql(ty, raw)?))),
+            Type::FLOAT8 => Value::Primitive(Primitive::from(f64::from_sql(ty, raw)?)),
+            Type::NUMERIC => pg_numeric(raw)?,
+            Type::BYTEA => Value::binary(raw.to_vec()),
+            Type::TIMESTAMPTZ => {
+                Value::Primitive(Primitive::Date(DateTime::<Utc>::from_sql(ty, raw)?))
+            }
+            Type::TIMESTAMP => Value::Primitive(Primitive::Date(
+                Utc.from_utc_datetime(&NaiveDateTime::from_sql(ty, raw)?),
+            )),
+            Type::DATE => Value::Primitive(Primitive::Date(
+                Utc.from_utc_datetime(&NaiveDate::from_sql(ty, raw)?.and_hms(0, 0, 0)),
+            )),
+            Type::UUID => Value::string(pg_uuid(raw)?),
+            Type::JSON => pg_json(raw)?,
+            // jsonb is the json text behind a version byte
+            Type::JSONB => pg_json(raw.get(1..).unwrap_or_default())?,
+            _ if <&str>::accepts(ty) => Value::string(<&str>::from_sql(ty, raw)?),
+            _ => {
+                return Err(format!(
+                "{} columns can't be shown, cast the column to text in the query (column::text)",
+                ty.name()
+            )
+                .into())
+            }
+        };
+
+        Ok(PgCell(value))
+    }
+
+    fn accepts(_: &postgres::types::Type) -> bool {
+        true
+    }
+}
+
+/// Numerics are sent as base 10000 digits, with the weight of the first digit and the sign.
+fn pg_numeric(raw: &[u8]) -> Result<Value, Box<dyn std::error::Error + Sync + Send>> {
+    use bigdecimal::BigDecimal;
+    use num_bigint::BigInt;
+
+    let word = |index: usize| -> Result<u16, Box<dyn std::error::Error + Sync + Send>> {
+        match raw.get(index * 2..index * 2 + 2) {
+            Some(bytes) => Ok(u16::from(bytes[0]) << 8 | u16::from(bytes[1])),
+            None => Err("invalid numeric".into()),
+        }
+    };
+
+    let digits = word(0)? as i16;
+    let weight = word(1)? as i16;
+    let sign = word(2)?;
+
+    if sign == 0xC000 {
+        return Ok(Value::string("NaN"));
+    }
+
+    let mut unscaled = BigInt::from(0);
+    for index in 0..digits.max(0) as usize {
+        unscaled = unscaled * 10_000 + word(4 + index)?;
+    }
+    if sign == 0x4000 {
+        unscaled = -unscaled;
+    }
+
+    let exponent = (i64::from(weight) - i64::from(digits) + 1) * 4;
+    Ok(Value::Primitive(Primitive::Decimal(BigDecimal::new(
+        unscaled, -exponent,
+    ))))
+}
+
+fn pg_uuid(raw: &[u8]) -> Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    if raw.len() != 16 {
+        return Err("invalid uuid".into());
+    }
+
+    let hex: String = raw.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    ))
+}
+
+fn pg_json(raw: &[u8]) -> Result<Value, Box<dyn std::error::Error + Sync + Send>> {
+    let text = std::str::from_utf8(raw)?.to_string();
+    Ok(from_json_string_to_value(text, Tag::unknown())?.item)
+}
+
+fn postgres_rows(
+    url: &str,
+    sql: &str,
+    parameters: &[Parameter],
+    tag: Tag,
+) -> Result<Vec<Tagged<Value>>, String> {
+    use openssl::ssl::{SslConnector, SslMethod};
+    use postgres::types::{ToSql, Type};
+    use postgres::Client;
+    use postgres_openssl::MakeTlsConnector;
+    use std::convert::TryFrom;
+
+    let connector = SslConnector::builder(SslMethod::tls()).map_err(|e| e.to_string())?;
+    let mut client = Client::connect(&require_tls(url)?, MakeTlsConnector::new(connector.build()))
+        .map_err(|e| e.to_string())?;
+
+    let statement = client.prepare(sql).map_err(|e| e.to_string())?;
+
+    // Postgres won't bind an i64 to a smaller integer column, so ints take the placeholder's size
+    let parameters = parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| -> Result<Box<dyn ToSql>, String> {
+            let out_of_range =
+                |column: &str| format!("${} is out of range for {} parameters", index + 1, column);
+
+            Ok(match parameter {
+                Parameter::Null => Box::new(None::<String>),
+                Parameter::Bool(b) => Box::new(*b),
+                Parameter::Int(i) => match statement.params().get(index) {
+                    Some(&Type::INT2) => {
+                        Box::new(i16::try_from(*i).map_err(|_| out_of_range("smallint"))?)
+                    }
+                    Some(&Type::INT4) => {
+                        Box::new(i32::try_from(*i).map_err(|_| out_of_range("integer"))?)
+                    }
+                    _ => Box::new(*i),
+                },
+                Parameter::Float(f) => Box::new(*f),
+                Parameter::Text(s) => Box::new(s.clone()),
+                Parameter::Bytes(b) => Box::new(b.clone()),
+                Parameter::Date(d) => Box::new(*d),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let parameters: Vec<&dyn ToSql> = parameters.iter().map(|p| p.as_ref()).collect();
+
+    let rows = client
+        .query(&statement, &parameters)
+        .map_err(|e| e.to_string())?;
+
+    let mut out = vec![];
+    for row in rows.iter() {
+        let mut collected = TaggedDictBuilder::new(tag);
+
+        for (index, column) in row.columns().iter().enumerate() {
+            let value = match row.try_get::<_, Option<PgCell>>(index) {
+                Ok(Some(PgCell(value))) => value,
+                Ok(None) => Value::nothing(),
+                Err(e) => return Err(e.to_string()),
+            };
+
+            collected.insert(column.name(), value);
+        }
+
+        out.push(collected.into_tagged_value());
+    }
+
+    Ok(out)
+}
+
+/// MySQL allows dates chrono can't represent, such as the `0000-00-00` zero date. The zero date
+/// stands for no date at all; any other invalid date is kept as its text.
+fn mysql_date(
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    micros: u32,
+) -> Value {
+    use chrono::NaiveDate;
+
+    let date = NaiveDate::from_ymd_opt(i32::from(year), u32::from(month), u32::from(day)).and_then(
+        |date| {
+            date.and_hms_micro_opt(
+                u32::from(hour),
+                u32::from(minute),
+                u32::from(second),
+                micros,
+            )
+        },
+    );
+
+    match date {
+        Some(date) => Value::Primitive(Primitive::Date(Utc.from_utc_datetime(&date))),
+        None if year == 0 && month == 0 && day == 0 => Value::nothing(),
+        None => Value::string(format!(
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            year, month, day, hour, minute, second
+        )),
+    }
+}
+
+fn mysql_rows(
+    url: &str,
+    sql: &str,
+    parameters: &[Parameter],
+    tag: Tag,
+) -> Result<Vec<Tagged<Value>>, String> {
+    use mysql::Value as MyValue;
+
+    let pool = mysql::Pool::new(url).map_err(|e| e.to_string())?;
+
+    let parameters: Vec<MyValue> = parameters
+        .iter()
+        .map(|parameter| match parameter {
+            Parameter::Null => MyValue::NULL,
+            Parameter::Bool(b) => MyValue::Int(*b as i64),
+            Parameter::Int(i) => MyValue::Int(*i),
+            Parameter::Float(f) => MyValue::Float(*f),
+            Parameter::Text(s) => MyValue::Bytes(s.clone().into_bytes()),
+            Parameter::Bytes(b) => MyValue::Bytes(b.clone()),
+            Parameter::Date(d) => MyValue::from(d.naive_utc()),
+        })
+        .collect();
+
+    let result = if parameters.is_empty() {
+        pool.prep_exec(sql, ())
+    } else {
+        pool.prep_exec(sql, parameters)
+    }
+    .map_err(|e| e.to_string())?;
+
+    let mut out = vec![];
+    for row in result {
+        let row = row.map_err(|e| e.to_string())?;
+        let columns = row.columns();
+        let mut collected = TaggedDictBuilder::new(tag);
+
+        for (column, value) in columns.iter().zip(row.unwrap()) {
+            let value = match value {
+                MyValue::NULL => Value::nothing(),
+                MyValue::Int(i) => Value::int(i),
+                MyValue::UInt(u) => Value::int(u),
+                MyValue::Float(f) => Value::Primitive(Primitive::from(f)),
+                MyValue::Bytes(b) => text_or_binary(b),
+                MyValue::Date(year, month, day, hour, minute, second, micros) => {
+                    mysql_date(year, month, day, hour, minute, second, micros)
+                }
+                MyValue::Time(negative, days, hours, minutes, seconds, _) => {
+                    let total = i64::from(days) * 86_400
+                        + i64::from(hours) * 3_600
+                        + i64::from(minutes) * 60
+                        + i64::from(seconds);
+                    Value::duration(if negative { -total } else { total })
+                }
+            };
+
+            collected.insert(column.name_str().to_string(), value);
+        }
+
+        out.push(collected.into_tagged_value());
+    }
+
+    Ok(out)
+}
+
+fn db(
+    DbArgs { url, sql, rest }: DbArgs,
+    RunnableContext { .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let backend = match backend(&url.item) {
+        Some(backend) => backend,
+        None => {
+            return Err(ShellError::labeled_error(
+                "Unsupported database url",
+                "expected a postgres:// or mysql:// url",
+                url.tag(),
+            ))
+        }
+    };
+
+    let parameters = rest
+        .iter()
+        .map(db_parameter)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let stream = async_stream_block! {
+        let rows = match backend {
+            Backend::Postgres => postgres_rows(&url.item, &sql.item, &parameters, sql.tag()),
+            Backend::MySql => mysql_rows(&url.item, &sql.item, &parameters, sql.tag()),
+        };
+
+        match rows {
+            Ok(rows) => {
+                for row in rows {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+            Err(reason) => yield Err(ShellError::labeled_error(
+                "Could not run query",
+                reason,
+                sql.tag(),
+            )),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{backend, db_parameter, mysql_date, require_tls, Backend, Parameter};
+    use crate::data::Value;
+    use crate::prelude::*;
+
+    #[test]
+    fn picks_the_backend_from_the_url_scheme() {
+        assert_eq!(backend("postgres://localhost/nu"), Some(Backend::Postgres));
+        assert_eq!(
+            backend("PostgreSQL://user@localhost:5432/nu"),
+            Some(Backend::Postgres)
+        );
+        assert_eq!(backend("mysql://root@localhost/nu"), Some(Backend::MySql));
+        assert_eq!(backend("sqlite://nu.db"), None);
+        assert_eq!(backend("localhost/nu"), None);
+    }
+
+    #[test]
+    fn converts_values_to_parameters() {
+        let tag = Tag::unknown();
+
+        assert_eq!(
+            db_parameter(&Value::int(42).tagged(tag)).unwrap(),
+            Parameter::Int(42)
+        );
+        assert_eq!(
+            db_parameter(&Value::string("nu").tagged(tag)).unwrap(),
+            Parameter::Text("nu".to_string())
+        );
+        assert_eq!(
+            db_parameter(&Value::nothing().tagged(tag)).unwrap(),
+            Parameter::Null
+        );
+        assert!(db_parameter(&Value::Table(vec![]).tagged(tag)).is_err());
+    }
+
+    #[test]
+    fn postgres_urls_require_tls_unless_they_say_otherwise() {
+        assert_eq!(
+            require_tls("postgres://localhost/nu"),
+            Ok("postgres://localhost/nu?sslmode=require".to_string())
+        );
+        assert_eq!(
+            require_tls("postgres://localhost/nu?connect_timeout=5"),
+            Ok("postgres://localhost/nu?connect_timeout=5&sslmode=require".to_string())
+        );
+        assert_eq!(
+            require_tls("postgres://localhost/nu?sslmode=disable"),
+            Ok("postgres://localhost/nu?sslmode=disable".to_string())
+        );
+        assert_eq!(
+            require_tls("postgres://localhost/nu?connect_timeout=5&sslmode=prefer"),
+            Ok("postgres://localhost/nu?connect_timeout=5&sslmode=prefer".to_string())
+        );
+        assert_eq!(
+            require_tls("postgres://localhost/nu?application_name=no_sslmode=here"),
+            Ok(
+                "postgres://localhost/nu?application_name=no_sslmode=here&sslmode=require"
+                    .to_string()
+            )
+        );
+        assert!(require_tls("postgres://[bad/nu").is_err());
+    }
+
+    #[test]
+    fn mysql_zero_dates_are_nothing() {
+        assert_eq!(mysql_date(0, 0, 0, 0, 0, 0, 0), Value::nothing());
+        assert_eq!(
+            mysql_date(2019, 2, 30, 0, 0, 0, 0),
+            Value::string("2019-02-30 00:00:00")
+        );
+    }
+
+    #[test]
+    fn decodes_postgres_numerics_and_uuids() {
+        use super::{pg_numeric, pg_uuid};
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+
+        // -1234.5678: two base 10000 digits, the first of weight 0
+        let raw = [0, 2, 0, 0, 0x40, 0, 0, 4, 0x04, 0xD2, 0x16, 0x2E];
+        assert_eq!(
+            pg_numeric(&raw).unwrap(),
+            Value::decimal(BigDecimal::from_str("-1234.5678").unwrap())
+        );
+
+        let raw: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            pg_uuid(&raw).unwrap(),
+            "00010203-0405-0607-0809-0a0b0c0d0e0f"
+        );
+    }
+}
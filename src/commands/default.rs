@@ -0,0 +1,64 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Default;
+
+#[derive(Deserialize)]
+pub struct DefaultArgs {
+    column: Tagged<String>,
+    value: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Default {
+    fn name(&self) -> &str {
+        "default"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("default")
+            .required("column", SyntaxShape::String)
+            .required("value", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Give a column a value in the rows where it is missing or nothing (eg. default owner \"unknown\")."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, default)?.run()
+    }
+}
+
+pub fn default(
+    DefaultArgs { column, value }: DefaultArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = input.values.map(move |row| {
+        let missing = match row.get_data_by_path(row.tag(), &column.item) {
+            Some(cell) => cell.is_nothing(),
+            None => true,
+        };
+
+        if !missing {
+            return ReturnSuccess::value(row);
+        }
+
+        match row.insert_data_at_path(row.tag(), &column.item, value.item.clone()) {
+            Some(row) => ReturnSuccess::value(row),
+            None => Err(ShellError::labeled_error_with_secondary(
+                "Could not set default",
+                "no place to put this column",
+                column.tag(),
+                "in this row",
+                row.tag(),
+            )),
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,111 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder};
+use crate::errors::ShellError;
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct Describe;
+
+#[derive(Deserialize)]
+pub struct DescribeArgs {
+    detailed: bool,
+}
+
+impl WholeStreamCommand for Describe {
+    fn name(&self) -> &str {
+        "describe"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("describe").switch("detailed")
+    }
+
+    fn usage(&self) -> &str {
+        "Describes the columns of the table and the type of their values."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, describe)?.run()
+    }
+}
+
+#[derive(Default)]
+struct ColumnProfile {
+    types: Vec<String>,
+    nulls: i64,
+    sample: Option<Tagged<Value>>,
+}
+
+impl ColumnProfile {
+    fn observe(&mut self, value: Option<&Tagged<Value>>) {
+        match value {
+            None
+            | Some(Tagged {
+                item: Value::Primitive(Primitive::Nothing),
+                ..
+            }) => self.nulls += 1,
+            Some(value) => {
+                let type_name = value.type_name();
+                if !self.types.contains(&type_name) {
+                    self.types.push(type_name);
+                }
+                if self.sample.is_none() {
+                    self.sample = Some(value.clone());
+                }
+            }
+        }
+    }
+
+    fn type_name(&self) -> String {
+        match self.types.len() {
+            0 => "nothing".to_string(),
+            1 => self.types[0].clone(),
+            _ => "any".to_string(),
+        }
+    }
+}
+
+pub fn describe(
+    DescribeArgs { detailed }: DescribeArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut columns: IndexMap<String, ColumnProfile> = IndexMap::new();
+
+        for value in values.iter() {
+            for column in value.data_descriptors() {
+                columns.entry(column).or_insert_with(ColumnProfile::default);
+            }
+        }
+
+        for value in values.iter() {
+            for (column, profile) in columns.iter_mut() {
+                profile.observe(value.get_data_by_key(column));
+            }
+        }
+
+        for (column, profile) in columns {
+            let mut row = TaggedDictBuilder::new(name);
+            row.insert("column", Value::string(column));
+            row.insert("type", Value::string(profile.type_name()));
+
+            if detailed {
+                row.insert("nulls", Value::int(profile.nulls));
+                match profile.sample {
+                    Some(sample) => row.insert_tagged("sample", sample),
+                    None => row.insert("sample", Value::nothing()),
+                }
+            }
+
+            yield ReturnSuccess::value(row.into_tagged_value());
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,178 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+
+pub struct DetectColumns;
+
+#[derive(Deserialize)]
+pub struct DetectColumnsArgs {
+    #[serde(rename(deserialize = "no-headers"))]
+    no_headers: bool,
+}
+
+const SAMPLE_LINES: usize = 10;
+const MIN_GAP: usize = 2;
+
+impl WholeStreamCommand for DetectColumns {
+    fn name(&self) -> &str {
+        "detect-columns"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("detect-columns").switch("no-headers")
+    }
+
+    fn usage(&self) -> &str {
+        "Split aligned text into columns, inferring the boundaries from the first lines."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, detect_columns)?.run()
+    }
+}
+
+/// Find the character ranges of each column. Columns are separated by at least `MIN_GAP`
+/// positions that are blank in every sampled line, so right-aligned values still land in the
+/// right column and headers like `MOUNTED ON` stay whole.
+fn column_boundaries(lines: &[&str]) -> Vec<usize> {
+    let sample: Vec<Vec<char>> = lines
+        .iter()
+        .take(SAMPLE_LINES)
+        .map(|line| line.chars().collect())
+        .collect();
+
+    let width = sample.iter().map(|line| line.len()).max().unwrap_or(0);
+
+    let mut starts = vec![];
+    let mut gap = 0;
+
+    for position in 0..width {
+        let blank = sample.iter().all(|line| {
+            line.get(position)
+                .map(|c| c.is_whitespace())
+                .unwrap_or(true)
+        });
+
+        if blank {
+            gap += 1;
+        } else {
+            if starts.is_empty() || gap >= MIN_GAP {
+                starts.push(position);
+            }
+
+            gap = 0;
+        }
+    }
+
+    starts
+}
+
+fn split_at_boundaries(line: &str, starts: &[usize]) -> Vec<String> {
+    let chars: Vec<char> = line.chars().collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(idx, start)| {
+            let end = match starts.get(idx + 1) {
+                Some(next) => *next,
+                None => chars.len(),
+            };
+
+            if *start >= chars.len() {
+                String::new()
+            } else {
+                chars[*start..end.min(chars.len())]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            }
+        })
+        .collect()
+}
+
+pub fn detect_columns_string_to_value(
+    s: String,
+    no_headers: bool,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
+    let tag = tag.into();
+    let lines: Vec<&str> = s.lines().filter(|l| !l.trim().is_empty()).collect();
+    let starts = column_boundaries(&lines);
+
+    let (headers, body) = if no_headers {
+        let headers = (1..=starts.len())
+            .map(|idx| format!("Column{}", idx))
+            .collect::<Vec<_>>();
+        (headers, &lines[..])
+    } else if let Some((first, rest)) = lines.split_first() {
+        (split_at_boundaries(first, &starts), rest)
+    } else {
+        (vec![], &lines[..])
+    };
+
+    let rows = body
+        .iter()
+        .map(|line| {
+            let mut row = TaggedDictBuilder::new(tag);
+
+            for (header, cell) in headers.iter().zip(split_at_boundaries(line, &starts)) {
+                row.insert_tagged(
+                    header.clone(),
+                    Value::Primitive(Primitive::String(cell)).tagged(tag),
+                );
+            }
+
+            row.into_tagged_value()
+        })
+        .collect();
+
+    Value::Table(rows).tagged(tag)
+}
+
+fn detect_columns(
+    DetectColumnsArgs { no_headers }: DetectColumnsArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    name_tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+
+            }
+        }
+
+        match detect_columns_string_to_value(concat_string, no_headers, name_tag) {
+            Tagged { item: Value::Table(list), .. } => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            x => yield ReturnSuccess::value(x),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,55 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::parser::CommandRegistry;
+use crate::prelude::*;
+
+pub struct Drop;
+
+#[derive(Deserialize)]
+pub struct DropArgs {
+    rows: Option<Tagged<u64>>,
+}
+
+impl WholeStreamCommand for Drop {
+    fn name(&self) -> &str {
+        "drop"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("drop").optional("rows", SyntaxShape::Number)
+    }
+
+    fn usage(&self) -> &str {
+        "Remove the last number of rows (1 by default)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, drop)?.run()
+    }
+}
+
+fn drop(DropArgs { rows }: DropArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
+    let rows = rows.map(|rows| rows.item as usize).unwrap_or(1);
+
+    let stream = async_stream_block! {
+        let mut values = context.input.values;
+        // Only the rows that may still turn out to be among the last are held back
+        let mut held: VecDeque<Tagged<Value>> = VecDeque::new();
+
+        while let Some(value) = values.next().await {
+            held.push_back(value);
+
+            if held.len() > rows {
+                if let Some(row) = held.pop_front() {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
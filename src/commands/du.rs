@@ -0,0 +1,241 @@
+use crate::commands::command::RunnablePerItemContext;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::parser::registry::{CommandRegistry, Signature};
+use crate::prelude::*;
+use glob::Pattern;
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+
+pub struct Du;
+
+#[derive(Deserialize)]
+pub struct DuArgs {
+    path: Option<Tagged<PathBuf>>,
+    #[serde(rename(deserialize = "max-depth"))]
+    max_depth: Option<Tagged<u64>>,
+    exclude: Option<Tagged<String>>,
+    #[serde(rename(deserialize = "follow-symlinks"))]
+    follow_symlinks: bool,
+}
+
+impl PerItemCommand for Du {
+    fn name(&self) -> &str {
+        "du"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("du")
+            .optional("path", SyntaxShape::Pattern)
+            .named("max-depth", SyntaxShape::Int)
+            .named("exclude", SyntaxShape::Pattern)
+            .switch("follow-symlinks")
+    }
+
+    fn usage(&self) -> &str {
+        "Find the disk usage of the current or given path, with the sizes of the directories inside."
+    }
+
+    fn run(
+        &self,
+        call_info: &CallInfo,
+        _registry: &CommandRegistry,
+        raw_args: &RawCommandArgs,
+        _input: Tagged<Value>,
+    ) -> Result<OutputStream, ShellError> {
+        call_info.process(&raw_args.shell_manager, du)?.run()
+    }
+}
+
+struct Walk {
+    exclude: Option<Pattern>,
+    max_depth: Option<u64>,
+    follow_symlinks: bool,
+    tag: Tag,
+}
+
+struct Usage {
+    apparent: u64,
+    physical: u64,
+}
+
+impl Usage {
+    fn of(metadata: &Metadata) -> Usage {
+        Usage {
+            apparent: metadata.len(),
+            physical: physical_size(metadata),
+        }
+    }
+
+    fn add(&mut self, other: &Usage) {
+        self.apparent += other.apparent;
+        self.physical += other.physical;
+    }
+}
+
+#[cfg(unix)]
+fn physical_size(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    // st_blocks is always counted in 512 byte units, whatever the filesystem's block size
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn physical_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// What tells directories apart, so one reached again through a symlink isn't walked twice.
+#[cfg(unix)]
+type DirId = (u64, u64);
+
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+#[cfg(unix)]
+fn dir_id(_path: &Path, metadata: &Metadata) -> Option<DirId> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_id(path: &Path, _metadata: &Metadata) -> Option<DirId> {
+    std::fs::canonicalize(path).ok()
+}
+
+impl Walk {
+    fn metadata(&self, path: &Path) -> std::io::Result<Metadata> {
+        if self.follow_symlinks {
+            std::fs::metadata(path)
+        } else {
+            std::fs::symlink_metadata(path)
+        }
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        match (&self.exclude, path.file_name()) {
+            (Some(pattern), Some(name)) => pattern.matches(&name.to_string_lossy()),
+            _ => false,
+        }
+    }
+
+    /// Total up `path`, listing the directories inside it until `max_depth` is reached.
+    /// Entries that can't be read are skipped rather than failing the whole walk, and so are
+    /// directories in `visited`, which a followed symlink can lead back to.
+    fn entry(
+        &self,
+        path: &Path,
+        display: &Path,
+        depth: u64,
+        visited: &mut HashSet<DirId>,
+    ) -> Option<(Usage, Tagged<Value>)> {
+        let metadata = self.metadata(path).ok()?;
+        let mut usage = Usage::of(&metadata);
+        let mut directories = vec![];
+
+        if metadata.is_dir() {
+            if !visited.insert(dir_id(path, &metadata)?) {
+                return None;
+            }
+
+            let entries = std::fs::read_dir(path).ok()?;
+
+            for entry in entries.filter_map(Result::ok) {
+                let child = entry.path();
+
+                if self.is_excluded(&child) {
+                    continue;
+                }
+
+                if let Ok(child_metadata) = self.metadata(&child) {
+                    if child_metadata.is_dir() {
+                        let child_display = display.join(entry.file_name());
+                        if let Some((child_usage, row)) =
+                            self.entry(&child, &child_display, depth + 1, visited)
+                        {
+                            usage.add(&child_usage);
+                            if self.max_depth.map(|max| depth < max).unwrap_or(true) {
+                                directories.push(row);
+                            }
+                        }
+                    } else {
+                        usage.add(&Usage::of(&child_metadata));
+                    }
+                }
+            }
+        }
+
+        let mut dict = TaggedDictBuilder::new(self.tag);
+        dict.insert("path", Value::string(display.to_string_lossy()));
+        dict.insert("apparent", Value::bytes(usage.apparent));
+        dict.insert("physical", Value::bytes(usage.physical));
+        dict.insert("directories", Value::Table(directories));
+
+        Some((usage, dict.into_tagged_value()))
+    }
+}
+
+fn du(args: DuArgs, context: &RunnablePerItemContext) -> Result<OutputStream, ShellError> {
+    let cwd = context.cwd();
+    let tag = context.name;
+
+    let exclude = match &args.exclude {
+        Some(exclude) => Some(Pattern::new(&exclude.item).map_err(|_| {
+            ShellError::labeled_error("Invalid pattern", "invalid pattern", exclude.tag())
+        })?),
+        None => None,
+    };
+
+    // With no path, report each entry of the current directory like `ls` would
+    let pattern = match &args.path {
+        Some(path) => cwd.join(&path.item),
+        None => cwd.join("*"),
+    };
+
+    let entries = glob::glob(&pattern.to_string_lossy()).map_err(|_| match &args.path {
+        Some(path) => ShellError::labeled_error("Invalid pattern", "invalid pattern", path.tag()),
+        None => ShellError::string("Invalid pattern."),
+    })?;
+
+    let walk = Walk {
+        exclude,
+        max_depth: args.max_depth.map(|depth| depth.item),
+        follow_symlinks: args.follow_symlinks,
+        tag,
+    };
+
+    let stream = futures::stream::iter(entries.filter_map(Result::ok)).filter_map(move |path| {
+        let display = path.strip_prefix(&cwd).unwrap_or(&path).to_path_buf();
+        let row = if walk.is_excluded(&path) {
+            None
+        } else {
+            walk.entry(&path, &display, 0, &mut HashSet::new())
+                .map(|(_, row)| ReturnSuccess::value(row))
+        };
+
+        futures::future::ready(row)
+    });
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Walk;
+    use crate::prelude::*;
+    use std::path::Path;
+
+    #[test]
+    fn excludes_by_file_name() {
+        let walk = Walk {
+            exclude: Some(glob::Pattern::new("*.lock").unwrap()),
+            max_depth: None,
+            follow_symlinks: false,
+            tag: Tag::unknown(),
+        };
+
+        assert!(walk.is_excluded(Path::new("nushell/Cargo.lock")));
+        assert!(!walk.is_excluded(Path::new("nushell/Cargo.toml")));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::evaluate::Scope;
+use crate::prelude::*;
+
+pub struct Each;
+
+#[derive(Deserialize)]
+pub struct EachArgs {
+    block: value::Block,
+    numbered: Tagged<bool>,
+}
+
+impl WholeStreamCommand for Each {
+    fn name(&self) -> &str {
+        "each"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("each")
+            .required("block", SyntaxShape::Block)
+            .switch("numbered")
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block on each row of the table, flattening lists the block returns. With --numbered, the row index is available as $index."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, each)?.run()
+    }
+}
+
+pub fn each(
+    EachArgs { block, numbered }: EachArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = input
+        .values
+        .enumerate()
+        .map(move |(index, item)| {
+            let result = if numbered.item {
+                let index = Value::int(index).tagged(item.tag());
+                block.invoke_in_scope(&Scope::new(item).with_var("index", index), &commands)
+            } else {
+                block.invoke(&item, &commands)
+            };
+
+            let mut out = VecDeque::new();
+
+            // A block producing a list contributes each of its items to the output
+            match result {
+                Ok(Tagged {
+                    item: Value::Table(rows),
+                    ..
+                }) => {
+                    for row in rows {
+                        out.push_back(ReturnSuccess::value(row));
+                    }
+                }
+                Ok(value) => out.push_back(ReturnSuccess::value(value)),
+                Err(err) => out.push_back(Err(err)),
+            }
+
+            out
+        })
+        .flatten();
+
+    Ok(stream.to_output_stream())
+}
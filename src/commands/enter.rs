@@ -1,4 +1,5 @@
 use crate::commands::command::CommandAction;
+use crate::commands::remote::Remote;
 use crate::commands::PerItemCommand;
 use crate::commands::UnevaluatedCallInfo;
 use crate::data::meta::Span;
@@ -19,7 +20,7 @@ impl PerItemCommand for Enter {
     }
 
     fn usage(&self) -> &str {
-        "Create a new shell and begin at this path."
+        "Create a new shell and begin at this path, or browse the JSON resources under an http(s) url."
     }
 
     fn run(
@@ -55,6 +56,13 @@ impl PerItemCommand for Enter {
                         )))]
                         .into())
                     }
+                } else if let Some(Remote::Http(_)) = Remote::parse(&location) {
+                    Ok(
+                        vec![Ok(ReturnSuccess::Action(CommandAction::EnterRemoteShell(
+                            location_clone,
+                        )))]
+                        .into(),
+                    )
                 } else if PathBuf::from(location).is_dir() {
                     Ok(vec![Ok(ReturnSuccess::Action(CommandAction::EnterShell(
                         location_clone,
@@ -105,6 +113,7 @@ impl PerItemCommand for Enter {
                                                 source: raw_args.call_info.source,
                                                 source_map: raw_args.call_info.source_map,
                                                 name_tag: raw_args.call_info.name_tag,
+                                                scope: raw_args.call_info.scope,
                                             },
                                         };
                                         let mut result = converter.run(
@@ -2,13 +2,20 @@ use crate::commands::UnevaluatedCallInfo;
 use crate::context::SpanSource;
 use crate::data::meta::Span;
 use crate::data::Value;
+use crate::data::{Primitive, TaggedDictBuilder};
 use crate::errors::ShellError;
 use crate::parser::hir::SyntaxShape;
 use crate::parser::registry::Signature;
 use crate::prelude::*;
+use base64::encode;
+use futures::future::BoxFuture;
+use futures_timer::TryFutureExt;
 use mime::Mime;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
+use surf::http::header::{HeaderMap, HeaderName, HeaderValue};
+use surf::middleware::{HttpClient, Middleware, Next, Request, Response};
 use surf::mime;
 use uuid::Uuid;
 pub struct Fetch;
@@ -22,10 +29,16 @@ impl PerItemCommand for Fetch {
         Signature::build(self.name())
             .required("path", SyntaxShape::Path)
             .switch("raw")
+            .named("header", SyntaxShape::Any)
+            .named("user", SyntaxShape::Any)
+            .named("password", SyntaxShape::Any)
+            .named("bearer", SyntaxShape::String)
+            .named("timeout", SyntaxShape::Int)
+            .switch("full")
     }
 
     fn usage(&self) -> &str {
-        "Load from a URL into a cell, convert to table if possible (avoid by appending '--raw')"
+        "Load from a URL into a cell, convert to table if possible (avoid by appending '--raw'). Pass --header \"Name: value\" (or a list of them) to send headers, --user/--password or --bearer to authenticate, and --full for the status and headers."
     }
 
     fn run(
@@ -55,18 +68,20 @@ fn run(
     let path_str = path_buf.display().to_string();
     let path_span = path.span();
     let has_raw = call_info.args.has("raw");
+    let has_full = call_info.args.has("full");
+    let options = FetchOptions::from_call_info(call_info)?;
     let registry = registry.clone();
     let raw_args = raw_args.clone();
 
     let stream = async_stream_block! {
 
-        let result = fetch(&path_str, path_span).await;
+        let result = fetch(&path_str, path_span, &options).await;
 
         if let Err(e) = result {
             yield Err(e);
             return;
         }
-        let (file_extension, contents, contents_tag, span_source) = result.unwrap();
+        let (file_extension, contents, contents_tag, span_source, response) = result.unwrap();
 
         let file_extension = if has_raw {
             None
@@ -85,6 +100,7 @@ fn run(
         }
 
         let tagged_contents = contents.tagged(contents_tag);
+        let mut body: Vec<Tagged<Value>> = vec![];
 
         if let Some(extension) = file_extension {
             let command_name = format!("from-{}", extension);
@@ -108,31 +124,210 @@ fn run(
                 for res in result_vec {
                     match res {
                         Ok(ReturnSuccess::Value(Tagged { item: Value::Table(list), ..})) => {
-                            for l in list {
-                                yield Ok(ReturnSuccess::Value(l));
-                            }
+                            body.extend(list);
                         }
                         Ok(ReturnSuccess::Value(Tagged { item, .. })) => {
-                            yield Ok(ReturnSuccess::Value(Tagged { item, tag: contents_tag }));
+                            body.push(Tagged { item, tag: contents_tag });
                         }
                         x => yield x,
                     }
                 }
             } else {
-                yield ReturnSuccess::value(tagged_contents);
+                body.push(tagged_contents);
             }
         } else {
-            yield ReturnSuccess::value(tagged_contents);
+            body.push(tagged_contents);
+        }
+
+        if has_full {
+            let body = if body.len() == 1 {
+                body.remove(0)
+            } else {
+                Value::Table(body).tagged(contents_tag)
+            };
+
+            yield ReturnSuccess::value(response.into_row(body, contents_tag));
+        } else {
+            for value in body {
+                yield ReturnSuccess::value(value);
+            }
         }
     };
 
     Ok(stream.to_output_stream())
 }
 
-pub async fn fetch(
+#[derive(Default)]
+pub(crate) struct FetchOptions {
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl FetchOptions {
+    fn from_call_info(call_info: &CallInfo) -> Result<FetchOptions, ShellError> {
+        let mut options = FetchOptions::default();
+
+        if let Some(header) = call_info.args.get("header") {
+            let headers = match &header.item {
+                Value::Table(list) => list.iter().collect(),
+                _ => vec![header],
+            };
+
+            for header in headers {
+                let (name, value) = parse_header(header)?;
+                options.headers.append(name, value);
+            }
+        }
+
+        let user = call_info
+            .args
+            .get("user")
+            .map(|x| x.as_string())
+            .transpose()?;
+        let password = call_info
+            .args
+            .get("password")
+            .map(|x| x.as_string())
+            .transpose()?;
+
+        match (user, password) {
+            (Some(user), password) => {
+                let login = encode(&format!("{}:{}", user, password.unwrap_or_default()));
+                options.headers.insert(
+                    "Authorization",
+                    header_value(&format!("Basic {}", login), call_info.name_tag)?,
+                );
+            }
+            (None, Some(_)) => {
+                return Err(ShellError::labeled_error(
+                    "Password without user",
+                    "--password also needs --user",
+                    call_info.name_tag,
+                ))
+            }
+            (None, None) => {}
+        }
+
+        if let Some(bearer) = call_info.args.get("bearer") {
+            options.headers.insert(
+                "Authorization",
+                header_value(&format!("Bearer {}", bearer.as_string()?), bearer.tag())?,
+            );
+        }
+
+        if let Some(timeout) = call_info.args.get("timeout") {
+            options.timeout = match timeout.item() {
+                Value::Primitive(Primitive::Int(seconds)) => match seconds.to_u64() {
+                    Some(seconds) => Some(Duration::from_secs(seconds)),
+                    None => {
+                        return Err(ShellError::labeled_error(
+                            "Invalid timeout",
+                            "expected a positive number of seconds",
+                            timeout.tag(),
+                        ))
+                    }
+                },
+                other => {
+                    return Err(ShellError::type_error(
+                        "number of seconds",
+                        timeout.copy_tag(other.type_name()),
+                    ))
+                }
+            };
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_header(header: &Tagged<Value>) -> Result<(HeaderName, HeaderValue), ShellError> {
+    let text = header.as_string()?;
+    let mut parts = text.splitn(2, ':');
+
+    let invalid = || {
+        ShellError::labeled_error(
+            "Invalid header",
+            "expected \"Name: value\"",
+            header.tag(),
+        )
+    };
+
+    match (parts.next(), parts.next()) {
+        (Some(name), Some(value)) => Ok((
+            HeaderName::from_bytes(name.trim().as_bytes()).map_err(|_| invalid())?,
+            header_value(value.trim(), header.tag())?,
+        )),
+        _ => Err(invalid()),
+    }
+}
+
+fn header_value(value: &str, tag: impl Into<Tag>) -> Result<HeaderValue, ShellError> {
+    HeaderValue::from_str(value).map_err(|_| {
+        ShellError::labeled_error(
+            "Invalid header",
+            "header values can't contain control characters",
+            tag,
+        )
+    })
+}
+
+/// Adds the headers given to `fetch` to the request. Surf only takes header names known when it
+/// is compiled, so the names from the command line are set on the underlying request instead.
+struct SendHeaders(HeaderMap);
+
+impl<C: HttpClient> Middleware<C> for SendHeaders {
+    fn handle<'a>(
+        &'a self,
+        mut req: Request,
+        client: C,
+        next: Next<'a, C>,
+    ) -> BoxFuture<'a, Result<Response, surf::Exception>> {
+        for (name, value) in self.0.iter() {
+            req.headers_mut().append(name.clone(), value.clone());
+        }
+
+        next.run(req, client)
+    }
+}
+
+/// The status and headers of a response, reported by `fetch --full`.
+pub(crate) struct ResponseInfo {
+    status: u16,
+    headers: Vec<(String, String)>,
+}
+
+impl ResponseInfo {
+    fn from_response(response: &mut surf::Response) -> ResponseInfo {
+        ResponseInfo {
+            status: response.status().as_u16(),
+            headers: response
+                .headers()
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+
+    fn into_row(self, body: Tagged<Value>, tag: Tag) -> Tagged<Value> {
+        let mut headers = TaggedDictBuilder::new(tag);
+        for (name, value) in self.headers {
+            headers.insert(name, Value::string(value));
+        }
+
+        let mut row = TaggedDictBuilder::new(tag);
+        row.insert_tagged("body", body);
+        row.insert("status", Value::int(self.status));
+        row.insert_tagged("headers", headers.into_tagged_value());
+
+        row.into_tagged_value()
+    }
+}
+
+pub(crate) async fn fetch(
     location: &str,
     span: Span,
-) -> Result<(Option<String>, Value, Tag, SpanSource), ShellError> {
+    options: &FetchOptions,
+) -> Result<(Option<String>, Value, Tag, SpanSource, ResponseInfo), ShellError> {
     if let Err(_) = url::Url::parse(location) {
         return Err(ShellError::labeled_error(
             "Incomplete or incorrect url",
@@ -141,9 +336,33 @@ pub async fn fetch(
         ));
     }
 
-    let response = surf::get(location).await;
-    match response {
-        Ok(mut r) => match r.headers().get("content-type") {
+    let request = surf::get(location).middleware(SendHeaders(options.headers.clone()));
+
+    let response = match options.timeout {
+        Some(timeout) => request.timeout(timeout).await,
+        None => request.await,
+    };
+
+    let mut r = match response {
+        Ok(r) => r,
+        Err(err) => {
+            let timed_out = err
+                .downcast_ref::<std::io::Error>()
+                .map(|err| err.kind() == std::io::ErrorKind::TimedOut)
+                .unwrap_or(false);
+
+            return Err(if timed_out {
+                ShellError::labeled_error("Request timed out", "no response in time", span)
+            } else {
+                ShellError::labeled_error("URL could not be opened", "url not found", span)
+            });
+        }
+    };
+
+    let response = ResponseInfo::from_response(&mut r);
+
+    let contents: Result<(Option<String>, Value, Tag, SpanSource), ShellError> =
+        match r.headers().get("content-type") {
             Some(content_type) => {
                 let content_type = Mime::from_str(content_type).unwrap();
                 match (content_type.type_(), content_type.subtype()) {
@@ -177,6 +396,21 @@ pub async fn fetch(
                         },
                         SpanSource::Url(location.to_string()),
                     )),
+                    (mime::APPLICATION, mime::WWW_FORM_URLENCODED) => Ok((
+                        Some("url".to_string()),
+                        Value::string(r.body_string().await.map_err(|_| {
+                            ShellError::labeled_error(
+                                "Could not load text from remote url",
+                                "could not load",
+                                span,
+                            )
+                        })?),
+                        Tag {
+                            span,
+                            origin: Uuid::new_v4(),
+                        },
+                        SpanSource::Url(location.to_string()),
+                    )),
                     (mime::APPLICATION, mime::OCTET_STREAM) => {
                         let buf: Vec<u8> = r.body_bytes().await.map_err(|_| {
                             ShellError::labeled_error(
@@ -271,15 +505,40 @@ pub async fn fetch(
                             SpanSource::Url(location.to_string()),
                         ))
                     }
-                    (ty, sub_ty) => Ok((
+                    (mime::TEXT, _) => Ok((
                         None,
-                        Value::string(format!("Not yet supported MIME type: {} {}", ty, sub_ty)),
+                        Value::string(r.body_string().await.map_err(|_| {
+                            ShellError::labeled_error(
+                                "Could not load text from remote url",
+                                "could not load",
+                                span,
+                            )
+                        })?),
                         Tag {
                             span,
                             origin: Uuid::new_v4(),
                         },
                         SpanSource::Url(location.to_string()),
                     )),
+                    // Anything else isn't text, so it's kept as the bytes that were sent
+                    _ => {
+                        let buf: Vec<u8> = r.body_bytes().await.map_err(|_| {
+                            ShellError::labeled_error(
+                                "Could not load binary file",
+                                "could not load",
+                                span,
+                            )
+                        })?;
+                        Ok((
+                            None,
+                            Value::binary(buf),
+                            Tag {
+                                span,
+                                origin: Uuid::new_v4(),
+                            },
+                            SpanSource::Url(location.to_string()),
+                        ))
+                    }
                 }
             }
             None => Ok((
@@ -291,13 +550,31 @@ pub async fn fetch(
                 },
                 SpanSource::Url(location.to_string()),
             )),
-        },
-        Err(_) => {
-            return Err(ShellError::labeled_error(
-                "URL could not be opened",
-                "url not found",
-                span,
-            ));
-        }
+        };
+    let (file_extension, contents, contents_tag, span_source) = contents?;
+
+    Ok((
+        file_extension,
+        contents,
+        contents_tag,
+        span_source,
+        response,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_header;
+    use crate::prelude::*;
+
+    #[test]
+    fn parses_name_value_headers() {
+        let header = Value::string("Accept: application/json").tagged_unknown();
+        let (name, value) = parse_header(&header).unwrap();
+        assert_eq!(name, "accept");
+        assert_eq!(value, "application/json");
+
+        let header = Value::string("no separator").tagged_unknown();
+        assert!(parse_header(&header).is_err());
     }
 }
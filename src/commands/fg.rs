@@ -0,0 +1,46 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Fg;
+
+#[derive(Deserialize)]
+pub struct FgArgs {
+    id: Tagged<i64>,
+}
+
+impl WholeStreamCommand for Fg {
+    fn name(&self) -> &str {
+        "fg"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("fg").required("id", SyntaxShape::Int)
+    }
+
+    fn usage(&self) -> &str {
+        "Wait for a background job to finish and output everything it printed."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, fg)?.run()
+    }
+}
+
+pub fn fg(
+    FgArgs { id }: FgArgs,
+    RunnableContext { commands, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    match commands.jobs().wait(id.item as usize) {
+        Some(output) => Ok(OutputStream::one(Value::string(output).tagged(name))),
+        None => Err(ShellError::labeled_error(
+            "Unknown job",
+            "no job with this id",
+            id.tag(),
+        )),
+    }
+}
@@ -7,7 +7,7 @@ pub struct First;
 
 #[derive(Deserialize)]
 pub struct FirstArgs {
-    amount: Tagged<u64>,
+    amount: Tagged<Value>,
 }
 
 impl WholeStreamCommand for First {
@@ -20,7 +20,7 @@ impl WholeStreamCommand for First {
     }
 
     fn usage(&self) -> &str {
-        "Show only the first number of rows."
+        "Show only the first number of rows, or a percentage of them (eg. first 10%)."
     }
 
     fn run(
@@ -32,9 +32,54 @@ impl WholeStreamCommand for First {
     }
 }
 
+/// The percentage in an amount like `10%`, shared by `first` and the `skip` plugin. Amounts that
+/// aren't written as a percentage give `None`.
+pub fn percentage(value: &Tagged<Value>) -> Result<Option<f64>, ShellError> {
+    match value.item() {
+        Value::Primitive(Primitive::String(s)) if s.ends_with('%') => {
+            match s[..s.len() - 1].parse::<f64>() {
+                Ok(p) if p >= 0.0 => Ok(Some(p)),
+                _ => Err(ShellError::labeled_error(
+                    "Invalid percentage",
+                    "expected a percentage like 10%",
+                    value.tag(),
+                )),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
 fn first(
     FirstArgs { amount }: FirstArgs,
     context: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
-    Ok(OutputStream::from_input(context.input.values.take(*amount)))
+    if let Some(percentage) = percentage(&amount)? {
+        let input = context.input;
+
+        let stream = async_stream_block! {
+            let values: Vec<Tagged<Value>> = input.values.collect().await;
+            let count = (values.len() as f64 * percentage / 100.0).round() as usize;
+
+            for value in values.into_iter().take(count) {
+                yield ReturnSuccess::value(value);
+            }
+        };
+
+        return Ok(stream.to_output_stream());
+    }
+
+    let rows: u64 = match amount.item() {
+        Value::Primitive(Primitive::Int(i)) => {
+            i.tagged(amount.tag()).coerce_into("converting for first")?
+        }
+        v => {
+            return Err(ShellError::type_error(
+                "Integer or percentage",
+                amount.copy_tag(v.type_name()),
+            ))
+        }
+    };
+
+    Ok(OutputStream::from_input(context.input.values.take(rows)))
 }
@@ -0,0 +1,237 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::Dictionary;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct Flatten;
+
+#[derive(Deserialize)]
+pub struct FlattenArgs {
+    rest: Vec<Tagged<String>>,
+    all: bool,
+}
+
+impl WholeStreamCommand for Flatten {
+    fn name(&self) -> &str {
+        "flatten"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("flatten")
+            .switch("all")
+            .rest(SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Lift the columns of nested rows into the row, and give each item of a nested table a row of its own. With --all, repeats until nothing is nested."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, flatten)?.run()
+    }
+}
+
+type Entries = IndexMap<String, Tagged<Value>>;
+
+/// Empty tables are left in place when flattening, so they don't count as nested.
+fn is_nested(value: &Value) -> bool {
+    match value {
+        Value::Row(_) => true,
+        Value::Table(items) => !items.is_empty(),
+        _ => false,
+    }
+}
+
+/// The name a nested column gets once lifted. It keeps its own name unless the row
+/// already has a column called that, in which case it's prefixed with its parent's.
+fn lifted_name(parent: &str, column: &str, row: &Entries, lifted: &Entries) -> String {
+    let taken = lifted.contains_key(column)
+        || row
+            .keys()
+            .any(|existing| existing != parent && existing == column);
+
+    if taken {
+        format!("{}_{}", parent, column)
+    } else {
+        column.to_string()
+    }
+}
+
+fn lift(parent: &str, nested: &Dictionary, row: &Entries, lifted: &mut Entries) {
+    for (column, value) in nested.entries.iter() {
+        let name = lifted_name(parent, column, row, lifted);
+        lifted.insert(name, value.clone());
+    }
+}
+
+/// Flatten the nested columns of a row one level, only touching `columns` when any are given.
+fn flatten_row(row: &Entries, columns: &[String], tag: Tag) -> Vec<Tagged<Value>> {
+    let mut flattened: Vec<Entries> = vec![IndexMap::new()];
+
+    for (column, value) in row.iter() {
+        let selected = columns.is_empty() || columns.contains(column);
+
+        match &value.item {
+            Value::Row(nested) if selected => {
+                for lifted in flattened.iter_mut() {
+                    lift(column, nested, row, lifted);
+                }
+            }
+            Value::Table(items) if selected && !items.is_empty() => {
+                flattened = flattened
+                    .into_iter()
+                    .flat_map(|lifted| {
+                        items.iter().map(move |item| {
+                            let mut lifted = lifted.clone();
+                            match &item.item {
+                                Value::Row(nested) => lift(column, nested, row, &mut lifted),
+                                _ => {
+                                    lifted.insert(column.clone(), item.clone());
+                                }
+                            }
+                            lifted
+                        })
+                    })
+                    .collect();
+            }
+            _ => {
+                for lifted in flattened.iter_mut() {
+                    lifted.insert(column.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    flattened
+        .into_iter()
+        .map(|entries| Value::Row(Dictionary::from(entries)).tagged(tag))
+        .collect()
+}
+
+fn flatten_value(value: Tagged<Value>, columns: &[String], all: bool) -> Vec<Tagged<Value>> {
+    let tag = value.tag();
+
+    let entries = match value.item {
+        Value::Row(dict) => dict.entries,
+        other => return vec![other.tagged(tag)],
+    };
+
+    let rows = flatten_row(&entries, columns, tag);
+
+    if !all {
+        return rows;
+    }
+
+    rows.into_iter()
+        .flat_map(|row| {
+            let still_nested = match &row.item {
+                Value::Row(dict) => dict.entries.values().any(|value| is_nested(&value.item)),
+                _ => false,
+            };
+
+            if still_nested {
+                flatten_value(row, &[], true)
+            } else {
+                vec![row]
+            }
+        })
+        .collect()
+}
+
+pub fn flatten(
+    FlattenArgs { rest, all }: FlattenArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let columns: Vec<String> = rest.into_iter().map(|column| column.item).collect();
+
+    let stream = input
+        .values
+        .map(move |value| {
+            flatten_value(value, &columns, all)
+                .into_iter()
+                .map(ReturnSuccess::value)
+                .collect::<VecDeque<_>>()
+        })
+        .flatten();
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::flatten_value;
+    use crate::data::TaggedDictBuilder;
+    use crate::prelude::*;
+
+    fn row(entries: Vec<(&str, Value)>) -> Tagged<Value> {
+        let mut dict = TaggedDictBuilder::new(Tag::unknown());
+        for (key, value) in entries {
+            dict.insert(key, value);
+        }
+        dict.into_tagged_value()
+    }
+
+    fn columns(value: &Tagged<Value>) -> Vec<String> {
+        value.data_descriptors()
+    }
+
+    #[test]
+    fn lifts_nested_rows_and_prefixes_clashing_columns() {
+        let package = row(vec![
+            ("name", Value::string("nu")),
+            ("version", Value::string("0.3.0")),
+        ]);
+        let input = row(vec![
+            ("name", Value::string("Cargo.toml")),
+            ("package", package.item),
+        ]);
+
+        let flattened = flatten_value(input, &[], false);
+
+        assert_eq!(flattened.len(), 1);
+        assert_eq!(
+            columns(&flattened[0]),
+            vec!["name", "package_name", "version"]
+        );
+    }
+
+    #[test]
+    fn gives_each_nested_table_item_its_own_row() {
+        let input = row(vec![
+            ("name", Value::string("nu")),
+            (
+                "authors",
+                Value::Table(vec![
+                    Value::string("jt").tagged_unknown(),
+                    Value::string("wycats").tagged_unknown(),
+                ]),
+            ),
+        ]);
+
+        let flattened = flatten_value(input, &["authors".to_string()], false);
+
+        assert_eq!(flattened.len(), 2);
+        assert_eq!(
+            flattened[1].get_data_by_key("authors").unwrap().item,
+            Value::string("wycats")
+        );
+    }
+
+    #[test]
+    fn all_flattens_until_nothing_is_nested() {
+        let inner = row(vec![("depth", Value::int(2))]);
+        let middle = row(vec![("inner", inner.item)]);
+        let input = row(vec![("outer", middle.item)]);
+
+        assert_eq!(
+            columns(&flatten_value(input.clone(), &[], false)[0]),
+            vec!["inner"]
+        );
+        assert_eq!(columns(&flatten_value(input, &[], true)[0]), vec!["depth"]);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct FlattenStream;
+
+impl WholeStreamCommand for FlattenStream {
+    fn name(&self) -> &str {
+        "flatten-stream"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("flatten-stream")
+    }
+
+    fn usage(&self) -> &str {
+        "Splats each table in the input into separate rows, passing other values through."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        flatten_stream(args, registry)
+    }
+}
+
+fn flatten_stream(
+    args: CommandArgs,
+    registry: &CommandRegistry,
+) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let (input, _args) = args.parts();
+
+    let stream = input
+        .values
+        .map(|item| match item {
+            Tagged {
+                item: Value::Table(rows),
+                ..
+            } => rows.into_iter().collect::<VecDeque<_>>(),
+            other => VecDeque::from(vec![other]),
+        })
+        .flatten();
+
+    Ok(stream.from_input_stream())
+}
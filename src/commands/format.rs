@@ -1,16 +1,111 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::Value;
+use crate::errors::ShellError;
 use crate::prelude::*;
-use crate::{EntriesListView, GenericView, TreeView};
-use futures::stream::{self, StreamExt};
-use std::sync::{Arc, Mutex};
-
-pub(crate) fn format(input: Vec<Value>, host: &mut dyn Host) {
-    let last = input.len() - 1;
-    for (i, item) in input.iter().enumerate() {
-        let view = GenericView::new(item);
-        crate::format::print_view(&view, &mut *host);
-
-        if last != i {
-            println!("");
+
+#[derive(Deserialize)]
+struct FormatArgs {
+    pattern: Tagged<String>,
+}
+
+pub struct Format;
+
+impl WholeStreamCommand for Format {
+    fn name(&self) -> &str {
+        "format"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format").required("pattern", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Format columns of each row into a string using a pattern (eg. format \"{name} is {size}\")."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, format)?.run()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum FormatPiece {
+    Text(String),
+    Column(String),
+}
+
+/// Split a pattern into its literal text and the `{column}` placeholders between it.
+/// A `{` without a closing `}` is kept as text.
+fn format_pieces(pattern: &str) -> Vec<FormatPiece> {
+    let mut pieces = vec![];
+    let mut rest = pattern;
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        if start > 0 {
+            pieces.push(FormatPiece::Text(rest[..start].to_string()));
         }
+        pieces.push(FormatPiece::Column(rest[start + 1..end].trim().to_string()));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        pieces.push(FormatPiece::Text(rest.to_string()));
+    }
+
+    pieces
+}
+
+fn format(
+    FormatArgs { pattern }: FormatArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let pieces = format_pieces(&pattern.item);
+
+    Ok(input
+        .values
+        .map(move |row| {
+            let mut output = String::new();
+
+            for piece in &pieces {
+                match piece {
+                    FormatPiece::Text(text) => output.push_str(text),
+                    // Columns the row doesn't have are left empty
+                    FormatPiece::Column(column) => {
+                        if let Some(value) = row.item.get_data_by_path(row.tag(), column) {
+                            output.push_str(&value.item.format_leaf(None));
+                        }
+                    }
+                }
+            }
+
+            ReturnSuccess::value(Value::string(output).tagged(row.tag()))
+        })
+        .to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_pieces, FormatPiece};
+
+    #[test]
+    fn splits_pattern_into_text_and_columns() {
+        assert_eq!(
+            format_pieces("{name} is { size }{"),
+            vec![
+                FormatPiece::Column("name".to_string()),
+                FormatPiece::Text(" is ".to_string()),
+                FormatPiece::Column("size".to_string()),
+                FormatPiece::Text("{".to_string()),
+            ]
+        );
     }
 }
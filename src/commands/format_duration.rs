@@ -0,0 +1,165 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::base::duration_to_string;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct FormatDuration;
+
+#[derive(Deserialize)]
+pub struct FormatDurationArgs {
+    column: Tagged<String>,
+    unit: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for FormatDuration {
+    fn name(&self) -> &str {
+        "format-duration"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format-duration")
+            .required("column", SyntaxShape::String)
+            .named("unit", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Format a column of seconds as a duration like 1h 2m 3s, or in the given unit (ns, us, ms, sec, min, hr, day)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, format_duration)?.run()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DurationUnit {
+    Nanosecond,
+    Microsecond,
+    Millisecond,
+    Second,
+    Minute,
+    Hour,
+    Day,
+}
+
+impl DurationUnit {
+    fn from_name(name: &str) -> Option<DurationUnit> {
+        match name {
+            "ns" => Some(DurationUnit::Nanosecond),
+            "us" => Some(DurationUnit::Microsecond),
+            "ms" => Some(DurationUnit::Millisecond),
+            "sec" | "s" => Some(DurationUnit::Second),
+            "min" | "m" => Some(DurationUnit::Minute),
+            "hr" | "h" => Some(DurationUnit::Hour),
+            "day" | "d" => Some(DurationUnit::Day),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            DurationUnit::Nanosecond => "ns",
+            DurationUnit::Microsecond => "us",
+            DurationUnit::Millisecond => "ms",
+            DurationUnit::Second => "sec",
+            DurationUnit::Minute => "min",
+            DurationUnit::Hour => "hr",
+            DurationUnit::Day => "day",
+        }
+    }
+
+    /// How many of this unit make up one second.
+    fn per_second(self) -> f64 {
+        match self {
+            DurationUnit::Nanosecond => 1_000_000_000.0,
+            DurationUnit::Microsecond => 1_000_000.0,
+            DurationUnit::Millisecond => 1_000.0,
+            DurationUnit::Second => 1.0,
+            DurationUnit::Minute => 1.0 / 60.0,
+            DurationUnit::Hour => 1.0 / 3_600.0,
+            DurationUnit::Day => 1.0 / 86_400.0,
+        }
+    }
+}
+
+fn parse_unit(unit: &Tagged<String>) -> Result<DurationUnit, ShellError> {
+    DurationUnit::from_name(&unit.item).ok_or_else(|| {
+        ShellError::labeled_error(
+            "Unknown unit",
+            "expected one of ns, us, ms, sec, min, hr, day",
+            unit.tag(),
+        )
+    })
+}
+
+fn duration_in_unit(seconds: i64, unit: DurationUnit) -> String {
+    let amount = format!("{:.1}", seconds as f64 * unit.per_second());
+
+    // Whole amounts read better without the trailing `.0`, including ones that round to it
+    let amount = amount.trim_end_matches(".0");
+
+    format!("{} {}", amount, unit.as_str())
+}
+
+fn seconds_of(value: &Tagged<Value>) -> Result<i64, ShellError> {
+    match value.item() {
+        Value::Primitive(Primitive::Duration(seconds)) => Ok(*seconds),
+        Value::Primitive(Primitive::Int(int)) => match int.to_i64() {
+            Some(seconds) => Ok(seconds),
+            None => Err(ShellError::labeled_error(
+                "Invalid duration",
+                "number of seconds is too large",
+                value.tag(),
+            )),
+        },
+        other => Err(ShellError::type_error(
+            "number of seconds",
+            value.copy_tag(other.type_name()),
+        )),
+    }
+}
+
+pub fn format_duration(
+    FormatDurationArgs { column, unit }: FormatDurationArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let unit = match unit {
+        Some(unit) => Some(parse_unit(&unit)?),
+        None => None,
+    };
+
+    let stream = input.values.map(move |row| {
+        let seconds = match row.get_data_by_key(&column.item) {
+            Some(value) => seconds_of(value)?,
+            None => {
+                return Err(ShellError::labeled_error_with_secondary(
+                    "Unknown column",
+                    "column not found",
+                    column.tag(),
+                    "in this row",
+                    row.tag(),
+                ))
+            }
+        };
+
+        let formatted = match unit {
+            Some(unit) => duration_in_unit(seconds, unit),
+            None => duration_to_string(seconds),
+        };
+
+        match row.replace_data_at_path(row.tag(), &column.item, Value::string(formatted)) {
+            Some(row) => ReturnSuccess::value(row),
+            None => Err(ShellError::labeled_error(
+                "Could not format column",
+                "column not found",
+                column.tag(),
+            )),
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
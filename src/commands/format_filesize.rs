@@ -0,0 +1,213 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::parser::Unit;
+use crate::prelude::*;
+use indexmap::IndexMap;
+use std::str::FromStr;
+
+pub struct FormatFilesize;
+
+#[derive(Deserialize)]
+pub struct FormatFilesizeArgs {
+    column: Tagged<String>,
+    unit: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for FormatFilesize {
+    fn name(&self) -> &str {
+        "format-filesize"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("format-filesize")
+            .required("column", SyntaxShape::String)
+            .named("unit", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Format a column of byte counts as a file size, in the given unit (B, KB, MB, GB, TB, PB), the most fitting one, or the way tables show them with binary, decimal or bytes."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, format_filesize)?.run()
+    }
+}
+
+const BINARY_UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+
+/// How tables write file sizes, chosen with `filesize_format` in the config: in units of 1024
+/// (KiB, MiB, ...), in units of 1000 (KB, MB, ...), or as a plain count of bytes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilesizeFormat {
+    Binary,
+    Decimal,
+    Bytes,
+}
+
+impl FilesizeFormat {
+    fn from_name(name: &str) -> Option<FilesizeFormat> {
+        match name {
+            "binary" => Some(FilesizeFormat::Binary),
+            "decimal" => Some(FilesizeFormat::Decimal),
+            "bytes" => Some(FilesizeFormat::Bytes),
+            _ => None,
+        }
+    }
+
+    /// The format set in `config`, or decimal when it's missing or not one we know.
+    pub(crate) fn from_config(config: &IndexMap<String, Tagged<Value>>) -> FilesizeFormat {
+        config
+            .get("filesize_format")
+            .and_then(|format| format.as_string().ok())
+            .and_then(|format| FilesizeFormat::from_name(&format))
+            .unwrap_or(FilesizeFormat::Decimal)
+    }
+
+    pub(crate) fn format(self, bytes: u64) -> String {
+        match self {
+            FilesizeFormat::Bytes if bytes > 0 => format!("{} B", bytes),
+            FilesizeFormat::Binary if bytes > 0 => {
+                // Move up to a unit as soon as the size reaches it, so 1048576 bytes is 1.0 MiB
+                // rather than 1024.0 KiB
+                let unit = BINARY_UNITS
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(power, unit)| (unit, 1024u64.pow(power as u32 + 1)))
+                    .find(|(_, size)| bytes >= *size);
+
+                match unit {
+                    Some((unit, size)) => format!("{:.1} {}", bytes as f64 / size as f64, unit),
+                    None => format!("{} B ", bytes),
+                }
+            }
+            // Sizes are written in decimal units by default, and an empty size looks the same
+            // in every format
+            _ => Primitive::Bytes(bytes).format(None),
+        }
+    }
+}
+
+/// What a column of byte counts is formatted as.
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Auto,
+    Unit(Unit),
+    Format(FilesizeFormat),
+}
+
+fn parse_unit(unit: &Tagged<String>) -> Result<Target, ShellError> {
+    if unit.item == "auto" {
+        return Ok(Target::Auto);
+    }
+
+    if let Some(format) = FilesizeFormat::from_name(&unit.item) {
+        return Ok(Target::Format(format));
+    }
+
+    match Unit::from_str(&unit.item) {
+        Ok(unit) if !unit.is_time() => Ok(Target::Unit(unit)),
+        _ => Err(ShellError::labeled_error(
+            "Unknown unit",
+            "expected one of B, KB, MB, GB, TB, PB, auto, binary, decimal or bytes",
+            unit.tag(),
+        )),
+    }
+}
+
+/// The number of bytes in one of `unit`, counted in 1000s so a size labelled MB reads the same
+/// as in the default decimal `filesize_format`. Size literals such as `10mb` count in 1024s.
+fn decimal_multiplier(unit: Unit) -> u64 {
+    let power = match unit {
+        Unit::KB => 1,
+        Unit::MB => 2,
+        Unit::GB => 3,
+        Unit::TB => 4,
+        Unit::PB => 5,
+        _ => 0,
+    };
+
+    1000u64.pow(power)
+}
+
+pub(crate) fn filesize_to_string(bytes: u64, unit: Option<Unit>) -> String {
+    let unit = unit.unwrap_or_else(|| {
+        *[Unit::PB, Unit::TB, Unit::GB, Unit::MB, Unit::KB]
+            .iter()
+            .find(|unit| bytes >= decimal_multiplier(**unit))
+            .unwrap_or(&Unit::B)
+    });
+
+    match unit {
+        Unit::B => format!("{} B", bytes),
+        _ => format!(
+            "{:.1} {}",
+            bytes as f64 / decimal_multiplier(unit) as f64,
+            unit.as_str()
+        ),
+    }
+}
+
+fn byte_count(value: &Tagged<Value>) -> Result<u64, ShellError> {
+    match value.item() {
+        Value::Primitive(Primitive::Bytes(bytes)) => Ok(*bytes),
+        Value::Primitive(Primitive::Int(int)) => match int.to_u64() {
+            Some(bytes) => Ok(bytes),
+            None => Err(ShellError::labeled_error(
+                "Invalid file size",
+                "expected a positive byte count",
+                value.tag(),
+            )),
+        },
+        other => Err(ShellError::type_error(
+            "byte count",
+            value.copy_tag(other.type_name()),
+        )),
+    }
+}
+
+pub fn format_filesize(
+    FormatFilesizeArgs { column, unit }: FormatFilesizeArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let target = match unit {
+        Some(unit) => parse_unit(&unit)?,
+        None => Target::Auto,
+    };
+
+    let stream = input.values.map(move |row| {
+        let bytes = match row.get_data_by_key(&column.item) {
+            Some(value) => byte_count(value)?,
+            None => {
+                return Err(ShellError::labeled_error_with_secondary(
+                    "Unknown column",
+                    "column not found",
+                    column.tag(),
+                    "in this row",
+                    row.tag(),
+                ))
+            }
+        };
+
+        let formatted = Value::string(match target {
+            Target::Auto => filesize_to_string(bytes, None),
+            Target::Unit(unit) => filesize_to_string(bytes, Some(unit)),
+            Target::Format(format) => format.format(bytes),
+        });
+
+        match row.replace_data_at_path(row.tag(), &column.item, formatted) {
+            Some(row) => ReturnSuccess::value(row),
+            None => Err(ShellError::labeled_error(
+                "Could not format column",
+                "column not found",
+                column.tag(),
+            )),
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
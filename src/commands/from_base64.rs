@@ -0,0 +1,62 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::Value;
+use crate::prelude::*;
+
+pub struct FromBase64;
+
+impl WholeStreamCommand for FromBase64 {
+    fn name(&self) -> &str {
+        "from-base64"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-base64")
+    }
+
+    fn usage(&self) -> &str {
+        "Decode base64 text into text, or into binary data when it isn't valid utf-8"
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_base64(args, registry)
+    }
+}
+
+/// Decoded bytes read as text when they can be, and are kept as binary data otherwise.
+pub(crate) fn text_or_binary(bytes: Vec<u8>) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(text) => Value::string(text),
+        Err(err) => Value::binary(err.into_bytes()),
+    }
+}
+
+fn from_base64(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+
+    let stream = args.input.values.map(move |value| match value.item() {
+        Value::Primitive(Primitive::String(s)) => match base64::decode(s.trim()) {
+            Ok(bytes) => ReturnSuccess::value(text_or_binary(bytes).tagged(value.tag())),
+            Err(_) => Err(ShellError::labeled_error_with_secondary(
+                "Could not decode base64",
+                "input cannot be decoded as base64",
+                name_tag,
+                "value originates from here",
+                value.tag(),
+            )),
+        },
+        _ => Err(ShellError::labeled_error_with_secondary(
+            "Expected base64 text from pipeline",
+            "requires text input",
+            name_tag,
+            "value originates from here",
+            value.tag(),
+        )),
+    });
+
+    Ok(stream.to_output_stream())
+}
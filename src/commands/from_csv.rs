@@ -2,12 +2,18 @@ use crate::commands::WholeStreamCommand;
 use crate::data::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
 use csv::ReaderBuilder;
+use std::str::FromStr;
 
 pub struct FromCSV;
 
 #[derive(Deserialize)]
 pub struct FromCSVArgs {
     headerless: bool,
+    separator: Option<Tagged<String>>,
+    quote: Option<Tagged<String>>,
+    skip: Option<Tagged<u64>>,
+    #[serde(rename(deserialize = "no-infer"))]
+    no_infer: bool,
 }
 
 impl WholeStreamCommand for FromCSV {
@@ -16,11 +22,18 @@ impl WholeStreamCommand for FromCSV {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from-csv").switch("headerless")
+        Signature::build("from-csv")
+            .switch("headerless")
+            .named("separator", SyntaxShape::String)
+            .named("quote", SyntaxShape::String)
+            .named("skip", SyntaxShape::Int)
+            .switch("no-infer")
+            // Inference is the default now, so this is only accepted for older pipelines
+            .switch("infer-types")
     }
 
     fn usage(&self) -> &str {
-        "Parse text as .csv and create table"
+        "Parse text as .csv and create table. Cells that look like numbers or booleans become them, unless --no-infer is given. --skip drops lines before the header, and --separator and --quote change the characters used."
     }
 
     fn run(
@@ -32,107 +45,328 @@ impl WholeStreamCommand for FromCSV {
     }
 }
 
-pub fn from_csv_string_to_value(
-    s: String,
+/// Convert a cell that looks like an integer, a decimal or a boolean into the
+/// matching value. Anything else, including numbers with leading zeros such as
+/// zip codes, stays a string.
+pub(crate) fn infer_cell_value(entry: &str) -> Value {
+    let digits = entry.trim_start_matches('-');
+    let leading_zero = digits.len() > 1 && digits.starts_with('0') && !digits.starts_with("0.");
+
+    if !leading_zero {
+        if let Ok(i) = BigInt::from_str(entry) {
+            return Value::int(i);
+        }
+
+        if entry.contains('.') {
+            if let Ok(d) = BigDecimal::from_str(entry) {
+                return Value::decimal(d);
+            }
+        }
+    }
+
+    match entry {
+        "true" => Value::boolean(true),
+        "false" => Value::boolean(false),
+        _ => Value::Primitive(Primitive::String(String::from(entry))),
+    }
+}
+
+/// Turns records into rows as they are read, so a large file never has to be held as a table.
+/// The first record names the columns unless the file is headerless.
+pub(crate) struct CsvRows {
+    fields: Option<Vec<String>>,
     headerless: bool,
-    tag: impl Into<Tag>,
-) -> Result<Tagged<Value>, csv::Error> {
-    let mut reader = ReaderBuilder::new()
-        .has_headers(false)
-        .from_reader(s.as_bytes());
-    let tag = tag.into();
-
-    let mut fields: VecDeque<String> = VecDeque::new();
-    let mut iter = reader.records();
-    let mut rows = vec![];
-
-    if let Some(result) = iter.next() {
-        let line = result?;
-
-        for (idx, item) in line.iter().enumerate() {
-            if headerless {
-                fields.push_back(format!("Column{}", idx + 1));
-            } else {
-                fields.push_back(item.to_string());
+    infer_types: bool,
+    tag: Tag,
+}
+
+impl CsvRows {
+    pub(crate) fn new(headerless: bool, infer_types: bool, tag: impl Into<Tag>) -> CsvRows {
+        CsvRows {
+            fields: None,
+            headerless,
+            infer_types,
+            tag: tag.into(),
+        }
+    }
+
+    /// The rows for the records in `reader`, which carry on from the records read before.
+    pub(crate) fn read<'a, R: std::io::Read + 'a>(
+        &'a mut self,
+        reader: csv::Reader<R>,
+    ) -> impl Iterator<Item = Result<Tagged<Value>, csv::Error>> + 'a {
+        reader
+            .into_records()
+            .filter_map(move |record| match record {
+                Ok(record) => self.row_for(&record).map(Ok),
+                Err(err) => Some(Err(err)),
+            })
+    }
+
+    /// The row for a record, or nothing when the record is the header (which a headerless file
+    /// skips, naming its columns by position instead).
+    fn row_for(&mut self, record: &csv::StringRecord) -> Option<Tagged<Value>> {
+        match &self.fields {
+            Some(fields) => Some(self.row(fields, record)),
+            None => {
+                let fields: Vec<String> = record
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, item)| {
+                        if self.headerless {
+                            format!("Column{}", idx + 1)
+                        } else {
+                            item.to_string()
+                        }
+                    })
+                    .collect();
+
+                self.fields = Some(fields);
+                None
             }
         }
     }
 
-    loop {
-        if let Some(row_values) = iter.next() {
-            let row_values = row_values?;
+    fn row(&self, fields: &[String], record: &csv::StringRecord) -> Tagged<Value> {
+        let mut row = TaggedDictBuilder::new(self.tag);
 
-            let mut row = TaggedDictBuilder::new(tag);
+        for (idx, entry) in record.iter().enumerate() {
+            let value = if self.infer_types {
+                infer_cell_value(entry)
+            } else {
+                Value::Primitive(Primitive::String(String::from(entry)))
+            };
+
+            let field = match fields.get(idx) {
+                Some(field) => field.clone(),
+                None => format!("Column{}", idx + 1),
+            };
+
+            row.insert_tagged(field, value.tagged(self.tag));
+        }
+
+        row.into_tagged_value()
+    }
+}
+
+/// The byte given for a flag like `--separator`, which has to be a single ASCII character.
+pub(crate) fn single_byte(flag: Option<Tagged<String>>, default: u8) -> Result<u8, ShellError> {
+    match flag {
+        None => Ok(default),
+        Some(flag) => match flag.item.as_bytes() {
+            [byte] if byte.is_ascii() => Ok(*byte),
+            _ => Err(ShellError::labeled_error(
+                "Expected a single character",
+                "requires a single ASCII character",
+                flag.tag(),
+            )),
+        },
+    }
+}
 
-            for (idx, entry) in row_values.iter().enumerate() {
-                row.insert_tagged(
-                    fields.get(idx).unwrap(),
-                    Value::Primitive(Primitive::String(String::from(entry))).tagged(tag),
-                );
+/// Drop up to `lines` whole lines from the start of `text`, returning how many are still to be
+/// dropped once more text comes in.
+fn skip_lines(text: &mut String, lines: usize) -> usize {
+    let mut lines = lines;
+
+    while lines > 0 {
+        match text.find('\n') {
+            Some(idx) => {
+                text.drain(..=idx);
+                lines -= 1;
             }
+            None => break,
+        }
+    }
+
+    lines
+}
 
-            rows.push(row.into_tagged_value());
-        } else {
-            break;
+/// How much of the start of `text` is whole records: the lines up to the last one that doesn't
+/// end inside a quoted field.
+fn complete_records(text: &str, quote: u8) -> usize {
+    let mut quoted = false;
+    let mut end = 0;
+
+    for (idx, byte) in text.bytes().enumerate() {
+        if byte == quote {
+            quoted = !quoted;
+        } else if byte == b'\n' && !quoted {
+            end = idx + 1;
         }
     }
 
-    Ok(Tagged::from_item(Value::Table(rows), tag))
+    end
 }
 
-fn from_csv(
-    FromCSVArgs {
-        headerless: skip_headers,
-    }: FromCSVArgs,
+pub(crate) struct DelimitedOptions {
+    pub(crate) headerless: bool,
+    pub(crate) separator: u8,
+    pub(crate) quote: u8,
+    pub(crate) skip: usize,
+    pub(crate) infer_types: bool,
+}
+
+/// Parse the text coming down the pipeline with the csv reader, shared by `from-csv` and
+/// `from-tsv`. `format_name` is used in errors.
+pub(crate) fn from_delimited_data(
+    options: DelimitedOptions,
+    format_name: &'static str,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_tag = name;
+    let mut values = input.values;
 
     let stream = async_stream_block! {
-        let values: Vec<Tagged<Value>> = input.values.collect().await;
-
-        let mut concat_string = String::new();
+        let mut rows = CsvRows::new(options.headerless, options.infer_types, name_tag);
+        let mut pending = String::new();
+        let mut to_skip = options.skip;
         let mut latest_tag: Option<Tag> = None;
 
-        for value in values {
-            let value_tag = value.tag();
-            latest_tag = Some(value_tag);
-            match value.item {
-                Value::Primitive(Primitive::String(s)) => {
-                    concat_string.push_str(&s);
-                    concat_string.push_str("\n");
-                }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
-                    "Expected a string from pipeline",
-                    "requires string input",
-                    name_tag,
-                    "value originates from here",
-                    value_tag,
-                )),
+        // Records are parsed as soon as the lines holding them have come in, so the first rows
+        // are handed on while the rest of the input is still being read
+        'input: loop {
+            let value = values.next().await;
+            let at_end = value.is_none();
 
+            match value {
+                Some(Tagged {
+                    item: Value::Primitive(Primitive::String(s)),
+                    tag,
+                }) => {
+                    latest_tag = Some(tag);
+                    pending.push_str(&s);
+                    pending.push_str("\n");
+                }
+                Some(value) => {
+                    yield Err(ShellError::labeled_error_with_secondary(
+                        "Expected a string from pipeline",
+                        "requires string input",
+                        name_tag,
+                        "value originates from here",
+                        value.tag(),
+                    ));
+                    continue;
+                }
+                None => {}
             }
-        }
 
-        match from_csv_string_to_value(concat_string, skip_headers, name_tag) {
-            Ok(x) => match x {
-                Tagged { item: Value::Table(list), .. } => {
-                    for l in list {
-                        yield ReturnSuccess::value(l);
+            to_skip = skip_lines(&mut pending, to_skip);
+
+            let end = if at_end {
+                pending.len()
+            } else {
+                complete_records(&pending, options.quote)
+            };
+            let complete: String = pending.drain(..end).collect();
+
+            let reader = ReaderBuilder::new()
+                .has_headers(false)
+                .delimiter(options.separator)
+                .quote(options.quote)
+                .from_reader(complete.as_bytes());
+
+            for row in rows.read(reader) {
+                match row {
+                    Ok(row) => yield ReturnSuccess::value(row),
+                    Err(_) => {
+                        if let Some(last_tag) = latest_tag {
+                            let error = ShellError::labeled_error_with_secondary(
+                                format!("Could not parse as {}", format_name),
+                                format!("input cannot be parsed as {}", format_name),
+                                name_tag,
+                                "value originates from here",
+                                last_tag,
+                            );
+                            yield Err(error);
+                        }
+                        break 'input;
                     }
                 }
-                x => yield ReturnSuccess::value(x),
-            },
-            Err(_) => if let Some(last_tag) = latest_tag {
-                yield Err(ShellError::labeled_error_with_secondary(
-                    "Could not parse as CSV",
-                    "input cannot be parsed as CSV",
-                    name_tag,
-                    "value originates from here",
-                    last_tag,
-                ))
-            } ,
+            }
+
+            if at_end {
+                break;
+            }
         }
     };
 
     Ok(stream.to_output_stream())
 }
+
+fn from_csv(
+    FromCSVArgs {
+        headerless,
+        separator,
+        quote,
+        skip,
+        no_infer,
+    }: FromCSVArgs,
+    runnable_context: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let options = DelimitedOptions {
+        headerless,
+        separator: single_byte(separator, b',')?,
+        quote: single_byte(quote, b'"')?,
+        skip: skip.map(|skip| skip.item as usize).unwrap_or(0),
+        infer_types: !no_infer,
+    };
+
+    from_delimited_data(options, "CSV", runnable_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete_records, skip_lines, CsvRows};
+    use crate::prelude::*;
+    use csv::ReaderBuilder;
+
+    fn reader(text: &str) -> csv::Reader<&[u8]> {
+        ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(text.as_bytes())
+    }
+
+    #[test]
+    fn rows_are_read_as_they_are_asked_for() {
+        let mut rows = CsvRows::new(false, false, Tag::unknown());
+        let mut read = rows.read(reader("name,lang\nnu,rust\nbroken,\"\n"));
+
+        let first = read.next().unwrap().unwrap();
+        assert_eq!(
+            first.get_data_by_key("lang").unwrap().as_string().unwrap(),
+            "rust"
+        );
+    }
+
+    #[test]
+    fn the_header_carries_over_to_later_records() {
+        let mut rows = CsvRows::new(false, false, Tag::unknown());
+
+        assert_eq!(rows.read(reader("name,lang\n")).count(), 0);
+
+        let row = rows.read(reader("nu,rust\n")).next().unwrap().unwrap();
+        assert_eq!(
+            row.get_data_by_key("lang").unwrap().as_string().unwrap(),
+            "rust"
+        );
+    }
+
+    #[test]
+    fn only_whole_records_are_complete() {
+        assert_eq!(complete_records("name,note\nnu,\"two\nlines", b'"'), 10);
+        assert_eq!(complete_records("nu,\"two\nlines\"\nnext", b'"'), 15);
+        assert_eq!(complete_records("partial", b'"'), 0);
+    }
+
+    #[test]
+    fn skips_whole_lines() {
+        let mut text = "# exported\n\nname\nnu\n".to_string();
+        assert_eq!(skip_lines(&mut text, 2), 0);
+        assert_eq!(text, "name\nnu\n");
+
+        let mut text = "name\n".to_string();
+        assert_eq!(skip_lines(&mut text, 3), 2);
+        assert_eq!(text, "");
+    }
+}
@@ -0,0 +1,181 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct FromEML;
+
+#[derive(Deserialize)]
+pub struct FromEMLArgs {
+    #[serde(rename(deserialize = "preview-body"))]
+    preview_body: Option<Tagged<u64>>,
+}
+
+impl WholeStreamCommand for FromEML {
+    fn name(&self) -> &str {
+        "from-eml"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-eml").named("preview-body", SyntaxShape::Int)
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .eml (an email) and create a row with its subject, from, to, other headers and body. With --preview-body, only that many bytes of the body are kept. Headers and body are shown as written: encoded words, quoted-printable, base64 and multipart parts are not decoded."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_eml)?.run()
+    }
+}
+
+/// Split a message into its headers, with folded lines joined back together, and its body,
+/// which starts after the first blank line.
+fn headers_and_body(text: &str) -> (Vec<(String, String)>, String) {
+    let mut headers: Vec<(String, String)> = vec![];
+    let mut lines = text.lines();
+
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            break;
+        }
+
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+                continue;
+            }
+        }
+
+        if let Some(idx) = line.find(':') {
+            headers.push((
+                line[..idx].trim().to_lowercase(),
+                line[idx + 1..].trim().to_string(),
+            ));
+        }
+    }
+
+    let body: Vec<&str> = lines.collect();
+
+    (headers, body.join("\n"))
+}
+
+/// The first `bytes` bytes of the body, shortened further if that would cut a character in two.
+fn preview(body: &str, bytes: usize) -> &str {
+    let mut end = std::cmp::min(bytes, body.len());
+
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    &body[..end]
+}
+
+pub fn from_eml_string_to_value(
+    s: &str,
+    preview_body: Option<usize>,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
+    let tag = tag.into();
+    let (headers, body) = headers_and_body(s);
+
+    let mut columns: IndexMap<String, Vec<Tagged<Value>>> = IndexMap::new();
+
+    for name in &["subject", "from", "to"] {
+        columns.insert(name.to_string(), vec![]);
+    }
+
+    for (name, value) in headers {
+        columns
+            .entry(name)
+            .or_insert_with(Vec::new)
+            .push(Value::string(value).tagged(tag));
+    }
+
+    let mut row = TaggedDictBuilder::new(tag);
+
+    for (name, mut values) in columns {
+        match values.len() {
+            0 => row.insert(name, Value::string("")),
+            1 => row.insert_tagged(name, values.remove(0)),
+            _ => row.insert(name, Value::Table(values)),
+        }
+    }
+
+    let body = match preview_body {
+        Some(bytes) => preview(&body, bytes),
+        None => &body[..],
+    };
+
+    row.insert("body", Value::string(body));
+
+    row.into_tagged_value()
+}
+
+fn from_eml(
+    FromEMLArgs { preview_body }: FromEMLArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let preview_body = preview_body.map(|bytes| bytes.item as usize);
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    name_tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+
+        yield ReturnSuccess::value(from_eml_string_to_value(&concat_string, preview_body, name_tag));
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{headers_and_body, preview};
+
+    #[test]
+    fn reads_folded_headers_and_body() {
+        let (headers, body) = headers_and_body(
+            "Subject: Nu\r\n release\r\nFrom: andres@nushell.sh\r\n\r\nHello,\r\n\r\nbye\r\n",
+        );
+
+        assert_eq!(
+            headers,
+            vec![
+                ("subject".to_string(), "Nu release".to_string()),
+                ("from".to_string(), "andres@nushell.sh".to_string())
+            ]
+        );
+        assert_eq!(body, "Hello,\n\nbye");
+    }
+
+    #[test]
+    fn previews_stop_at_character_boundaries() {
+        assert_eq!(preview("Andrés", 5), "Andr");
+        assert_eq!(preview("Andrés", 6), "André");
+        assert_eq!(preview("Nu", 10), "Nu");
+    }
+}
@@ -0,0 +1,136 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use scraper::{ElementRef, Html, Selector};
+
+pub struct FromHTML;
+
+impl WholeStreamCommand for FromHTML {
+    fn name(&self) -> &str {
+        "from-html"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-html")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .html and create a table from each <table> element."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_html(args, registry)
+    }
+}
+
+fn cell_text(cell: ElementRef) -> String {
+    cell.text()
+        .collect::<Vec<_>>()
+        .join("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn convert_html_table_to_nu_value(table: ElementRef, tag: Tag) -> Tagged<Value> {
+    let row_selector = Selector::parse("tr").unwrap();
+    let header_selector = Selector::parse("th").unwrap();
+    let cell_selector = Selector::parse("th, td").unwrap();
+
+    let mut headers: Vec<String> = vec![];
+    let mut rows = vec![];
+
+    for row in table.select(&row_selector) {
+        // A leading row made up only of <th> cells names the columns
+        if headers.is_empty() && rows.is_empty() {
+            let cells: Vec<_> = row.select(&cell_selector).collect();
+            if !cells.is_empty() && cells.len() == row.select(&header_selector).count() {
+                headers = cells.into_iter().map(cell_text).collect();
+                continue;
+            }
+        }
+
+        let mut dict = TaggedDictBuilder::new(tag);
+        for (idx, cell) in row.select(&cell_selector).enumerate() {
+            let column = match headers.get(idx) {
+                Some(header) if !header.is_empty() => header.clone(),
+                _ => format!("Column{}", idx),
+            };
+            dict.insert(column, Value::string(cell_text(cell)));
+        }
+
+        rows.push(dict.into_tagged_value());
+    }
+
+    Value::Table(rows).tagged(tag)
+}
+
+pub fn from_html_string_to_value(s: &str, tag: impl Into<Tag>) -> Vec<Tagged<Value>> {
+    let tag = tag.into();
+    let document = Html::parse_document(s);
+    let table_selector = Selector::parse("table").unwrap();
+
+    document
+        .select(&table_selector)
+        .map(|table| convert_html_table_to_nu_value(table, tag))
+        .collect()
+}
+
+fn from_html(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+        let mut latest_tag: Option<Tag> = None;
+
+        for value in values {
+            let value_tag = value.tag();
+            latest_tag = Some(value_tag);
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                // `fetch` and `open` already convert .html, so tables pass straight through
+                _ => yield ReturnSuccess::value(value),
+            }
+        }
+
+        let tables = if concat_string.is_empty() {
+            None
+        } else {
+            Some(from_html_string_to_value(&concat_string, tag))
+        };
+
+        match tables.as_ref().map(Vec::len) {
+            None => {}
+            // Without any tables, the page is more useful left as text
+            Some(0) => yield ReturnSuccess::value(
+                Value::string(concat_string).tagged(latest_tag.unwrap_or(tag)),
+            ),
+            Some(1) => match tables.unwrap().remove(0) {
+                Tagged { item: Value::Table(rows), .. } => {
+                    for row in rows {
+                        yield ReturnSuccess::value(row);
+                    }
+                }
+                x => yield ReturnSuccess::value(x),
+            },
+            Some(_) => {
+                for table in tables.unwrap() {
+                    yield ReturnSuccess::value(table);
+                }
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,281 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{TimeZone, Utc};
+use indexmap::IndexMap;
+
+pub struct FromICS;
+
+impl WholeStreamCommand for FromICS {
+    fn name(&self) -> &str {
+        "from-ics"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-ics")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .ics (iCalendar) and create a table of its events."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_ics(args, registry)
+    }
+}
+
+/// A `BEGIN:NAME` ... `END:NAME` block of iCalendar or vCard text, with its properties in the
+/// order they were written and the blocks nested inside it.
+#[derive(Debug, Default)]
+pub(crate) struct Component {
+    pub(crate) name: String,
+    pub(crate) properties: Vec<(String, String)>,
+    pub(crate) children: Vec<Component>,
+}
+
+/// Join lines that were folded onto the next line, which continue with a space or tab.
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for line in text.lines() {
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+
+        if !line.trim().is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+
+    lines
+}
+
+/// Split a content line like `DTSTART;TZID=Europe/Madrid:20191010T120000` into its name and
+/// value. Parameters are dropped, minding colons inside quoted parameter values.
+fn content_line(line: &str) -> Option<(String, String)> {
+    let mut quoted = false;
+
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => {
+                let name = line[..idx].split(';').next().unwrap_or("");
+                return Some((name.trim().to_uppercase(), line[idx + 1..].to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Read the components of the content line format shared by iCalendar and vCard.
+pub(crate) fn parse_components(text: &str) -> Result<Vec<Component>, String> {
+    let mut components = vec![];
+    let mut open: Vec<Component> = vec![];
+
+    for line in unfold(text) {
+        let (name, value) =
+            content_line(&line).ok_or_else(|| format!("expected NAME:value, found {}", line))?;
+
+        match name.as_str() {
+            "BEGIN" => open.push(Component {
+                name: value.trim().to_uppercase(),
+                ..Component::default()
+            }),
+            "END" => {
+                let component = match open.pop() {
+                    Some(component) if component.name == value.trim().to_uppercase() => component,
+                    _ => return Err(format!("unexpected END:{}", value)),
+                };
+
+                match open.last_mut() {
+                    Some(parent) => parent.children.push(component),
+                    None => components.push(component),
+                }
+            }
+            _ => match open.last_mut() {
+                Some(component) => component.properties.push((name, value)),
+                None => return Err(format!("{} is outside of any BEGIN and END", name)),
+            },
+        }
+    }
+
+    match open.pop() {
+        Some(component) => Err(format!("missing END:{}", component.name)),
+        None => Ok(components),
+    }
+}
+
+/// Undo the escaping of commas, semicolons, backslashes and newlines in text values.
+pub(crate) fn unescape(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(escaped) => out.push(escaped),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+/// A row with a column for each property and nested component, named in lowercase. Anything
+/// written more than once becomes a table of all of them.
+pub(crate) fn component_to_row(
+    component: &Component,
+    tag: Tag,
+    to_value: &dyn Fn(&str) -> Value,
+) -> Tagged<Value> {
+    let mut columns: IndexMap<String, Vec<Tagged<Value>>> = IndexMap::new();
+
+    for (name, value) in component.properties.iter() {
+        columns
+            .entry(name.to_lowercase())
+            .or_insert_with(Vec::new)
+            .push(to_value(value).tagged(tag));
+    }
+
+    for child in component.children.iter() {
+        columns
+            .entry(child.name.to_lowercase())
+            .or_insert_with(Vec::new)
+            .push(component_to_row(child, tag, to_value));
+    }
+
+    let mut row = TaggedDictBuilder::new(tag);
+
+    for (name, mut values) in columns {
+        if values.len() == 1 {
+            row.insert_tagged(name, values.remove(0));
+        } else {
+            row.insert_tagged(name, Value::Table(values).tagged(tag));
+        }
+    }
+
+    row.into_tagged_value()
+}
+
+/// Times written in UTC, like `20191010T120000Z`, become dates. Everything else is text.
+fn ics_value(value: &str) -> Value {
+    match Utc.datetime_from_str(value, "%Y%m%dT%H%M%SZ") {
+        Ok(date) => Value::Primitive(Primitive::Date(date)),
+        Err(_) => Value::string(unescape(value)),
+    }
+}
+
+pub fn from_ics_string_to_value(
+    s: &str,
+    tag: impl Into<Tag>,
+) -> Result<Vec<Tagged<Value>>, String> {
+    let tag = tag.into();
+    let mut events = vec![];
+
+    for calendar in parse_components(s)? {
+        for child in calendar.children.iter() {
+            if child.name == "VEVENT" {
+                events.push(component_to_row(child, tag, &ics_value));
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+fn from_ics(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+        let mut latest_tag: Option<Tag> = None;
+
+        for value in values {
+            let value_tag = value.tag();
+            latest_tag = Some(value_tag);
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+
+        match from_ics_string_to_value(&concat_string, tag) {
+            Ok(events) => {
+                for event in events {
+                    yield ReturnSuccess::value(event);
+                }
+            }
+            Err(reason) => if let Some(last_tag) = latest_tag {
+                yield Err(ShellError::labeled_error_with_secondary(
+                    "Could not parse as iCalendar",
+                    reason,
+                    tag,
+                    "value originates from here",
+                    last_tag,
+                ))
+            },
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_components, unescape};
+
+    #[test]
+    fn reads_nested_components_and_folded_lines() {
+        let text = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nSUMMARY:Nu\r\n  release\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let calendars = parse_components(text).unwrap();
+
+        assert_eq!(calendars.len(), 1);
+        assert_eq!(calendars[0].children[0].name, "VEVENT");
+        assert_eq!(
+            calendars[0].children[0].properties,
+            vec![("SUMMARY".to_string(), "Nu release".to_string())]
+        );
+    }
+
+    #[test]
+    fn unbalanced_components_are_errors() {
+        assert!(parse_components("BEGIN:VEVENT\nSUMMARY:Nu\n").is_err());
+        assert!(parse_components("BEGIN:VEVENT\nEND:VTODO\n").is_err());
+    }
+
+    #[test]
+    fn unescapes_text() {
+        assert_eq!(
+            unescape("Room 1\\, 2nd floor\\nBring a laptop"),
+            "Room 1, 2nd floor\nBring a laptop"
+        );
+    }
+}
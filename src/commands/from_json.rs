@@ -19,7 +19,7 @@ impl WholeStreamCommand for FromJSON {
     }
 
     fn usage(&self) -> &str {
-        "Parse text as .json and create table."
+        "Parse text as .json and create table. With --objects, each line is read as its own JSON document (JSON Lines)."
     }
 
     fn run(
@@ -68,19 +68,80 @@ pub fn from_json_string_to_value(
     Ok(convert_json_value_to_nu_value(&v, tag))
 }
 
+/// Parse one line of JSON Lines text, if it isn't blank.
+fn json_line(
+    line: &str,
+    name_tag: Tag,
+    value_tag: Tag,
+) -> Option<Result<ReturnSuccess, ShellError>> {
+    if line.trim().is_empty() {
+        return None;
+    }
+
+    Some(
+        match from_json_string_to_value(line.to_string(), name_tag) {
+            Ok(x) => ReturnSuccess::value(x),
+            Err(_) => Err(ShellError::labeled_error_with_secondary(
+                "Could not parse as JSON",
+                "input cannot be parsed as JSON",
+                name_tag,
+                "value originates from here",
+                value_tag,
+            )),
+        },
+    )
+}
+
 fn from_json(
     FromJSONArgs { objects }: FromJSONArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_tag = name;
+    let mut values = input.values;
+
+    if objects {
+        // Each line is its own document, so rows go out as soon as their line has arrived
+        let stream = async_stream_block! {
+            let mut pending = String::new();
+            let mut latest_tag = name_tag;
+
+            while let Some(value) = values.next().await {
+                latest_tag = value.tag();
+                match value.item {
+                    Value::Primitive(Primitive::String(s)) => {
+                        pending.push_str(&s);
+                        pending.push_str("\n");
+
+                        while let Some(idx) = pending.find('\n') {
+                            let line: String = pending.drain(..=idx).collect();
+                            if let Some(row) = json_line(&line, name_tag, latest_tag) {
+                                yield row;
+                            }
+                        }
+                    }
+                    _ => yield Err(ShellError::labeled_error_with_secondary(
+                        "Expected a string from pipeline",
+                        "requires string input",
+                        name_tag,
+                        "value originates from here",
+                        latest_tag,
+                    )),
+                }
+            }
 
-    let stream = async_stream_block! {
-        let values: Vec<Tagged<Value>> = input.values.collect().await;
+            if let Some(row) = json_line(&pending, name_tag, latest_tag) {
+                yield row;
+            }
+        };
 
+        return Ok(stream.to_output_stream());
+    }
+
+    let stream = async_stream_block! {
         let mut concat_string = String::new();
         let mut latest_tag: Option<Tag> = None;
 
-        for value in values {
+        while let Some(value) = values.next().await {
             let value_tag = value.tag();
             latest_tag = Some(value_tag);
             match value.item {
@@ -99,48 +160,24 @@ fn from_json(
             }
         }
 
-
-        if objects {
-            for json_str in concat_string.lines() {
-                if json_str.is_empty() {
-                    continue;
-                }
-
-                match from_json_string_to_value(json_str.to_string(), name_tag) {
-                    Ok(x) =>
-                        yield ReturnSuccess::value(x),
-                    Err(_) => {
-                        if let Some(last_tag) = latest_tag {
-                            yield Err(ShellError::labeled_error_with_secondary(
-                                "Could nnot parse as JSON",
-                                "input cannot be parsed as JSON",
-                                name_tag,
-                                "value originates from here",
-                                last_tag))
+        match from_json_string_to_value(concat_string, name_tag) {
+            Ok(x) =>
+                match x {
+                    Tagged { item: Value::Table(list), .. } => {
+                        for l in list {
+                            yield ReturnSuccess::value(l);
                         }
                     }
+                    x => yield ReturnSuccess::value(x),
                 }
-            }
-        } else {
-            match from_json_string_to_value(concat_string, name_tag) {
-                Ok(x) =>
-                    match x {
-                        Tagged { item: Value::Table(list), .. } => {
-                            for l in list {
-                                yield ReturnSuccess::value(l);
-                            }
-                        }
-                        x => yield ReturnSuccess::value(x),
-                    }
-                Err(_) => {
-                    if let Some(last_tag) = latest_tag {
-                        yield Err(ShellError::labeled_error_with_secondary(
-                            "Could not parse as JSON",
-                            "input cannot be parsed as JSON",
-                            name_tag,
-                            "value originates from here",
-                            last_tag))
-                    }
+            Err(_) => {
+                if let Some(last_tag) = latest_tag {
+                    yield Err(ShellError::labeled_error_with_secondary(
+                        "Could not parse as JSON",
+                        "input cannot be parsed as JSON",
+                        name_tag,
+                        "value originates from here",
+                        last_tag))
                 }
             }
         }
@@ -0,0 +1,106 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::interchange::InterchangeSeed;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+use serde::de::DeserializeSeed;
+
+pub struct FromMsgpack;
+
+impl WholeStreamCommand for FromMsgpack {
+    fn name(&self) -> &str {
+        "from-msgpack"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-msgpack")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse binary data as .msgpack (MessagePack) and create table."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_msgpack(args, registry)
+    }
+}
+
+pub fn from_msgpack_bytes_to_value(
+    bytes: &[u8],
+    tag: impl Into<Tag>,
+) -> Result<Tagged<Value>, String> {
+    let mut deserializer = rmp_serde::Deserializer::new(bytes);
+
+    InterchangeSeed(tag.into())
+        .deserialize(&mut deserializer)
+        .map_err(|err| err.to_string())
+}
+
+fn from_msgpack(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::Binary(vb)) =>
+                    match from_msgpack_bytes_to_value(&vb, tag) {
+                        Ok(Tagged { item: Value::Table(rows), .. }) => {
+                            for row in rows {
+                                yield ReturnSuccess::value(row);
+                            }
+                        }
+                        Ok(x) => yield ReturnSuccess::value(x),
+                        Err(reason) => yield Err(ShellError::labeled_error_with_secondary(
+                            "Could not parse as MessagePack",
+                            reason,
+                            tag,
+                            "value originates from here",
+                            value_tag,
+                        )),
+                    }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected binary data from pipeline",
+                    "requires binary input",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_msgpack_bytes_to_value;
+    use crate::commands::to_msgpack::value_to_msgpack_bytes;
+    use crate::data::{TaggedDictBuilder, Value};
+    use crate::prelude::*;
+
+    #[test]
+    fn rows_survive_a_round_trip() {
+        let mut row = TaggedDictBuilder::new(Tag::unknown());
+        row.insert("name", Value::string("nu"));
+        row.insert("stars", Value::int(42));
+        row.insert("logo", Value::binary(vec![0, 159, 146, 150]));
+        row.insert("license", Value::nothing());
+        let row = row.into_tagged_value();
+
+        let bytes = value_to_msgpack_bytes(&row).unwrap();
+
+        assert_eq!(
+            from_msgpack_bytes_to_value(&bytes, Tag::unknown()).unwrap(),
+            row
+        );
+    }
+}
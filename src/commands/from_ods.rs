@@ -0,0 +1,86 @@
+use crate::commands::from_xlsx::workbook_to_value;
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, Value};
+use crate::errors::ShellError;
+use crate::prelude::*;
+use calamine::{Ods, Reader};
+use std::io::Cursor;
+
+pub struct FromODS;
+
+#[derive(Deserialize)]
+pub struct FromODSArgs {
+    sheet: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for FromODS {
+    fn name(&self) -> &str {
+        "from-ods"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-ods").named("sheet", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Parse binary OpenDocument spreadsheet (.ods) data and create a table for each sheet, or the rows of a single --sheet."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_ods)?.run()
+    }
+}
+
+pub fn from_ods_bytes_to_value(
+    bytes: Vec<u8>,
+    sheet: Option<&Tagged<String>>,
+    tag: Tag,
+) -> Result<Tagged<Value>, ShellError> {
+    let mut workbook: Ods<_> = Ods::new(Cursor::new(bytes)).map_err(|_| {
+        ShellError::labeled_error(
+            "Could not parse as ODS",
+            "input cannot be parsed as ODS",
+            tag,
+        )
+    })?;
+
+    workbook_to_value(&mut workbook, sheet, tag)
+}
+
+pub fn from_ods(
+    FromODSArgs { sheet }: FromODSArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::Binary(vb)) =>
+                    match from_ods_bytes_to_value(vb, sheet.as_ref(), name) {
+                        Ok(Tagged { item: Value::Table(rows), .. }) => {
+                            for row in rows {
+                                yield ReturnSuccess::value(row);
+                            }
+                        }
+                        Ok(x) => yield ReturnSuccess::value(x),
+                        Err(err) => yield Err(err),
+                    }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected binary data from pipeline",
+                    "requires binary input",
+                    name,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,268 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{TimeZone, Utc};
+use parquet::basic::{LogicalType, Type as PhysicalType};
+use parquet::file::reader::{FileReader, Length, SerializedFileReader, TryClone};
+use parquet::record::{Row, RowAccessor, RowFormatter};
+use parquet::schema::types::{Type, TypePtr};
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+pub struct FromParquet;
+
+impl WholeStreamCommand for FromParquet {
+    fn name(&self) -> &str {
+        "from-parquet"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-parquet")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse binary data as .parquet and create table. Row groups are decoded as the rows are needed; dates, decimals and nested columns are shown as text."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_parquet(args, registry)
+    }
+}
+
+/// The parquet file as read from the pipeline. Clones share the bytes, so the reader can open
+/// each column without copying the file.
+struct ParquetBytes {
+    bytes: Arc<Vec<u8>>,
+    position: u64,
+}
+
+impl ParquetBytes {
+    fn cursor(&self) -> Cursor<&[u8]> {
+        let mut cursor = Cursor::new(&self.bytes[..]);
+        cursor.set_position(self.position);
+        cursor
+    }
+}
+
+impl Read for ParquetBytes {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut cursor = self.cursor();
+        let read = cursor.read(buf)?;
+        self.position = cursor.position();
+        Ok(read)
+    }
+}
+
+impl Seek for ParquetBytes {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let mut cursor = self.cursor();
+        self.position = cursor.seek(pos)?;
+        Ok(self.position)
+    }
+}
+
+impl Length for ParquetBytes {
+    fn len(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+}
+
+impl TryClone for ParquetBytes {
+    fn try_clone(&self) -> parquet::errors::Result<Self> {
+        Ok(ParquetBytes {
+            bytes: self.bytes.clone(),
+            position: self.position,
+        })
+    }
+}
+
+fn open(bytes: &Arc<Vec<u8>>) -> Result<SerializedFileReader<ParquetBytes>, String> {
+    SerializedFileReader::new(ParquetBytes {
+        bytes: bytes.clone(),
+        position: 0,
+    })
+    .map_err(|err| err.to_string())
+}
+
+/// The value of a primitive column, read with the accessor its physical and logical type call
+/// for. Anything without a matching accessor is left for the caller to show as text.
+fn convert_parquet_column(row: &Row, idx: usize, column: &Type) -> Option<Value> {
+    if !column.is_primitive() {
+        return None;
+    }
+
+    let logical_type = column.get_basic_info().logical_type();
+
+    let value = match (column.get_physical_type(), logical_type) {
+        (PhysicalType::BOOLEAN, _) => Value::boolean(row.get_bool(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::INT_8) => Value::number(row.get_byte(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::INT_16) => Value::number(row.get_short(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::UINT_8) => Value::number(row.get_ubyte(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::UINT_16) => Value::number(row.get_ushort(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::UINT_32) => Value::number(row.get_uint(idx).ok()?),
+        (PhysicalType::INT32, LogicalType::INT_32)
+        | (PhysicalType::INT32, LogicalType::NONE) => Value::number(row.get_int(idx).ok()?),
+        (PhysicalType::INT64, LogicalType::UINT_64) => Value::number(row.get_ulong(idx).ok()?),
+        (PhysicalType::INT64, LogicalType::INT_64)
+        | (PhysicalType::INT64, LogicalType::NONE) => Value::number(row.get_long(idx).ok()?),
+        (PhysicalType::INT64, LogicalType::TIMESTAMP_MILLIS) | (PhysicalType::INT96, _) => {
+            timestamp(row.get_timestamp(idx).ok()?)
+        }
+        (PhysicalType::FLOAT, _) => {
+            let float = row.get_float(idx).ok()?;
+            if !float.is_finite() {
+                return None;
+            }
+            Value::number(float)
+        }
+        (PhysicalType::DOUBLE, _) => {
+            let double = row.get_double(idx).ok()?;
+            if !double.is_finite() {
+                return None;
+            }
+            Value::number(double)
+        }
+        (PhysicalType::BYTE_ARRAY, LogicalType::UTF8)
+        | (PhysicalType::BYTE_ARRAY, LogicalType::ENUM)
+        | (PhysicalType::BYTE_ARRAY, LogicalType::JSON) => {
+            Value::string(row.get_string(idx).ok()?.clone())
+        }
+        (PhysicalType::BYTE_ARRAY, LogicalType::NONE)
+        | (PhysicalType::BYTE_ARRAY, LogicalType::BSON) => {
+            Value::binary(row.get_bytes(idx).ok()?.data().to_vec())
+        }
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Parquet timestamps count milliseconds from the epoch.
+fn timestamp(millis: u64) -> Value {
+    Value::Primitive(Primitive::Date(Utc.timestamp_millis(millis as i64)))
+}
+
+fn convert_parquet_row_to_nu_value(row: &Row, columns: &[TypePtr], tag: Tag) -> Tagged<Value> {
+    let mut collected = TaggedDictBuilder::new(tag);
+
+    for (idx, column) in columns.iter().enumerate().take(row.len()) {
+        let value = match convert_parquet_column(row, idx, column) {
+            Some(value) => value,
+            None => match row.fmt(idx).to_string() {
+                ref text if text == "null" => Value::nothing(),
+                text => Value::string(text),
+            },
+        };
+
+        collected.insert(column.name(), value);
+    }
+
+    collected.into_tagged_value()
+}
+
+fn row_group_count(bytes: &Arc<Vec<u8>>) -> Result<usize, String> {
+    Ok(open(bytes)?.num_row_groups())
+}
+
+/// The rows of one row group. The reader can't be kept across the stream's yields, so it is
+/// opened again for each group; only the footer is read twice, and the rows of groups that are
+/// never reached aren't decoded at all.
+fn read_row_group(
+    bytes: &Arc<Vec<u8>>,
+    idx: usize,
+    tag: Tag,
+) -> Result<Vec<Tagged<Value>>, String> {
+    let reader = open(bytes)?;
+    let columns = reader
+        .metadata()
+        .file_metadata()
+        .schema()
+        .get_fields()
+        .to_vec();
+    let group = reader.get_row_group(idx).map_err(|err| err.to_string())?;
+    let rows = group.get_row_iter(None).map_err(|err| err.to_string())?;
+
+    Ok(rows
+        .map(|row| convert_parquet_row_to_nu_value(&row, &columns, tag))
+        .collect())
+}
+
+fn from_parquet(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::Binary(vb)) => {
+                    let bytes = Arc::new(vb);
+
+                    let groups = match row_group_count(&bytes) {
+                        Ok(groups) => groups,
+                        Err(reason) => {
+                            yield Err(ShellError::labeled_error_with_secondary(
+                                "Could not parse as Parquet",
+                                reason,
+                                tag,
+                                "value originates from here",
+                                value_tag,
+                            ));
+                            continue;
+                        }
+                    };
+
+                    for idx in 0..groups {
+                        match read_row_group(&bytes, idx, tag) {
+                            Ok(rows) => {
+                                for row in rows {
+                                    yield ReturnSuccess::value(row);
+                                }
+                            }
+                            Err(reason) => {
+                                yield Err(ShellError::labeled_error_with_secondary(
+                                    "Could not read Parquet row group",
+                                    reason,
+                                    tag,
+                                    "value originates from here",
+                                    value_tag,
+                                ));
+                                break;
+                            }
+                        }
+                    }
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected binary data from pipeline",
+                    "requires binary input",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timestamp;
+    use crate::data::{Primitive, Value};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn timestamps_count_milliseconds_from_the_epoch() {
+        assert_eq!(
+            timestamp(1_570_752_000_500),
+            Value::Primitive(Primitive::Date(Utc.ymd(2019, 10, 11).and_hms_milli(0, 0, 0, 500)))
+        );
+    }
+}
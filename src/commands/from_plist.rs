@@ -0,0 +1,121 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use chrono::{DateTime, Utc};
+use std::io::Cursor;
+use std::time::SystemTime;
+
+pub struct FromPlist;
+
+impl WholeStreamCommand for FromPlist {
+    fn name(&self) -> &str {
+        "from-plist"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-plist")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse an Apple property list (.plist), either XML text or binary data, and create table."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_plist(args, registry)
+    }
+}
+
+fn convert_plist_value_to_nu_value(v: &plist::Value, tag: impl Into<Tag>) -> Tagged<Value> {
+    let tag = tag.into();
+
+    match v {
+        plist::Value::Array(a) => Value::Table(
+            a.iter()
+                .map(|x| convert_plist_value_to_nu_value(x, tag))
+                .collect(),
+        )
+        .tagged(tag),
+        plist::Value::Dictionary(d) => {
+            let mut collected = TaggedDictBuilder::new(tag);
+
+            for (k, v) in d.iter() {
+                collected.insert_tagged(k.clone(), convert_plist_value_to_nu_value(v, tag));
+            }
+
+            collected.into_tagged_value()
+        }
+        plist::Value::Boolean(b) => Value::boolean(*b).tagged(tag),
+        plist::Value::Data(d) => Value::binary(d.clone()).tagged(tag),
+        plist::Value::Date(d) => {
+            let time: SystemTime = d.clone().into();
+            let date: DateTime<Utc> = time.into();
+            Value::Primitive(Primitive::Date(date)).tagged(tag)
+        }
+        plist::Value::Real(f) => Value::number(*f).tagged(tag),
+        plist::Value::Integer(i) => match (i.as_signed(), i.as_unsigned()) {
+            (Some(i), _) => Value::number(i).tagged(tag),
+            (None, Some(u)) => Value::number(u).tagged(tag),
+            (None, None) => Value::nothing().tagged(tag),
+        },
+        plist::Value::String(s) => Value::string(s).tagged(tag),
+        _ => Value::nothing().tagged(tag),
+    }
+}
+
+pub fn from_plist_bytes_to_value(
+    bytes: Vec<u8>,
+    tag: impl Into<Tag>,
+) -> Result<Tagged<Value>, plist::Error> {
+    let v = plist::Value::from_reader(Cursor::new(bytes))?;
+    Ok(convert_plist_value_to_nu_value(&v, tag))
+}
+
+fn from_plist(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        for value in values {
+            let value_tag = value.tag();
+            let bytes = match value.item {
+                Value::Primitive(Primitive::String(s)) => s.into_bytes(),
+                Value::Primitive(Primitive::Binary(b)) => b,
+                _ => {
+                    yield Err(ShellError::labeled_error_with_secondary(
+                        "Expected text or binary data from pipeline",
+                        "requires text or binary input",
+                        tag,
+                        "value originates from here",
+                        value_tag,
+                    ));
+                    continue;
+                }
+            };
+
+            match from_plist_bytes_to_value(bytes, tag) {
+                Ok(Tagged { item: Value::Table(list), .. }) => {
+                    for l in list {
+                        yield ReturnSuccess::value(l);
+                    }
+                }
+                Ok(x) => yield ReturnSuccess::value(x),
+                Err(_) => yield Err(ShellError::labeled_error_with_secondary(
+                    "Could not parse as plist",
+                    "input cannot be parsed as a property list",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,118 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::interchange::InterchangeSeed;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+use serde::de::DeserializeSeed;
+
+pub struct FromRON;
+
+impl WholeStreamCommand for FromRON {
+    fn name(&self) -> &str {
+        "from-ron"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-ron")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .ron (Rusty Object Notation) and create table."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_ron(args, registry)
+    }
+}
+
+/// Structs become rows and tuples tables. Struct and enum names are left out, as they only
+/// matter to the Rust types the file was written for.
+pub fn from_ron_string_to_value(s: &str, tag: impl Into<Tag>) -> Result<Tagged<Value>, String> {
+    let mut deserializer = ron::de::Deserializer::from_str(s).map_err(|err| err.to_string())?;
+
+    let value = InterchangeSeed(tag.into())
+        .deserialize(&mut deserializer)
+        .map_err(|err| err.to_string())?;
+
+    deserializer.end().map_err(|err| err.to_string())?;
+
+    Ok(value)
+}
+
+fn from_ron(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+        let mut latest_tag: Option<Tag> = None;
+
+        for value in values {
+            let value_tag = value.tag();
+            latest_tag = Some(value_tag);
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+
+        match from_ron_string_to_value(&concat_string, tag) {
+            Ok(Tagged { item: Value::Table(list), .. }) => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            Ok(x) => yield ReturnSuccess::value(x),
+            Err(reason) => if let Some(last_tag) = latest_tag {
+                yield Err(ShellError::labeled_error_with_secondary(
+                    "Could not parse as RON",
+                    reason,
+                    tag,
+                    "value originates from here",
+                    last_tag,
+                ))
+            },
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_ron_string_to_value;
+    use crate::prelude::*;
+
+    #[test]
+    fn named_structs_become_rows() {
+        let value = from_ron_string_to_value(
+            "Config(name: \"nu\", tags: [\"shell\"], limit: Some(3))",
+            Tag::unknown(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            value.get_data_by_key("name").unwrap().as_string().unwrap(),
+            "nu"
+        );
+        assert_eq!(
+            value.get_data_by_key("limit").unwrap().as_string().unwrap(),
+            "3"
+        );
+    }
+}
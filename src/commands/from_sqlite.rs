@@ -83,7 +83,7 @@ pub fn convert_sqlite_file_to_nu_value(
     Ok(Value::Table(meta_out).tagged(tag))
 }
 
-fn convert_sqlite_row_to_nu_value(
+pub(crate) fn convert_sqlite_row_to_nu_value(
     row: &Row,
     tag: impl Into<Tag> + Clone,
 ) -> Result<Tagged<Value>, rusqlite::Error> {
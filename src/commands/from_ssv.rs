@@ -0,0 +1,295 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+
+pub struct FromSSV;
+
+const DEFAULT_MINIMUM_SPACES: usize = 2;
+
+#[derive(Deserialize)]
+pub struct FromSSVArgs {
+    headerless: bool,
+    #[serde(rename(deserialize = "aligned-columns"))]
+    aligned_columns: bool,
+    #[serde(rename(deserialize = "minimum-spaces"))]
+    minimum_spaces: Option<Tagged<u64>>,
+}
+
+impl WholeStreamCommand for FromSSV {
+    fn name(&self) -> &str {
+        "from-ssv"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-ssv")
+            .switch("headerless")
+            .switch("aligned-columns")
+            .named("minimum-spaces", SyntaxShape::Int)
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text where columns are separated by spaces, like the output of ps or df, and create table. Columns are split at runs of --minimum-spaces spaces (2 by default), or with --aligned-columns, by where each header sits."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_ssv)?.run()
+    }
+}
+
+type Span = (usize, usize);
+
+/// Where the fields of a line start and end, taking fields to be separated by at least
+/// `minimum_spaces` whitespace characters.
+fn field_spans(line: &[char], minimum_spaces: usize) -> Vec<Span> {
+    let minimum_spaces = std::cmp::max(minimum_spaces, 1);
+    let mut spans = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut spaces = 0;
+
+    for (idx, c) in line.iter().enumerate() {
+        if c.is_whitespace() {
+            spaces += 1;
+
+            if spaces >= minimum_spaces {
+                if let Some(start) = start.take() {
+                    spans.push((start, end));
+                }
+            }
+        } else {
+            if start.is_none() {
+                start = Some(idx);
+            }
+            spaces = 0;
+            end = idx + 1;
+        }
+    }
+
+    if let Some(start) = start {
+        spans.push((start, end));
+    }
+
+    spans
+}
+
+fn text(line: &[char], (start, end): Span) -> String {
+    line[start..end].iter().collect()
+}
+
+/// Split a row at the same runs of spaces as the header. Anything past the last column is
+/// kept as part of it.
+fn separated_cells(line: &[char], columns: usize, minimum_spaces: usize) -> Vec<String> {
+    let mut spans = field_spans(line, minimum_spaces);
+
+    if columns > 0 && spans.len() > columns {
+        let last = (spans[columns - 1].0, spans[spans.len() - 1].1);
+        spans.truncate(columns - 1);
+        spans.push(last);
+    }
+
+    spans.into_iter().map(|span| text(line, span)).collect()
+}
+
+/// Give each word of a row to the header it sits under. Words that aren't under any header,
+/// like right-aligned numbers, belong to the nearest header on their left.
+fn aligned_cells(line: &[char], headers: &[Span]) -> Vec<String> {
+    let mut columns: Vec<Option<Span>> = vec![None; headers.len()];
+
+    for (start, end) in field_spans(line, 1) {
+        let column = headers
+            .iter()
+            .position(|(header_start, header_end)| start < *header_end && *header_start < end)
+            .or_else(|| {
+                headers
+                    .iter()
+                    .rposition(|(header_start, _)| *header_start <= start)
+            })
+            .unwrap_or(0);
+
+        columns[column] = Some(match columns[column] {
+            Some((first, _)) => (first, end),
+            None => (start, end),
+        });
+    }
+
+    columns
+        .into_iter()
+        .map(|span| span.map(|span| text(line, span)).unwrap_or_default())
+        .collect()
+}
+
+pub fn from_ssv_string_to_value(
+    s: &str,
+    headerless: bool,
+    aligned_columns: bool,
+    minimum_spaces: usize,
+    tag: impl Into<Tag>,
+) -> Tagged<Value> {
+    let tag = tag.into();
+    let lines: Vec<Vec<char>> = s
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().collect())
+        .collect();
+
+    let header_line = match lines.first() {
+        Some(line) => line,
+        None => return Value::Table(vec![]).tagged(tag),
+    };
+
+    let header_spans = field_spans(header_line, minimum_spaces);
+    let headers: Vec<String> = if headerless {
+        (1..=header_spans.len())
+            .map(|idx| format!("Column{}", idx))
+            .collect()
+    } else {
+        header_spans
+            .iter()
+            .map(|span| text(header_line, *span))
+            .collect()
+    };
+
+    let rows = if headerless { &lines[..] } else { &lines[1..] };
+
+    let rows = rows
+        .iter()
+        .map(|line| {
+            let cells = if aligned_columns {
+                aligned_cells(line, &header_spans)
+            } else {
+                separated_cells(line, headers.len(), minimum_spaces)
+            };
+
+            let mut row = TaggedDictBuilder::new(tag);
+            for (idx, header) in headers.iter().enumerate() {
+                let cell = cells.get(idx).cloned().unwrap_or_default();
+                row.insert(header.clone(), Value::Primitive(Primitive::String(cell)));
+            }
+            row.into_tagged_value()
+        })
+        .collect();
+
+    Value::Table(rows).tagged(tag)
+}
+
+fn from_ssv(
+    FromSSVArgs {
+        headerless,
+        aligned_columns,
+        minimum_spaces,
+    }: FromSSVArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let minimum_spaces = minimum_spaces
+        .map(|spaces| spaces.item as usize)
+        .unwrap_or(DEFAULT_MINIMUM_SPACES);
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    name_tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+
+        match from_ssv_string_to_value(&concat_string, headerless, aligned_columns, minimum_spaces, name_tag) {
+            Tagged { item: Value::Table(list), .. } => {
+                for l in list {
+                    yield ReturnSuccess::value(l);
+                }
+            }
+            x => yield ReturnSuccess::value(x),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_ssv_string_to_value;
+    use crate::prelude::*;
+
+    fn cells(input: &str, aligned_columns: bool, minimum_spaces: usize) -> Vec<Vec<String>> {
+        match from_ssv_string_to_value(
+            input,
+            false,
+            aligned_columns,
+            minimum_spaces,
+            Tag::unknown(),
+        )
+        .item
+        {
+            Value::Table(rows) => rows
+                .iter()
+                .map(|row| {
+                    row.data_descriptors()
+                        .iter()
+                        .map(|column| {
+                            format!(
+                                "{}={}",
+                                column,
+                                row.get_data_by_key(column).unwrap().as_string().unwrap()
+                            )
+                        })
+                        .collect()
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn splits_at_runs_of_spaces() {
+        let input = "
+            Filesystem  Mounted on
+            /dev/sda1   /boot  extra
+        ";
+
+        assert_eq!(
+            cells(input, false, 2),
+            vec![vec!["Filesystem=/dev/sda1", "Mounted on=/boot  extra"]]
+        );
+    }
+
+    #[test]
+    fn aligned_columns_follow_the_headers() {
+        let input = "
+USER    PID %CPU COMMAND
+root      1  0.0 /sbin/init splash
+andres 4242 12.5 nu
+";
+
+        assert_eq!(
+            cells(input, true, 1),
+            vec![
+                vec![
+                    "USER=root",
+                    "PID=1",
+                    "%CPU=0.0",
+                    "COMMAND=/sbin/init splash"
+                ],
+                vec!["USER=andres", "PID=4242", "%CPU=12.5", "COMMAND=nu"],
+            ]
+        );
+    }
+}
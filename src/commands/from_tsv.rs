@@ -1,13 +1,17 @@
+use crate::commands::from_csv::{from_delimited_data, single_byte, DelimitedOptions};
 use crate::commands::WholeStreamCommand;
-use crate::data::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
-use csv::ReaderBuilder;
 
 pub struct FromTSV;
 
 #[derive(Deserialize)]
 pub struct FromTSVArgs {
     headerless: bool,
+    separator: Option<Tagged<String>>,
+    quote: Option<Tagged<String>>,
+    skip: Option<Tagged<u64>>,
+    #[serde(rename(deserialize = "no-infer"))]
+    no_infer: bool,
 }
 
 impl WholeStreamCommand for FromTSV {
@@ -16,11 +20,18 @@ impl WholeStreamCommand for FromTSV {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from-tsv").switch("headerless")
+        Signature::build("from-tsv")
+            .switch("headerless")
+            .named("separator", SyntaxShape::String)
+            .named("quote", SyntaxShape::String)
+            .named("skip", SyntaxShape::Int)
+            .switch("no-infer")
+            // Inference is the default now, so this is only accepted for older pipelines
+            .switch("infer-types")
     }
 
     fn usage(&self) -> &str {
-        "Parse text as .tsv and create table."
+        "Parse text as .tsv and create table. Takes the same flags as from-csv."
     }
 
     fn run(
@@ -32,108 +43,23 @@ impl WholeStreamCommand for FromTSV {
     }
 }
 
-pub fn from_tsv_string_to_value(
-    s: String,
-    headerless: bool,
-    tag: impl Into<Tag>,
-) -> Result<Tagged<Value>, csv::Error> {
-    let mut reader = ReaderBuilder::new()
-        .has_headers(false)
-        .delimiter(b'\t')
-        .from_reader(s.as_bytes());
-    let tag = tag.into();
-
-    let mut fields: VecDeque<String> = VecDeque::new();
-    let mut iter = reader.records();
-    let mut rows = vec![];
-
-    if let Some(result) = iter.next() {
-        let line = result?;
-
-        for (idx, item) in line.iter().enumerate() {
-            if headerless {
-                fields.push_back(format!("Column{}", idx + 1));
-            } else {
-                fields.push_back(item.to_string());
-            }
-        }
-    }
-
-    loop {
-        if let Some(row_values) = iter.next() {
-            let row_values = row_values?;
-
-            let mut row = TaggedDictBuilder::new(tag);
-
-            for (idx, entry) in row_values.iter().enumerate() {
-                row.insert_tagged(
-                    fields.get(idx).unwrap(),
-                    Value::Primitive(Primitive::String(String::from(entry))).tagged(tag),
-                );
-            }
-
-            rows.push(row.into_tagged_value());
-        } else {
-            break;
-        }
-    }
-
-    Ok(Tagged::from_item(Value::Table(rows), tag))
-}
-
 fn from_tsv(
     FromTSVArgs {
-        headerless: skip_headers,
+        headerless,
+        separator,
+        quote,
+        skip,
+        no_infer,
     }: FromTSVArgs,
-    RunnableContext { input, name, .. }: RunnableContext,
+    runnable_context: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
-    let name_tag = name;
-
-    let stream = async_stream_block! {
-        let values: Vec<Tagged<Value>> = input.values.collect().await;
-
-        let mut concat_string = String::new();
-        let mut latest_tag: Option<Tag> = None;
-
-        for value in values {
-            let value_tag = value.tag();
-            latest_tag = Some(value_tag);
-            match value.item {
-                Value::Primitive(Primitive::String(s)) => {
-                    concat_string.push_str(&s);
-                    concat_string.push_str("\n");
-                }
-                _ => yield Err(ShellError::labeled_error_with_secondary(
-                    "Expected a string from pipeline",
-                    "requires string input",
-                    name_tag,
-                    "value originates from here",
-                    value_tag,
-                )),
-
-            }
-        }
-
-        match from_tsv_string_to_value(concat_string, skip_headers, name_tag) {
-            Ok(x) => match x {
-                Tagged { item: Value::Table(list), .. } => {
-                    for l in list {
-                        yield ReturnSuccess::value(l);
-                    }
-                }
-                x => yield ReturnSuccess::value(x),
-            },
-            Err(_) => if let Some(last_tag) = latest_tag {
-                yield Err(ShellError::labeled_error_with_secondary(
-                    "Could not parse as TSV",
-                    "input cannot be parsed as TSV",
-                    name_tag,
-                    "value originates from here",
-                    last_tag,
-                ))
-            } ,
-        }
+    let options = DelimitedOptions {
+        headerless,
+        separator: single_byte(separator, b'\t')?,
+        quote: single_byte(quote, b'"')?,
+        skip: skip.map(|skip| skip.item as usize).unwrap_or(0),
+        infer_types: !no_infer,
     };
 
-    Ok(stream.to_output_stream())
+    from_delimited_data(options, "TSV", runnable_context)
 }
@@ -14,7 +14,7 @@ impl WholeStreamCommand for FromURL {
     }
 
     fn usage(&self) -> &str {
-        "Parse url-encoded string as a table."
+        "Parse url-encoded string, or the query string of a url, as a table."
     }
 
     fn run(
@@ -26,6 +26,21 @@ impl WholeStreamCommand for FromURL {
     }
 }
 
+/// The query string part of the input, so a whole url (or one starting at `?`) reads the same
+/// as `a=1&b=2` on its own. Anything after a `#` isn't part of the query.
+fn query_of(text: &str) -> &str {
+    let text = text.trim();
+    let query = match text.find('?') {
+        Some(start) => &text[start + 1..],
+        None => text,
+    };
+
+    match query.find('#') {
+        Some(end) => &query[..end],
+        None => query,
+    }
+}
+
 fn from_url(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
     let args = args.evaluate_once(registry)?;
     let tag = args.name_tag();
@@ -55,7 +70,7 @@ fn from_url(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStrea
             }
         }
 
-        let result = serde_urlencoded::from_str::<Vec<(String, String)>>(&concat_string);
+        let result = serde_urlencoded::from_str::<Vec<(String, String)>>(query_of(&concat_string));
 
         match result {
             Ok(result) => {
@@ -83,3 +98,18 @@ fn from_url(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStrea
 
     Ok(stream.to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::query_of;
+
+    #[test]
+    fn reads_the_query_of_a_whole_url() {
+        assert_eq!(query_of("a=1&b=2"), "a=1&b=2");
+        assert_eq!(query_of("?a=1&b=2"), "a=1&b=2");
+        assert_eq!(
+            query_of("https://www.nushell.sh/search?q=shell&page=2#results"),
+            "q=shell&page=2"
+        );
+    }
+}
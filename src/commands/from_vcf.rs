@@ -0,0 +1,95 @@
+use crate::commands::from_ics::{component_to_row, parse_components, unescape};
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+
+pub struct FromVCF;
+
+impl WholeStreamCommand for FromVCF {
+    fn name(&self) -> &str {
+        "from-vcf"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-vcf")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse text as .vcf (vCard) and create a table of its contacts."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        from_vcf(args, registry)
+    }
+}
+
+fn vcf_value(value: &str) -> Value {
+    Value::string(unescape(value))
+}
+
+pub fn from_vcf_string_to_value(
+    s: &str,
+    tag: impl Into<Tag>,
+) -> Result<Vec<Tagged<Value>>, String> {
+    let tag = tag.into();
+
+    Ok(parse_components(s)?
+        .iter()
+        .filter(|component| component.name == "VCARD")
+        .map(|card| component_to_row(card, tag, &vcf_value))
+        .collect())
+}
+
+fn from_vcf(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let mut concat_string = String::new();
+        let mut latest_tag: Option<Tag> = None;
+
+        for value in values {
+            let value_tag = value.tag();
+            latest_tag = Some(value_tag);
+            match value.item {
+                Value::Primitive(Primitive::String(s)) => {
+                    concat_string.push_str(&s);
+                    concat_string.push_str("\n");
+                }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    tag,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+
+        match from_vcf_string_to_value(&concat_string, tag) {
+            Ok(contacts) => {
+                for contact in contacts {
+                    yield ReturnSuccess::value(contact);
+                }
+            }
+            Err(reason) => if let Some(last_tag) = latest_tag {
+                yield Err(ShellError::labeled_error_with_secondary(
+                    "Could not parse as vCard",
+                    reason,
+                    tag,
+                    "value originates from here",
+                    last_tag,
+                ))
+            },
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,159 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::errors::ShellError;
+use crate::prelude::*;
+use calamine::{DataType, Range, Reader, Xlsx};
+use std::io::Cursor;
+
+pub struct FromXLSX;
+
+#[derive(Deserialize)]
+pub struct FromXLSXArgs {
+    sheet: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for FromXLSX {
+    fn name(&self) -> &str {
+        "from-xlsx"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("from-xlsx").named("sheet", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Parse binary Excel (.xlsx) data and create a table for each sheet, or the rows of a single --sheet."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, from_xlsx)?.run()
+    }
+}
+
+fn convert_cell_to_nu_value(cell: &DataType, tag: Tag) -> Tagged<Value> {
+    match cell {
+        DataType::Empty => Value::nothing().tagged(tag),
+        DataType::String(s) => Value::string(s).tagged(tag),
+        // Spreadsheets keep every number as a float, so whole ones are read back as integers
+        DataType::Float(f) if f.fract() == 0.0 && f.abs() < i64::max_value() as f64 => {
+            Value::number(*f as i64).tagged(tag)
+        }
+        DataType::Float(f) => Value::number(*f).tagged(tag),
+        DataType::Int(i) => Value::number(*i).tagged(tag),
+        DataType::Bool(b) => Value::boolean(*b).tagged(tag),
+        DataType::Error(e) => Value::string(format!("{:?}", e)).tagged(tag),
+    }
+}
+
+fn convert_sheet_to_nu_value(range: &Range<DataType>, tag: Tag) -> Vec<Tagged<Value>> {
+    range
+        .rows()
+        .map(|row| {
+            let mut dict = TaggedDictBuilder::new(tag);
+
+            for (idx, cell) in row.iter().enumerate() {
+                dict.insert_tagged(
+                    format!("Column{}", idx),
+                    convert_cell_to_nu_value(cell, tag),
+                );
+            }
+
+            dict.into_tagged_value()
+        })
+        .collect()
+}
+
+/// A table for each sheet of a workbook, or just the rows of `sheet` when one is picked.
+pub(crate) fn workbook_to_value<R: Reader>(
+    workbook: &mut R,
+    sheet: Option<&Tagged<String>>,
+    tag: Tag,
+) -> Result<Tagged<Value>, ShellError> {
+    let sheet_range = |workbook: &mut R, name: &str| match workbook.worksheet_range(name) {
+        Some(Ok(range)) => Ok(range),
+        _ => Err(ShellError::labeled_error(
+            "Could not read sheet",
+            format!("sheet '{}' cannot be read", name),
+            tag,
+        )),
+    };
+
+    match sheet {
+        Some(sheet) => {
+            if !workbook.sheet_names().contains(&sheet.item) {
+                return Err(ShellError::labeled_error(
+                    "Unknown sheet",
+                    format!("expected one of {}", workbook.sheet_names().join(", ")),
+                    sheet.tag(),
+                ));
+            }
+
+            let range = sheet_range(workbook, &sheet.item)?;
+            Ok(Value::Table(convert_sheet_to_nu_value(&range, tag)).tagged(tag))
+        }
+        None => {
+            let mut dict = TaggedDictBuilder::new(tag);
+
+            for name in workbook.sheet_names().to_owned() {
+                let range = sheet_range(workbook, &name)?;
+                dict.insert(name, Value::Table(convert_sheet_to_nu_value(&range, tag)));
+            }
+
+            Ok(dict.into_tagged_value())
+        }
+    }
+}
+
+pub fn from_xlsx_bytes_to_value(
+    bytes: Vec<u8>,
+    sheet: Option<&Tagged<String>>,
+    tag: Tag,
+) -> Result<Tagged<Value>, ShellError> {
+    let mut workbook: Xlsx<_> = Xlsx::new(Cursor::new(bytes)).map_err(|_| {
+        ShellError::labeled_error(
+            "Could not parse as XLSX",
+            "input cannot be parsed as XLSX",
+            tag,
+        )
+    })?;
+
+    workbook_to_value(&mut workbook, sheet, tag)
+}
+
+pub fn from_xlsx(
+    FromXLSXArgs { sheet }: FromXLSXArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        for value in values {
+            let value_tag = value.tag();
+            match value.item {
+                Value::Primitive(Primitive::Binary(vb)) =>
+                    match from_xlsx_bytes_to_value(vb, sheet.as_ref(), name) {
+                        Ok(Tagged { item: Value::Table(rows), .. }) => {
+                            for row in rows {
+                                yield ReturnSuccess::value(row);
+                            }
+                        }
+                        Ok(x) => yield ReturnSuccess::value(x),
+                        Err(err) => yield Err(err),
+                    }
+                _ => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected binary data from pipeline",
+                    "requires binary input",
+                    name,
+                    "value originates from here",
+                    value_tag,
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
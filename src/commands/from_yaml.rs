@@ -1,6 +1,8 @@
 use crate::commands::WholeStreamCommand;
 use crate::data::{Primitive, TaggedDictBuilder, Value};
 use crate::prelude::*;
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::Marker;
 
 pub struct FromYAML;
 
@@ -50,49 +52,119 @@ impl WholeStreamCommand for FromYML {
     }
 }
 
-fn convert_yaml_value_to_nu_value(v: &serde_yaml::Value, tag: impl Into<Tag>) -> Tagged<Value> {
+fn convert_yaml_value_to_nu_value(
+    v: &serde_yaml::Value,
+    tag: impl Into<Tag>,
+) -> Result<Tagged<Value>, ShellError> {
     let tag = tag.into();
 
-    match v {
+    Ok(match v {
         serde_yaml::Value::Bool(b) => Value::boolean(*b).tagged(tag),
         serde_yaml::Value::Number(n) if n.is_i64() => {
             Value::number(n.as_i64().unwrap()).tagged(tag)
         }
-        serde_yaml::Value::Number(n) if n.is_f64() => {
-            Value::Primitive(Primitive::from(n.as_f64().unwrap())).tagged(tag)
+        serde_yaml::Value::Number(n) if n.is_u64() => {
+            Value::number(n.as_u64().unwrap()).tagged(tag)
         }
+        serde_yaml::Value::Number(n) => match n.as_f64().and_then(BigDecimal::from_f64) {
+            Some(decimal) => Value::decimal(decimal).tagged(tag),
+            None => {
+                return Err(ShellError::labeled_error(
+                    "Could not convert YAML number",
+                    format!("{} is not a finite number", n),
+                    tag,
+                ))
+            }
+        },
         serde_yaml::Value::String(s) => Value::string(s).tagged(tag),
         serde_yaml::Value::Sequence(a) => Value::Table(
             a.iter()
                 .map(|x| convert_yaml_value_to_nu_value(x, tag))
-                .collect(),
+                .collect::<Result<_, _>>()?,
         )
         .tagged(tag),
         serde_yaml::Value::Mapping(t) => {
             let mut collected = TaggedDictBuilder::new(tag);
 
             for (k, v) in t.iter() {
-                match k {
-                    serde_yaml::Value::String(k) => {
-                        collected.insert_tagged(k.clone(), convert_yaml_value_to_nu_value(v, tag));
+                // Scalar keys, such as `1: a`, name their column with their text
+                let key = match k {
+                    serde_yaml::Value::String(k) => k.clone(),
+                    serde_yaml::Value::Number(k) => k.to_string(),
+                    serde_yaml::Value::Bool(k) => k.to_string(),
+                    serde_yaml::Value::Null => "null".to_string(),
+                    _ => {
+                        return Err(ShellError::labeled_error(
+                            "Could not convert YAML mapping",
+                            "only scalar keys can name columns",
+                            tag,
+                        ))
                     }
-                    _ => unimplemented!("Unknown key type"),
-                }
+                };
+
+                collected.insert_tagged(key, convert_yaml_value_to_nu_value(v, tag)?);
             }
 
             collected.into_tagged_value()
         }
         serde_yaml::Value::Null => Value::Primitive(Primitive::Nothing).tagged(tag),
-        x => unimplemented!("Unsupported yaml case: {:?}", x),
-    }
+    })
 }
 
 pub fn from_yaml_string_to_value(
     s: String,
     tag: impl Into<Tag>,
-) -> serde_yaml::Result<Tagged<Value>> {
-    let v: serde_yaml::Value = serde_yaml::from_str(&s)?;
-    Ok(convert_yaml_value_to_nu_value(&v, tag))
+) -> Result<Tagged<Value>, ShellError> {
+    let v: serde_yaml::Value =
+        serde_yaml::from_str(&s).map_err(|e| ShellError::string(e.to_string()))?;
+    convert_yaml_value_to_nu_value(&v, tag)
+}
+
+/// Collects the character offset where each document of a YAML stream starts.
+struct DocumentStarts(Vec<usize>);
+
+impl MarkedEventReceiver for DocumentStarts {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        if let Event::DocumentStart = event {
+            self.0.push(mark.index());
+        }
+    }
+}
+
+/// Parse each document of a YAML stream on its own. The YAML parser finds where the documents
+/// start, so `---` inside a block scalar doesn't split one and `...` ends one.
+pub fn from_yaml_string_to_documents(
+    s: String,
+    tag: impl Into<Tag>,
+) -> Result<Vec<Tagged<Value>>, ShellError> {
+    let tag = tag.into();
+    let mut starts = DocumentStarts(vec![]);
+
+    Parser::new(s.chars())
+        .load(&mut starts, true)
+        .map_err(|e| ShellError::string(e.to_string()))?;
+
+    // The first document also takes anything before it, such as comments or directives
+    if let Some(first) = starts.0.first_mut() {
+        *first = 0;
+    }
+
+    let offsets: Vec<usize> = s.char_indices().map(|(offset, _)| offset).collect();
+    let byte_offset = |index: usize| offsets.get(index).cloned().unwrap_or_else(|| s.len());
+
+    let mut documents = vec![];
+
+    for (i, start) in starts.0.iter().enumerate() {
+        let end = match starts.0.get(i + 1) {
+            Some(next) => byte_offset(*next),
+            None => s.len(),
+        };
+
+        let document = &s[byte_offset(*start)..end];
+        documents.push(from_yaml_string_to_value(document.to_string(), tag)?);
+    }
+
+    Ok(documents)
 }
 
 fn from_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
@@ -125,14 +197,20 @@ fn from_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStre
             }
         }
 
-        match from_yaml_string_to_value(concat_string, tag) {
-            Ok(x) => match x {
-                Tagged { item: Value::Table(list), .. } => {
-                    for l in list {
-                        yield ReturnSuccess::value(l);
+        match from_yaml_string_to_documents(concat_string, tag) {
+            Ok(mut documents) => if documents.len() == 1 {
+                match documents.remove(0) {
+                    Tagged { item: Value::Table(list), .. } => {
+                        for l in list {
+                            yield ReturnSuccess::value(l);
+                        }
                     }
+                    x => yield ReturnSuccess::value(x),
+                }
+            } else {
+                for document in documents {
+                    yield ReturnSuccess::value(document);
                 }
-                x => yield ReturnSuccess::value(x),
             },
             Err(_) => if let Some(last_tag) = latest_tag {
                 yield Err(ShellError::labeled_error_with_secondary(
@@ -36,6 +36,11 @@ impl WholeStreamCommand for Get {
 }
 
 fn get_member(path: &Tagged<String>, obj: &Tagged<Value>) -> Result<Tagged<Value>, ShellError> {
+    let segments: Vec<&str> = path.split(".").collect();
+    if segments.contains(&"*") {
+        return obj.get_data_by_column_path(path.tag(), &segments);
+    }
+
     let mut current = Some(obj);
     for p in path.split(".") {
         if let Some(obj) = current {
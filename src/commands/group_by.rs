@@ -0,0 +1,102 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use indexmap::IndexMap;
+
+pub struct GroupBy;
+
+#[derive(Deserialize)]
+pub struct GroupByArgs {
+    column_name: Tagged<String>,
+    #[serde(rename(deserialize = "to-table"))]
+    to_table: bool,
+}
+
+impl WholeStreamCommand for GroupBy {
+    fn name(&self) -> &str {
+        "group-by"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("group-by")
+            .required("column_name", SyntaxShape::String)
+            .switch("to-table")
+    }
+
+    fn usage(&self) -> &str {
+        "Creates a new table with the data from the table rows grouped by the column given."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, group_by)?.run()
+    }
+}
+
+fn group_key(column_name: &Tagged<String>, row: &Tagged<Value>) -> Result<String, ShellError> {
+    match row.get_data_by_key(&column_name.item) {
+        Some(value) => value.as_string(),
+        None => Err(ShellError::labeled_error_with_secondary(
+            "Unknown column",
+            "column not found",
+            column_name.tag(),
+            "in this row",
+            row.tag(),
+        )),
+    }
+}
+
+pub fn group_by(
+    GroupByArgs {
+        column_name,
+        to_table,
+    }: GroupByArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        if to_table {
+            for row in values {
+                match group_key(&column_name, &row) {
+                    Ok(group) => {
+                        let mut out = TaggedDictBuilder::new(row.tag());
+                        out.insert("group", Value::string(group));
+
+                        if let Value::Row(dict) = row.item {
+                            for (key, value) in dict.entries {
+                                out.insert_tagged(key, value);
+                            }
+                        }
+
+                        yield ReturnSuccess::value(out.into_tagged_value());
+                    }
+                    Err(err) => yield Err(err),
+                }
+            }
+        } else {
+            let mut groups: IndexMap<String, Vec<Tagged<Value>>> = IndexMap::new();
+
+            for row in values {
+                match group_key(&column_name, &row) {
+                    Ok(group) => groups.entry(group).or_insert_with(|| vec![]).push(row),
+                    Err(err) => yield Err(err),
+                }
+            }
+
+            let mut out = TaggedDictBuilder::new(name);
+
+            for (group, rows) in groups {
+                out.insert_tagged(group, Value::Table(rows).tagged(name));
+            }
+
+            yield ReturnSuccess::value(out.into_tagged_value());
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
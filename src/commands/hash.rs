@@ -0,0 +1,177 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use sha2::Digest;
+use std::path::PathBuf;
+
+pub struct Hash;
+
+#[derive(Deserialize)]
+pub struct HashArgs {
+    column: Option<Tagged<String>>,
+    md5: bool,
+    sha1: bool,
+    sha256: bool,
+}
+
+impl WholeStreamCommand for Hash {
+    fn name(&self) -> &str {
+        "hash"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("hash")
+            .optional("column", SyntaxShape::String)
+            .switch("md5")
+            .switch("sha1")
+            .switch("sha256")
+    }
+
+    fn usage(&self) -> &str {
+        "Compute the digest of strings or binary data as hex, using sha256 unless --md5 or --sha1 is given (eg. open file.iso --raw | hash --sha256). Given a column of file paths, adds a column with the digest of each file."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, hash)?.run()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Algorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl Algorithm {
+    fn as_str(&self) -> &str {
+        match self {
+            Algorithm::Md5 => "md5",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha256 => "sha256",
+        }
+    }
+
+    fn digest(&self, bytes: &[u8]) -> String {
+        match self {
+            Algorithm::Md5 => hex::encode(md5::Md5::digest(bytes)),
+            Algorithm::Sha1 => hex::encode(sha1::Sha1::digest(bytes)),
+            Algorithm::Sha256 => hex::encode(sha2::Sha256::digest(bytes)),
+        }
+    }
+}
+
+fn bytes_of(value: &Tagged<Value>) -> Result<Vec<u8>, ShellError> {
+    match value.item() {
+        Value::Primitive(Primitive::String(s)) => Ok(s.as_bytes().to_vec()),
+        Value::Primitive(Primitive::Binary(b)) => Ok(b.clone()),
+        other => Err(ShellError::type_error(
+            "string or binary data",
+            value.copy_tag(other.type_name()),
+        )),
+    }
+}
+
+/// Read the file a cell names, relative to the current directory unless its path is absolute.
+fn file_contents(cwd: &PathBuf, value: &Tagged<Value>) -> Result<Vec<u8>, ShellError> {
+    let path = match value.item() {
+        Value::Primitive(Primitive::Path(path)) => path.clone(),
+        Value::Primitive(Primitive::String(path)) => PathBuf::from(path),
+        other => {
+            return Err(ShellError::type_error(
+                "file path",
+                value.copy_tag(other.type_name()),
+            ))
+        }
+    };
+
+    std::fs::read(cwd.join(&path)).map_err(|_| {
+        ShellError::labeled_error("File could not be read", "could not read file", value.tag())
+    })
+}
+
+pub fn hash(
+    HashArgs {
+        column,
+        md5,
+        sha1,
+        sha256,
+    }: HashArgs,
+    RunnableContext {
+        input,
+        shell_manager,
+        name,
+        ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let algorithm = match (md5, sha1, sha256) {
+        (false, false, _) => Algorithm::Sha256,
+        (true, false, false) => Algorithm::Md5,
+        (false, true, false) => Algorithm::Sha1,
+        _ => {
+            return Err(ShellError::labeled_error(
+                "Can only use one hash",
+                "use only one of --md5, --sha1 or --sha256",
+                name,
+            ))
+        }
+    };
+
+    let cwd = PathBuf::from(shell_manager.path());
+
+    let stream = input.values.map(move |value| match &column {
+        None => {
+            let digest = algorithm.digest(&bytes_of(&value)?);
+            ReturnSuccess::value(Value::string(digest).tagged(value.tag()))
+        }
+        Some(column) => {
+            let path = match value.get_data_by_key(&column.item) {
+                Some(path) => path,
+                None => {
+                    return Err(ShellError::labeled_error_with_secondary(
+                        "Unknown column",
+                        "column not found",
+                        column.tag(),
+                        "in this row",
+                        value.tag(),
+                    ))
+                }
+            };
+
+            let digest = algorithm.digest(&file_contents(&cwd, path)?);
+
+            match value.insert_data_at_path(value.tag(), algorithm.as_str(), Value::string(digest))
+            {
+                Some(row) => ReturnSuccess::value(row),
+                None => Err(ShellError::labeled_error(
+                    "Could not add the digest",
+                    "rows are needed to hash a column",
+                    column.tag(),
+                )),
+            }
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Algorithm;
+
+    #[test]
+    fn digests_are_written_as_hex() {
+        assert_eq!(
+            Algorithm::Md5.digest(b"nushell"),
+            "93769d01cf78fc2f42cd9107d20457ac"
+        );
+        assert_eq!(
+            Algorithm::Sha256.digest(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
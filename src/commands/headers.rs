@@ -0,0 +1,88 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Headers;
+
+#[derive(Deserialize)]
+pub struct HeadersArgs {}
+
+impl WholeStreamCommand for Headers {
+    fn name(&self) -> &str {
+        "headers"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("headers")
+    }
+
+    fn usage(&self) -> &str {
+        "Use the values of the first row as the column names of the rest of the table."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, headers)?.run()
+    }
+}
+
+/// The names the first row gives each of its columns, falling back to the column's
+/// own name where the cell is empty.
+fn header_names(first: &Tagged<Value>) -> Vec<(String, String)> {
+    first
+        .data_descriptors()
+        .into_iter()
+        .map(|column| {
+            let header = match first.get_data_by_key(&column) {
+                Some(cell) if !cell.is_empty() => cell.format_leaf(None),
+                _ => column.clone(),
+            };
+            (column, header)
+        })
+        .collect()
+}
+
+pub fn headers(
+    _: HeadersArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+
+        let headers = match values.next().await {
+            Some(first @ Tagged { item: Value::Row(_), .. }) => header_names(&first),
+            Some(other) => {
+                yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a table",
+                    "requires a table with a header row",
+                    name,
+                    "value originates from here",
+                    other.tag(),
+                ));
+                vec![]
+            }
+            None => vec![],
+        };
+
+        if !headers.is_empty() {
+            while let Some(row) = values.next().await {
+                let mut dict = TaggedDictBuilder::new(row.tag());
+
+                for (column, header) in headers.iter() {
+                    match row.get_data_by_key(column) {
+                        Some(cell) => dict.insert_tagged(header.clone(), cell.clone()),
+                        None => dict.insert(header.clone(), Value::nothing()),
+                    }
+                }
+
+                yield ReturnSuccess::value(dict.into_tagged_value());
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
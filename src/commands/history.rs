@@ -0,0 +1,39 @@
+use crate::cli::History as HistoryFile;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct History;
+
+impl WholeStreamCommand for History {
+    fn name(&self) -> &str {
+        "history"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("history")
+    }
+
+    fn usage(&self) -> &str {
+        "Display the command history, oldest first."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        history(args, registry)
+    }
+}
+
+fn history(args: CommandArgs, _registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let tag = args.call_info.name_tag;
+
+    let entries: VecDeque<Tagged<Value>> = HistoryFile::entries()?
+        .into_iter()
+        .map(|line| Value::string(line).tagged(tag))
+        .collect();
+
+    Ok(entries.into())
+}
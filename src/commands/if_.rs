@@ -0,0 +1,73 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct If;
+
+#[derive(Deserialize)]
+pub struct IfArgs {
+    condition: value::Block,
+    then_case: value::Block,
+    else_case: value::Block,
+}
+
+impl WholeStreamCommand for If {
+    fn name(&self) -> &str {
+        "if"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("if")
+            .required("condition", SyntaxShape::Block)
+            .required("then_case", SyntaxShape::Block)
+            .required("else_case", SyntaxShape::Block)
+    }
+
+    fn usage(&self) -> &str {
+        "Run the first block for each row matching the condition and the second block for the rest (eg. if $it.size > 1kb { echo big } { echo small })."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, if_)?.run()
+    }
+}
+
+pub fn if_(
+    IfArgs {
+        condition,
+        then_case,
+        else_case,
+    }: IfArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+
+        while let Some(row) = values.next().await {
+            let result = match condition.invoke(&row, &commands) {
+                Ok(v) if v.is_true() => then_case.invoke(&row, &commands),
+                Ok(_) => else_case.invoke(&row, &commands),
+                Err(e) => Err(e),
+            };
+
+            // Like `each`, a block producing a list contributes each of its items
+            match result {
+                Ok(Tagged { item: Value::Table(rows), .. }) => {
+                    for row in rows {
+                        yield ReturnSuccess::value(row);
+                    }
+                }
+                Ok(value) => yield ReturnSuccess::value(value),
+                Err(e) => yield Err(e),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,76 @@
+use crate::commands::update::value_for_row;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Insert;
+
+#[derive(Deserialize)]
+pub struct InsertArgs {
+    column: Tagged<String>,
+    value: Tagged<Value>,
+    force: bool,
+}
+
+impl WholeStreamCommand for Insert {
+    fn name(&self) -> &str {
+        "insert"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("insert")
+            .required("column", SyntaxShape::String)
+            .required("value", SyntaxShape::Any)
+            .switch("force")
+    }
+
+    fn usage(&self) -> &str {
+        "Insert a new column. The value may be a block computed from the row (eg. insert total { $it.price })."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, insert)?.run()
+    }
+}
+
+pub fn insert(
+    InsertArgs {
+        column,
+        value,
+        force,
+    }: InsertArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = input.values.map(move |row| {
+        if !force && row.get_data_by_path(row.tag(), &column.item).is_some() {
+            return Err(ShellError::labeled_error_with_secondary(
+                "Column already exists",
+                "use --force to overwrite it",
+                column.tag(),
+                "in this row",
+                row.tag(),
+            ));
+        }
+
+        let inserted = value_for_row(&value, &row, &commands)?;
+
+        match row.insert_data_at_path(row.tag(), &column.item, inserted) {
+            Some(row) => ReturnSuccess::value(row),
+            None => Err(ShellError::labeled_error_with_secondary(
+                "Could not insert column",
+                "no place to insert this column",
+                column.tag(),
+                "in this row",
+                row.tag(),
+            )),
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
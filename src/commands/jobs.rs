@@ -0,0 +1,47 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Jobs;
+
+impl WholeStreamCommand for Jobs {
+    fn name(&self) -> &str {
+        "jobs"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("jobs")
+    }
+
+    fn usage(&self) -> &str {
+        "List the jobs running in the background."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        jobs(args, registry)
+    }
+}
+
+fn jobs(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let tag = args.call_info.name_tag;
+
+    let jobs: VecDeque<Tagged<Value>> = registry
+        .jobs()
+        .each(|id, job| {
+            let mut dict = TaggedDictBuilder::new(tag);
+            dict.insert("id", Value::int(id as i64));
+            dict.insert("command", Value::string(job.command.clone()));
+            dict.insert("status", Value::string(job.status()));
+            dict.insert("pid", Value::int(job.pid() as i64));
+            dict.into_tagged_value()
+        })
+        .into_iter()
+        .collect();
+
+    Ok(jobs.into())
+}
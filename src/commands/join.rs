@@ -0,0 +1,264 @@
+use crate::commands::append::block_rows;
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Join;
+
+#[derive(Deserialize)]
+pub struct JoinArgs {
+    block: value::Block,
+    key: Tagged<String>,
+    left: bool,
+    right: bool,
+    outer: bool,
+}
+
+impl WholeStreamCommand for Join {
+    fn name(&self) -> &str {
+        "join"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("join")
+            .required("block", SyntaxShape::Block)
+            .required("key", SyntaxShape::String)
+            .switch("left")
+            .switch("right")
+            .switch("outer")
+    }
+
+    fn usage(&self) -> &str {
+        "Join the rows of the table a block outputs to the input rows with the same key (eg. join { (open b.csv) } id). Unmatched rows are kept with --left, --right or --outer."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, join)?.run()
+    }
+}
+
+struct Joiner {
+    key: String,
+    left_columns: Vec<String>,
+    right_columns: Vec<String>,
+    tag: Tag,
+}
+
+fn columns_of(rows: &[Tagged<Value>]) -> Vec<String> {
+    let mut columns = vec![];
+    for row in rows {
+        for column in row.data_descriptors() {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+    }
+    columns
+}
+
+impl Joiner {
+    fn key_of<'a>(&self, row: &'a Tagged<Value>) -> Option<&'a Tagged<Value>> {
+        row.get_data_by_key(&self.key)
+    }
+
+    /// A row with every column of both tables. Columns missing from either side are nothing,
+    /// and a column both tables have, other than the key, is kept for each as `column` and
+    /// `column_right`.
+    fn combine(
+        &self,
+        left: Option<&Tagged<Value>>,
+        right: Option<&Tagged<Value>>,
+    ) -> Tagged<Value> {
+        let mut dict = TaggedDictBuilder::new(self.tag);
+        let cell = |row: Option<&Tagged<Value>>, column: &str| {
+            row.and_then(|row| row.get_data_by_key(column)).cloned()
+        };
+
+        for column in self.left_columns.iter() {
+            let value = if *column == self.key {
+                cell(left, column).or_else(|| cell(right, column))
+            } else {
+                cell(left, column)
+            };
+            dict.insert_tagged(
+                column.clone(),
+                value.unwrap_or_else(|| Value::nothing().tagged(self.tag)),
+            );
+        }
+
+        for column in self.right_columns.iter() {
+            if *column == self.key && self.left_columns.contains(column) {
+                continue;
+            }
+
+            let name = if self.left_columns.contains(column) {
+                format!("{}_right", column)
+            } else {
+                column.clone()
+            };
+            dict.insert_tagged(
+                name,
+                cell(right, column).unwrap_or_else(|| Value::nothing().tagged(self.tag)),
+            );
+        }
+
+        dict.into_tagged_value()
+    }
+
+    fn join(
+        &self,
+        left_rows: &[Tagged<Value>],
+        right_rows: &[Tagged<Value>],
+        keep_left: bool,
+        keep_right: bool,
+    ) -> Vec<Tagged<Value>> {
+        let mut joined = vec![];
+        let mut right_matched = vec![false; right_rows.len()];
+
+        for left in left_rows {
+            let mut matched = false;
+
+            if let Some(key) = self.key_of(left) {
+                for (idx, right) in right_rows.iter().enumerate() {
+                    if self.key_of(right).map(|other| other.item == key.item) == Some(true) {
+                        joined.push(self.combine(Some(left), Some(right)));
+                        right_matched[idx] = true;
+                        matched = true;
+                    }
+                }
+            }
+
+            if !matched && keep_left {
+                joined.push(self.combine(Some(left), None));
+            }
+        }
+
+        if keep_right {
+            for (right, matched) in right_rows.iter().zip(right_matched) {
+                if !matched {
+                    joined.push(self.combine(None, Some(right)));
+                }
+            }
+        }
+
+        joined
+    }
+}
+
+pub fn join(
+    JoinArgs {
+        block,
+        key,
+        left,
+        right,
+        outer,
+    }: JoinArgs,
+    RunnableContext {
+        input,
+        commands,
+        name,
+        ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if [left, right, outer].iter().filter(|mode| **mode).count() > 1 {
+        return Err(ShellError::labeled_error(
+            "Can only join one way",
+            "use only one of --left, --right or --outer",
+            name,
+        ));
+    }
+
+    let stream = async_stream_block! {
+        let left_rows: Vec<Tagged<Value>> = input.values.collect().await;
+
+        match block_rows(&block, &commands) {
+            Ok(right_rows) => {
+                let joiner = Joiner {
+                    key: key.item.clone(),
+                    left_columns: columns_of(&left_rows),
+                    right_columns: columns_of(&right_rows),
+                    tag: name,
+                };
+
+                let joined = joiner.join(&left_rows, &right_rows, left || outer, right || outer);
+
+                for row in joined {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+            Err(err) => yield Err(err),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{columns_of, Joiner};
+    use crate::data::TaggedDictBuilder;
+    use crate::prelude::*;
+
+    fn row(entries: Vec<(&str, Value)>) -> Tagged<Value> {
+        let mut dict = TaggedDictBuilder::new(Tag::unknown());
+        for (key, value) in entries {
+            dict.insert(key, value);
+        }
+        dict.into_tagged_value()
+    }
+
+    fn joined(keep_left: bool, keep_right: bool) -> Vec<String> {
+        let left = vec![
+            row(vec![("id", Value::int(1)), ("name", Value::string("nu"))]),
+            row(vec![("id", Value::int(2)), ("name", Value::string("git"))]),
+        ];
+        let right = vec![
+            row(vec![
+                ("id", Value::int(1)),
+                ("name", Value::string("shell")),
+            ]),
+            row(vec![
+                ("id", Value::int(3)),
+                ("name", Value::string("editor")),
+            ]),
+        ];
+
+        let joiner = Joiner {
+            key: "id".to_string(),
+            left_columns: columns_of(&left),
+            right_columns: columns_of(&right),
+            tag: Tag::unknown(),
+        };
+
+        joiner
+            .join(&left, &right, keep_left, keep_right)
+            .iter()
+            .map(|row| {
+                row.data_descriptors()
+                    .iter()
+                    .map(|column| row.get_data_by_key(column).unwrap().format_leaf(None))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inner_join_keeps_only_matching_rows() {
+        assert_eq!(joined(false, false), vec!["1,nu,shell"]);
+    }
+
+    #[test]
+    fn outer_join_keeps_unmatched_rows_from_both_sides() {
+        assert_eq!(joined(false, true), vec!["1,nu,shell", "3,,editor"]);
+        assert_eq!(
+            joined(true, true),
+            vec!["1,nu,shell", "2,git,", "3,,editor"]
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct KillJob;
+
+#[derive(Deserialize)]
+pub struct KillJobArgs {
+    id: Tagged<i64>,
+}
+
+impl WholeStreamCommand for KillJob {
+    fn name(&self) -> &str {
+        "kill-job"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("kill-job").required("id", SyntaxShape::Int)
+    }
+
+    fn usage(&self) -> &str {
+        "Stop a background job. Its output so far can still be retrieved with fg."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, kill_job)?.run()
+    }
+}
+
+pub fn kill_job(
+    KillJobArgs { id }: KillJobArgs,
+    RunnableContext { commands, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    match commands.jobs().kill(id.item as usize) {
+        Some(Ok(())) => Ok(OutputStream::empty()),
+        Some(Err(err)) => Err(err),
+        None => Err(ShellError::labeled_error(
+            "Unknown job",
+            "no job with this id",
+            id.tag(),
+        )),
+    }
+}
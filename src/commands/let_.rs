@@ -0,0 +1,74 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Let;
+
+#[derive(Deserialize)]
+pub struct LetArgs {
+    name: Tagged<String>,
+    equals: Tagged<String>,
+    value: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Let {
+    fn name(&self) -> &str {
+        "let"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("let")
+            .required("name", SyntaxShape::String)
+            .required("equals", SyntaxShape::String)
+            .required("value", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Bind a value to a variable (eg. let limit = 10), available as $limit in later pipeline stages and lines."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, let_)?.run()
+    }
+}
+
+pub fn let_(
+    LetArgs {
+        name,
+        equals,
+        value,
+    }: LetArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if equals.item != "=" {
+        return Err(ShellError::labeled_error(
+            "Expected = after the variable name",
+            "expected =",
+            equals.tag(),
+        ));
+    }
+
+    let valid_name = name
+        .item
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '-');
+
+    if name.item.is_empty() || !valid_name || name.item == "it" || name.item == "nu" {
+        return Err(ShellError::labeled_error(
+            "Invalid variable name",
+            "expected a name like limit or file_count",
+            name.tag(),
+        ));
+    }
+
+    commands.set_variable(name.item, value);
+
+    // Bindings pass their input through, so a `let` may sit anywhere in a pipeline
+    Ok(input.values.map(ReturnSuccess::value).to_output_stream())
+}
@@ -7,7 +7,10 @@ pub struct LS;
 
 #[derive(Deserialize)]
 pub struct LsArgs {
-    path: Option<Tagged<PathBuf>>,
+    pub path: Option<Tagged<PathBuf>>,
+    #[serde(rename(deserialize = "follow-symlinks"))]
+    pub follow_symlinks: bool,
+    pub full: bool,
 }
 
 impl WholeStreamCommand for LS {
@@ -16,11 +19,14 @@ impl WholeStreamCommand for LS {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("ls").optional("path", SyntaxShape::Pattern)
+        Signature::build("ls")
+            .optional("path", SyntaxShape::Pattern)
+            .switch("follow-symlinks")
+            .switch("full")
     }
 
     fn usage(&self) -> &str {
-        "View the contents of the current or given path."
+        "View the contents of the current or given path. Patterns like **/*.rs match recursively."
     }
 
     fn run(
@@ -33,6 +39,6 @@ impl WholeStreamCommand for LS {
     }
 }
 
-fn ls(LsArgs { path }: LsArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
-    context.shell_manager.ls(path, context.name)
+fn ls(args: LsArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
+    context.shell_manager.ls(args, context.name)
 }
@@ -0,0 +1,92 @@
+use crate::commands::append::block_rows;
+use crate::commands::WholeStreamCommand;
+use crate::data::Dictionary;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Merge;
+
+#[derive(Deserialize)]
+pub struct MergeArgs {
+    block: value::Block,
+}
+
+impl WholeStreamCommand for Merge {
+    fn name(&self) -> &str {
+        "merge"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("merge").required("block", SyntaxShape::Block)
+    }
+
+    fn usage(&self) -> &str {
+        "Merge the columns of the table a block outputs into the input rows, row by row (eg. merge { (open sizes.csv) })."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, merge)?.run()
+    }
+}
+
+/// Add the columns of `other` to `row`, with `other` winning where both have a column.
+fn merge_rows(row: Tagged<Value>, other: &Tagged<Value>) -> Result<Tagged<Value>, ShellError> {
+    let mut dict = match row.item {
+        Value::Row(dict) => dict,
+        _ => {
+            return Err(ShellError::labeled_error(
+                "Can only merge into rows",
+                "this value isn't a row",
+                row.tag,
+            ))
+        }
+    };
+
+    match &other.item {
+        Value::Row(other_dict) => {
+            for (column, value) in other_dict.entries.iter() {
+                dict.entries.insert(column.clone(), value.clone());
+            }
+            Ok(Value::Row(Dictionary::from(dict.entries)).tagged(row.tag))
+        }
+        _ => Err(ShellError::labeled_error(
+            "Can only merge rows",
+            "the block output a value that isn't a row",
+            other.tag(),
+        )),
+    }
+}
+
+pub fn merge(
+    MergeArgs { block }: MergeArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        match block_rows(&block, &commands) {
+            Ok(others) => {
+                let mut values = input.values;
+                let mut others = others.into_iter();
+
+                // Rows past the end of the block's table are passed through as they are
+                while let Some(row) = values.next().await {
+                    match others.next() {
+                        Some(other) => match merge_rows(row, &other) {
+                            Ok(merged) => yield ReturnSuccess::value(merged),
+                            Err(err) => yield Err(err),
+                        },
+                        None => yield ReturnSuccess::value(row),
+                    }
+                }
+            }
+            Err(err) => yield Err(err),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -11,6 +11,8 @@ pub struct Move;
 pub struct MoveArgs {
     pub src: Tagged<PathBuf>,
     pub dst: Tagged<PathBuf>,
+    #[serde(rename(deserialize = "dry-run"))]
+    pub dry_run: Tagged<bool>,
 }
 
 impl PerItemCommand for Move {
@@ -23,6 +25,7 @@ impl PerItemCommand for Move {
             .required("source", SyntaxShape::Pattern)
             .required("destination", SyntaxShape::Path)
             .named("file", SyntaxShape::Any)
+            .switch("dry-run")
     }
 
     fn usage(&self) -> &str {
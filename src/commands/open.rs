@@ -1,3 +1,4 @@
+use crate::commands::remote::{self, Remote};
 use crate::commands::UnevaluatedCallInfo;
 use crate::context::SpanSource;
 use crate::data::meta::Span;
@@ -6,6 +7,7 @@ use crate::errors::ShellError;
 use crate::parser::hir::SyntaxShape;
 use crate::parser::registry::Signature;
 use crate::prelude::*;
+use crate::shell::remote_shell::destination;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 pub struct Open;
@@ -22,7 +24,7 @@ impl PerItemCommand for Open {
     }
 
     fn usage(&self) -> &str {
-        "Load a file into a cell, convert to table if possible (avoid by appending '--raw')"
+        "Load a file or an http(s)://, s3:// or ftp:// url into a cell, convert to table if possible (avoid by appending '--raw')"
     }
 
     fn run(
@@ -44,6 +46,11 @@ fn run(
     let shell_manager = &raw_args.shell_manager;
     let cwd = PathBuf::from(shell_manager.path());
     let full_path = PathBuf::from(cwd);
+    // Inside a remote shell, other paths are found relative to the url it is at
+    let remote_cwd = match Remote::parse(&shell_manager.path()) {
+        Some(Remote::Http(_)) => Some(shell_manager.path()),
+        _ => None,
+    };
 
     let path = match call_info
         .args
@@ -61,7 +68,20 @@ fn run(
 
     let stream = async_stream_block! {
 
-        let result = fetch(&full_path, &path_str, path_span).await;
+        let location = match &remote_cwd {
+            Some(cwd) if Remote::parse(&path_str).is_none() => {
+                destination(cwd, cwd, &path_str, path_span.into()).await
+            }
+            _ => Ok(path_str.clone()),
+        };
+
+        let result = match location {
+            Ok(location) => match Remote::parse(&location) {
+                Some(remote) => remote::load(&remote, &location, path_span).await,
+                None => fetch(&full_path, &location, path_span).await,
+            },
+            Err(e) => Err(e),
+        };
 
         if let Err(e) = result {
             yield Err(e);
@@ -102,11 +122,13 @@ fn run(
                         source: raw_args.call_info.source,
                         source_map: raw_args.call_info.source_map,
                         name_tag: raw_args.call_info.name_tag,
+                        scope: raw_args.call_info.scope,
                     }
                 };
                 let mut result = converter.run(new_args.with_input(vec![tagged_contents]), &registry, false);
-                let result_vec: Vec<Result<ReturnSuccess, ShellError>> = result.drain_vec().await;
-                for res in result_vec {
+                // Pass rows along as the converter makes them, so large files can be cut short
+                // by `first` without being converted in full
+                while let Some(res) = result.next().await {
                     match res {
                         Ok(ReturnSuccess::Value(Tagged { item: Value::Table(list), ..})) => {
                             for l in list {
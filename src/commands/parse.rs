@@ -0,0 +1,140 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::errors::ShellError;
+use crate::prelude::*;
+use regex::Regex;
+
+#[derive(Deserialize)]
+struct ParseArgs {
+    pattern: Tagged<String>,
+    regex: bool,
+}
+
+pub struct Parse;
+
+impl WholeStreamCommand for Parse {
+    fn name(&self) -> &str {
+        "parse"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("parse")
+            .required("pattern", SyntaxShape::String)
+            .switch("regex")
+    }
+
+    fn usage(&self) -> &str {
+        "Parse columns from lines of text using a pattern (eg. parse \"{name}={value}\"), or a regex with named captures."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, parse)?.run()
+    }
+}
+
+/// Turn a template like `{name}={value}` into an anchored regex, where each `{column}`
+/// captures as little as it can while still letting the rest of the line match.
+fn template_to_regex(template: &str) -> String {
+    let mut regex = String::from("^");
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+
+        regex.push_str(&regex::escape(&rest[..start]));
+        regex.push_str(&format!("(?P<{}>.*?)", &rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+
+    regex.push_str(&regex::escape(rest));
+    regex.push('$');
+    regex
+}
+
+fn parse(
+    ParseArgs { pattern, regex }: ParseArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let source = if regex {
+        pattern.item.clone()
+    } else {
+        template_to_regex(&pattern.item)
+    };
+
+    let matcher = Regex::new(&source).map_err(|e| {
+        ShellError::labeled_error("Could not parse pattern", e.to_string(), pattern.tag())
+    })?;
+
+    let columns: Vec<String> = matcher
+        .capture_names()
+        .filter_map(|column| column.map(String::from))
+        .collect();
+
+    if columns.is_empty() {
+        return Err(ShellError::labeled_error(
+            "Pattern has no columns",
+            "expected at least one {column} or named capture",
+            pattern.tag(),
+        ));
+    }
+
+    Ok(input
+        .values
+        .map(move |v| {
+            let rows: Vec<ReturnValue> = match v.item {
+                Value::Primitive(Primitive::String(ref s)) => s
+                    .lines()
+                    .filter_map(|line| matcher.captures(line))
+                    .map(|captures| {
+                        let mut dict = TaggedDictBuilder::new(v.tag());
+                        for column in &columns {
+                            let text = captures.name(column).map(|m| m.as_str()).unwrap_or("");
+                            dict.insert(column.clone(), Value::string(text));
+                        }
+                        ReturnSuccess::value(dict.into_tagged_value())
+                    })
+                    .collect(),
+                _ => vec![Err(ShellError::labeled_error_with_secondary(
+                    "Expected a string from pipeline",
+                    "requires string input",
+                    name,
+                    "value originates from here",
+                    v.tag(),
+                ))],
+            };
+
+            futures::stream::iter(rows)
+        })
+        .flatten()
+        .to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::template_to_regex;
+    use regex::Regex;
+
+    #[test]
+    fn template_captures_columns_between_literals() {
+        let matcher = Regex::new(&template_to_regex("{name}={value}")).unwrap();
+        let captures = matcher.captures("editor=vim=nvim").unwrap();
+
+        assert_eq!(&captures["name"], "editor");
+        assert_eq!(&captures["value"], "vim=nvim");
+    }
+
+    #[test]
+    fn template_escapes_literal_text() {
+        let matcher = Regex::new(&template_to_regex("[{level}] {message}.")).unwrap();
+
+        assert!(matcher.is_match("[warn] disk almost full."));
+        assert!(!matcher.is_match("warn disk almost full"));
+    }
+}
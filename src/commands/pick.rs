@@ -1,12 +1,14 @@
 use crate::commands::WholeStreamCommand;
 use crate::context::CommandRegistry;
-use crate::data::base::select_fields;
+use crate::data::base::{select_fields, Block};
 use crate::errors::ShellError;
+use crate::parser::hir::{Literal, RawExpression, Variable};
+use crate::parser::Operator;
 use crate::prelude::*;
 
 #[derive(Deserialize)]
 struct PickArgs {
-    rest: Vec<Tagged<String>>,
+    rest: Vec<Tagged<Value>>,
 }
 
 pub struct Pick;
@@ -21,7 +23,7 @@ impl WholeStreamCommand for Pick {
     }
 
     fn usage(&self) -> &str {
-        "Down-select table to only these columns."
+        "Down-select table to only these columns, or the columns whose names match a block (eg. pick { $it == meta_* })."
     }
 
     fn run(
@@ -35,7 +37,12 @@ impl WholeStreamCommand for Pick {
 
 fn pick(
     PickArgs { rest: fields }: PickArgs,
-    RunnableContext { input, name, .. }: RunnableContext,
+    RunnableContext {
+        input,
+        name,
+        commands,
+        ..
+    }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     if fields.len() == 0 {
         return Err(ShellError::labeled_error(
@@ -45,7 +52,49 @@ fn pick(
         ));
     }
 
-    let fields: Vec<_> = fields.iter().map(|f| f.item.clone()).collect();
+    if let Some(Tagged {
+        item: Value::Block(predicate),
+        ..
+    }) = fields.first()
+    {
+        let predicate = predicate.clone();
+        let glob = glob_predicate(&predicate)?;
+
+        let objects = input.values.map(move |value| {
+            let mut columns = vec![];
+
+            for column in value.data_descriptors() {
+                let matched = match &glob {
+                    Some((pattern, equal)) => pattern.matches(&column) == *equal,
+                    None => predicate
+                        .invoke(
+                            &Value::string(column.clone()).tagged(value.tag()),
+                            &commands,
+                        )?
+                        .is_true(),
+                };
+
+                if matched {
+                    columns.push(column);
+                }
+            }
+
+            ReturnSuccess::value(select_fields(&value.item, &columns, value.tag()))
+        });
+
+        return Ok(objects.to_output_stream());
+    }
+
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|f| match f.item() {
+            Value::Primitive(Primitive::String(s)) => Ok(s.clone()),
+            other => Err(ShellError::type_error(
+                "column name",
+                f.copy_tag(other.type_name()),
+            )),
+        })
+        .collect::<Result<_, _>>()?;
 
     let objects = input
         .values
@@ -53,3 +102,36 @@ fn pick(
 
     Ok(objects.from_input_stream())
 }
+
+/// A block like `{ $it == meta_* }` matches the column names against the glob, along with whether
+/// they should match (`==`) or not (`!=`).
+fn glob_predicate(block: &Block) -> Result<Option<(glob::Pattern, bool)>, ShellError> {
+    let binary = match &block.expressions[..] {
+        [Tagged {
+            item: RawExpression::Binary(binary),
+            ..
+        }] => binary,
+        _ => return Ok(None),
+    };
+
+    let equal = match binary.op().item {
+        Operator::Equal => true,
+        Operator::NotEqual => false,
+        _ => return Ok(None),
+    };
+
+    match (&binary.left().item, &binary.right().item) {
+        (
+            RawExpression::Variable(Variable::It(_)),
+            RawExpression::Literal(Literal::GlobPattern),
+        ) => {
+            let tag = binary.right().tag();
+
+            match glob::Pattern::new(tag.slice(&block.source)) {
+                Ok(pattern) => Ok(Some((pattern, equal))),
+                Err(err) => Err(ShellError::labeled_error("Invalid pattern", err.msg, tag)),
+            }
+        }
+        _ => Ok(None),
+    }
+}
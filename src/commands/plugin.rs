@@ -35,6 +35,22 @@ pub enum NuResult {
     },
 }
 
+/// Requests a plugin may make of nu during the handshake or while it is filtering.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "method")]
+#[allow(non_camel_case_types)]
+pub enum PluginRequest {
+    config_get { params: String },
+}
+
+/// The value a plugin's `config_get` request is answered with. Plugins only ever see the keys
+/// they ask for, never the whole configuration.
+pub(crate) fn config_answer(key: &str) -> Option<Tagged<Value>> {
+    crate::data::config::config(Tag::unknown())
+        .ok()
+        .and_then(|config| config.get(key).cloned())
+}
+
 #[derive(new)]
 pub struct PluginCommand {
     name: String,
@@ -60,11 +76,141 @@ impl WholeStreamCommand for PluginCommand {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        filter_plugin(self.path.clone(), args, registry)
+        filter_plugin(self.name.clone(), self.path.clone(), args, registry)
+    }
+}
+
+/// A running filter plugin. The process stays alive for the whole stream, and is
+/// fed one JSON-RPC request per line over stdin while its responses are read back
+/// incrementally from a single buffered stdout.
+pub(crate) struct PluginProcess {
+    name: String,
+    tag: Tag,
+    child: std::process::Child,
+    stdin: Option<std::process::ChildStdin>,
+    stdout: BufReader<std::process::ChildStdout>,
+}
+
+impl PluginProcess {
+    pub(crate) fn spawn(
+        name: impl Into<String>,
+        mut command: std::process::Command,
+        tag: Tag,
+    ) -> Result<PluginProcess, ShellError> {
+        let mut child = command
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| ShellError::string("Failed to open plugin stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| ShellError::string("Failed to open plugin stdout"))?;
+
+        Ok(PluginProcess {
+            name: name.into(),
+            tag,
+            child,
+            stdin: Some(stdin),
+            stdout: BufReader::new(stdout),
+        })
+    }
+
+    fn send<T: Serialize>(&mut self, method: &str, params: T) -> Result<(), ShellError> {
+        let request = JsonRpc::new(method, params);
+        let request_raw = serde_json::to_string(&request)
+            .map_err(|err| ShellError::unexpected(format!("{}", err)))?;
+
+        let stdin = self
+            .stdin
+            .as_mut()
+            .ok_or_else(|| ShellError::string("Plugin has already finished"))?;
+
+        stdin.write_all(format!("{}\n", request_raw).as_bytes())?;
+        stdin.flush()?;
+
+        Ok(())
+    }
+
+    /// Send a request and read the single line the plugin answers it with.
+    pub(crate) fn request<T: Serialize>(
+        &mut self,
+        method: &str,
+        params: T,
+    ) -> VecDeque<ReturnValue> {
+        let mut result = VecDeque::new();
+
+        // A plugin that misbehaved has been stopped, so the rest of the stream passes it by
+        if self.stdin.is_none() {
+            return result;
+        }
+
+        if let Err(err) = self.send(method, params) {
+            result.push_back(Err(err));
+            return result;
+        }
+
+        let mut input = String::new();
+        loop {
+            input.clear();
+            if let Err(e) = self.stdout.read_line(&mut input) {
+                result.push_back(Err(ShellError::string(format!(
+                    "Error while reading {} response: {:?}",
+                    method, e
+                ))));
+                return result;
+            }
+
+            match serde_json::from_str::<PluginRequest>(&input) {
+                Ok(PluginRequest::config_get { params: key }) => {
+                    if let Err(err) = self.send("config_value", config_answer(&key)) {
+                        result.push_back(Err(err));
+                        return result;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        match serde_json::from_str::<NuResult>(&input) {
+            Ok(NuResult::response { params }) => match params {
+                Ok(params) => return params,
+                Err(e) => result.push_back(ReturnValue::Err(e)),
+            },
+            Err(_) => {
+                result.push_back(Err(ShellError::labeled_error(
+                    format!("Plugin {} sent invalid output", self.name),
+                    format!("in its {} response: {}", method, input.trim_end()),
+                    self.tag,
+                )));
+                self.abort();
+            }
+        }
+
+        result
+    }
+
+    /// Stop a plugin that can no longer be understood.
+    fn abort(&mut self) {
+        self.stdin.take();
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    /// Ask the plugin to quit, close its stdin and wait for it to exit.
+    pub(crate) fn finish(&mut self) {
+        let _ = self.send("quit", Vec::<Value>::new());
+        self.stdin.take();
+        let _ = self.child.wait();
     }
 }
 
 pub fn filter_plugin(
+    name: String,
     path: String,
     args: CommandArgs,
     registry: &CommandRegistry,
@@ -73,11 +219,11 @@ pub fn filter_plugin(
 
     let args = args.evaluate_once(registry)?;
 
-    let mut child = std::process::Command::new(path)
-        .stdin(std::process::Stdio::piped())
-        .stdout(std::process::Stdio::piped())
-        .spawn()
-        .expect("Failed to spawn child process");
+    let mut plugin = PluginProcess::spawn(
+        name,
+        std::process::Command::new(path),
+        args.call_info.name_tag,
+    )?;
 
     let mut bos: VecDeque<Tagged<Value>> = VecDeque::new();
     bos.push_back(Value::Primitive(Primitive::BeginningOfStream).tagged_unknown());
@@ -96,164 +242,16 @@ pub fn filter_plugin(
             Tagged {
                 item: Value::Primitive(Primitive::BeginningOfStream),
                 ..
-            } => {
-                let stdin = child.stdin.as_mut().expect("Failed to open stdin");
-                let stdout = child.stdout.as_mut().expect("Failed to open stdout");
-
-                let mut reader = BufReader::new(stdout);
-
-                let request = JsonRpc::new("begin_filter", call_info.clone());
-                let request_raw = serde_json::to_string(&request).unwrap();
-                match stdin.write(format!("{}\n", request_raw).as_bytes()) {
-                    Ok(_) => {}
-                    Err(err) => {
-                        let mut result = VecDeque::new();
-                        result.push_back(Err(ShellError::unexpected(format!("{}", err))));
-                        return result;
-                    }
-                }
-
-                let mut input = String::new();
-                match reader.read_line(&mut input) {
-                    Ok(_) => {
-                        let response = serde_json::from_str::<NuResult>(&input);
-                        match response {
-                            Ok(NuResult::response { params }) => match params {
-                                Ok(params) => params,
-                                Err(e) => {
-                                    let mut result = VecDeque::new();
-                                    result.push_back(ReturnValue::Err(e));
-                                    result
-                                }
-                            },
-                            Err(e) => {
-                                let mut result = VecDeque::new();
-                                result.push_back(Err(ShellError::string(format!(
-                                    "Error while processing begin_filter response: {:?} {}",
-                                    e, input
-                                ))));
-                                result
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        let mut result = VecDeque::new();
-                        result.push_back(Err(ShellError::string(format!(
-                            "Error while reading begin_filter response: {:?}",
-                            e
-                        ))));
-                        result
-                    }
-                }
-            }
+            } => plugin.request("begin_filter", call_info.clone()),
             Tagged {
                 item: Value::Primitive(Primitive::EndOfStream),
                 ..
             } => {
-                let stdin = child.stdin.as_mut().expect("Failed to open stdin");
-                let stdout = child.stdout.as_mut().expect("Failed to open stdout");
-
-                let mut reader = BufReader::new(stdout);
-
-                let request: JsonRpc<std::vec::Vec<Value>> = JsonRpc::new("end_filter", vec![]);
-                let request_raw = match serde_json::to_string(&request) {
-                    Ok(req) => req,
-                    Err(err) => {
-                        let mut result = VecDeque::new();
-                        result.push_back(Err(ShellError::unexpected(format!("{}", err))));
-                        return result;
-                    }
-                };
-
-                let _ = stdin.write(format!("{}\n", request_raw).as_bytes()); // TODO: Handle error
-
-                let mut input = String::new();
-                let result = match reader.read_line(&mut input) {
-                    Ok(_) => {
-                        let response = serde_json::from_str::<NuResult>(&input);
-                        match response {
-                            Ok(NuResult::response { params }) => match params {
-                                Ok(params) => {
-                                    let request: JsonRpc<std::vec::Vec<Value>> =
-                                        JsonRpc::new("quit", vec![]);
-                                    let request_raw = serde_json::to_string(&request).unwrap();
-                                    let _ = stdin.write(format!("{}\n", request_raw).as_bytes()); // TODO: Handle error
-
-                                    params
-                                }
-                                Err(e) => {
-                                    let mut result = VecDeque::new();
-                                    result.push_back(ReturnValue::Err(e));
-                                    result
-                                }
-                            },
-                            Err(e) => {
-                                let mut result = VecDeque::new();
-                                result.push_back(Err(ShellError::string(format!(
-                                    "Error while processing end_filter response: {:?} {}",
-                                    e, input
-                                ))));
-                                result
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        let mut result = VecDeque::new();
-                        result.push_back(Err(ShellError::string(format!(
-                            "Error while reading end_filter: {:?}",
-                            e
-                        ))));
-                        result
-                    }
-                };
-
-                let _ = child.wait();
-
+                let result = plugin.request("end_filter", Vec::<Value>::new());
+                plugin.finish();
                 result
             }
-            _ => {
-                let stdin = child.stdin.as_mut().expect("Failed to open stdin");
-                let stdout = child.stdout.as_mut().expect("Failed to open stdout");
-
-                let mut reader = BufReader::new(stdout);
-
-                let request = JsonRpc::new("filter", v);
-                let request_raw = serde_json::to_string(&request).unwrap();
-                let _ = stdin.write(format!("{}\n", request_raw).as_bytes()); // TODO: Handle error
-
-                let mut input = String::new();
-                match reader.read_line(&mut input) {
-                    Ok(_) => {
-                        let response = serde_json::from_str::<NuResult>(&input);
-                        match response {
-                            Ok(NuResult::response { params }) => match params {
-                                Ok(params) => params,
-                                Err(e) => {
-                                    let mut result = VecDeque::new();
-                                    result.push_back(ReturnValue::Err(e));
-                                    result
-                                }
-                            },
-                            Err(e) => {
-                                let mut result = VecDeque::new();
-                                result.push_back(Err(ShellError::string(format!(
-                                    "Error while processing filter response: {:?} {}",
-                                    e, input
-                                ))));
-                                result
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        let mut result = VecDeque::new();
-                        result.push_back(Err(ShellError::string(format!(
-                            "Error while reading filter response: {:?}",
-                            e
-                        ))));
-                        result
-                    }
-                }
-            }
+            _ => plugin.request("filter", v),
         })
         .flatten();
 
@@ -306,12 +304,126 @@ pub fn sink_plugin(
         let _ = writeln!(tmpfile, "{}", request_raw);
         let _ = tmpfile.flush();
 
+        let response = tempfile::NamedTempFile::new().unwrap();
+
         let mut child = std::process::Command::new(path)
             .arg(tmpfile.path())
+            .arg(response.path())
             .spawn()
             .expect("Failed to spawn child process");
 
         let _ = child.wait();
+
+        let raw = std::fs::read_to_string(response.path()).unwrap_or_default();
+        for value in sink_response(&raw) {
+            yield value;
+        }
     };
     Ok(OutputStream::new(stream))
 }
+
+/// Sinks may leave a response behind for values to send down the pipeline. A sink
+/// that writes nothing simply ends the stream.
+pub(crate) fn sink_response(raw: &str) -> VecDeque<ReturnValue> {
+    if raw.trim().is_empty() {
+        return VecDeque::new();
+    }
+
+    match serde_json::from_str::<NuResult>(raw) {
+        Ok(NuResult::response { params }) => match params {
+            Ok(params) => params,
+            Err(e) => {
+                let mut result = VecDeque::new();
+                result.push_back(ReturnValue::Err(e));
+                result
+            }
+        },
+        Err(e) => {
+            let mut result = VecDeque::new();
+            result.push_back(Err(ShellError::string(format!(
+                "Error while processing sink response: {:?} {}",
+                e, raw
+            ))));
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonRpc, PluginProcess};
+    use crate::prelude::*;
+
+    // A stand-in plugin that answers every request with the number of requests
+    // it has seen so far, which tells us whether the same process handled them.
+    #[cfg(unix)]
+    fn counting_plugin() -> std::process::Command {
+        let response = JsonRpc::new(
+            "response",
+            Ok::<_, ShellError>(vec![ReturnSuccess::value(
+                Value::string("@COUNT@").tagged_unknown(),
+            )]),
+        );
+        let response = serde_json::to_string(&response).unwrap();
+        let mut parts = response.split("@COUNT@");
+
+        let mut command = std::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(r#"n=0; while read line; do n=$((n+1)); printf '%s%s%s\n' "$BEFORE" "$n" "$AFTER"; done"#)
+            .env("BEFORE", parts.next().unwrap())
+            .env("AFTER", parts.next().unwrap());
+        command
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn one_plugin_process_handles_the_whole_stream() {
+        let mut plugin =
+            PluginProcess::spawn("counter", counting_plugin(), Tag::unknown()).unwrap();
+
+        let mut responses = vec![];
+        responses.extend(plugin.request("begin_filter", Vec::<Value>::new()));
+        for item in &["a", "b", "c"] {
+            responses.extend(plugin.request("filter", Value::string(*item).tagged_unknown()));
+        }
+        responses.extend(plugin.request("end_filter", Vec::<Value>::new()));
+        plugin.finish();
+
+        let counts: Vec<String> = responses
+            .into_iter()
+            .map(|response| match response {
+                Ok(ReturnSuccess::Value(v)) => v.as_string().unwrap(),
+                other => panic!("unexpected plugin response: {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(counts, vec!["1", "2", "3", "4", "5"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_plugin_output_is_reported_and_stops_the_plugin() {
+        let mut command = std::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg("while read line; do echo 'garbage'; done");
+
+        let mut plugin = PluginProcess::spawn("garbler", command, Tag::unknown()).unwrap();
+
+        let responses = plugin.request("begin_filter", Vec::<Value>::new());
+        assert_eq!(responses.len(), 1);
+        match &responses[0] {
+            Err(err) => assert_eq!(
+                err.clone().to_diagnostic().message,
+                "Plugin garbler sent invalid output"
+            ),
+            other => panic!("expected an error, got {:?}", other),
+        }
+
+        let responses = plugin.request("filter", Value::string("a").tagged_unknown());
+        assert!(responses.is_empty());
+
+        plugin.finish();
+    }
+}
@@ -1,4 +1,4 @@
-use crate::commands::UnevaluatedCallInfo;
+use crate::commands::{UnevaluatedCallInfo, WholeStreamCommand};
 use crate::context::SpanSource;
 use crate::data::Value;
 use crate::errors::ShellError;
@@ -13,7 +13,9 @@ use surf::mime;
 
 pub struct Post;
 
-impl PerItemCommand for Post {
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+
+impl WholeStreamCommand for Post {
     fn name(&self) -> &str {
         "post"
     }
@@ -21,33 +23,35 @@ impl PerItemCommand for Post {
     fn signature(&self) -> Signature {
         Signature::build(self.name())
             .required("path", SyntaxShape::Any)
-            .required("body", SyntaxShape::Any)
+            .optional("body", SyntaxShape::Any)
             .named("user", SyntaxShape::Any)
             .named("password", SyntaxShape::Any)
+            .named("content-type", SyntaxShape::String)
+            .named("form", SyntaxShape::Any)
             .switch("raw")
     }
 
     fn usage(&self) -> &str {
-        "Post content to a url and retrieve data as a table if possible."
+        "Post content to a url and retrieve data as a table if possible. Without a body, the input is sent (tables and rows as JSON, or url-encoded with --content-type application/x-www-form-urlencoded). Use --form key=value (or a list of them) for multipart form data."
     }
 
     fn run(
         &self,
-        call_info: &CallInfo,
+        args: CommandArgs,
         registry: &CommandRegistry,
-        raw_args: &RawCommandArgs,
-        _input: Tagged<Value>,
     ) -> Result<OutputStream, ShellError> {
-        run(call_info, registry, raw_args)
+        run(args, registry)
     }
 }
 
-fn run(
-    call_info: &CallInfo,
-    registry: &CommandRegistry,
-    raw_args: &RawCommandArgs,
-) -> Result<OutputStream, ShellError> {
-    let call_info = call_info.clone();
+fn run(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let raw_args = RawCommandArgs {
+        host: args.host.clone(),
+        shell_manager: args.shell_manager.clone(),
+        call_info: args.call_info.clone(),
+    };
+    let (input, args) = args.evaluate_once(registry)?.split();
+    let call_info = args.call_info;
     let path = match call_info
         .args
         .nth(0)
@@ -55,27 +59,44 @@ fn run(
     {
         file => file.clone(),
     };
-    let body = match call_info
-        .args
-        .nth(1)
-        .ok_or_else(|| ShellError::string(&format!("No body specified")))?
-    {
-        file => file.clone(),
-    };
+    let options = PostOptions::from_call_info(&call_info)?;
+    let body_arg = call_info.args.nth(1).cloned();
+    let name_tag = call_info.name_tag;
     let path_str = path.as_string()?;
     let path_span = path.tag();
     let has_raw = call_info.args.has("raw");
-    let user = call_info.args.get("user").map(|x| x.as_string().unwrap());
-    let password = call_info
-        .args
-        .get("password")
-        .map(|x| x.as_string().unwrap());
     let registry = registry.clone();
-    let raw_args = raw_args.clone();
 
     let stream = async_stream_block! {
-        let (file_extension, contents, contents_tag, span_source) =
-            post(&path_str, &body, user, password, path_span, &registry, &raw_args).await.unwrap();
+        // The whole input is sent as one body, so a table goes out as a single JSON array
+        let body = match body_arg {
+            Some(body) => body,
+            None => {
+                let mut values: Vec<Tagged<Value>> = input.values.collect().await;
+
+                match values.len() {
+                    0 if options.form.is_empty() => {
+                        yield Err(ShellError::labeled_error(
+                            "No body specified",
+                            "needs a body, --form fields, or input to send",
+                            name_tag,
+                        ));
+                        return;
+                    }
+                    0 => Value::nothing().tagged(name_tag),
+                    1 => values.remove(0),
+                    _ => Value::Table(values).tagged(name_tag),
+                }
+            }
+        };
+
+        let result = post(&path_str, &body, &options, path_span, &registry, &raw_args).await;
+
+        if let Err(e) = result {
+            yield Err(e);
+            return;
+        }
+        let (file_extension, contents, contents_tag, span_source) = result.unwrap();
 
         let file_extension = if has_raw {
             None
@@ -110,6 +131,7 @@ fn run(
                         source: raw_args.call_info.source,
                         source_map: raw_args.call_info.source_map,
                         name_tag: raw_args.call_info.name_tag,
+                        scope: raw_args.call_info.scope,
                     }
                 };
                 let mut result = converter.run(new_args.with_input(vec![tagged_contents]), &registry, false);
@@ -138,11 +160,100 @@ fn run(
     Ok(stream.to_output_stream())
 }
 
-pub async fn post(
-    location: &str,
-    body: &Tagged<Value>,
+enum PostBody {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Default)]
+pub(crate) struct PostOptions {
     user: Option<String>,
     password: Option<String>,
+    content_type: Option<String>,
+    form: Vec<(String, String)>,
+}
+
+impl PostOptions {
+    fn from_call_info(call_info: &CallInfo) -> Result<PostOptions, ShellError> {
+        let mut options = PostOptions::default();
+
+        options.user = call_info
+            .args
+            .get("user")
+            .map(|x| x.as_string())
+            .transpose()?;
+        options.password = call_info
+            .args
+            .get("password")
+            .map(|x| x.as_string())
+            .transpose()?;
+        options.content_type = call_info
+            .args
+            .get("content-type")
+            .map(|x| x.as_string())
+            .transpose()?;
+
+        if let Some(form) = call_info.args.get("form") {
+            let fields = match &form.item {
+                Value::Table(list) => list.iter().collect(),
+                _ => vec![form],
+            };
+
+            for field in fields {
+                options.form.push(parse_form_field(field)?);
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_form_field(field: &Tagged<Value>) -> Result<(String, String), ShellError> {
+    let text = field.as_string()?;
+    let mut parts = text.splitn(2, '=');
+
+    match (parts.next(), parts.next()) {
+        (Some(key), Some(value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(ShellError::labeled_error(
+            "Invalid form field",
+            "expected key=value",
+            field.tag(),
+        )),
+    }
+}
+
+/// Encode fields as a multipart/form-data body using the given boundary.
+pub(crate) fn multipart_body(fields: &[(String, String)], boundary: &str) -> Vec<u8> {
+    let mut body = String::new();
+
+    for (key, value) in fields {
+        body.push_str(&format!("--{}\r\n", boundary));
+        body.push_str(&format!(
+            "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+            key.replace('"', "\\\"")
+        ));
+        body.push_str(value);
+        body.push_str("\r\n");
+    }
+
+    body.push_str(&format!("--{}--\r\n", boundary));
+    body.into_bytes()
+}
+
+/// The content type of a multipart body: the type asked for, or multipart/form-data, along
+/// with the boundary the body was written with.
+pub(crate) fn multipart_content_type(content_type: Option<&str>, boundary: &str) -> String {
+    format!(
+        "{}; boundary={}",
+        content_type.unwrap_or("multipart/form-data"),
+        boundary
+    )
+}
+
+pub(crate) async fn post(
+    location: &str,
+    body: &Tagged<Value>,
+    options: &PostOptions,
     tag: Tag,
     registry: &CommandRegistry,
     raw_args: &RawCommandArgs,
@@ -150,89 +261,125 @@ pub async fn post(
     let registry = registry.clone();
     let raw_args = raw_args.clone();
     if location.starts_with("http:") || location.starts_with("https:") {
-        let login = match (user, password) {
+        let login = match (options.user.clone(), options.password.clone()) {
             (Some(user), Some(password)) => Some(encode(&format!("{}:{}", user, password))),
             (Some(user), _) => Some(encode(&format!("{}:", user))),
             _ => None,
         };
-        let response = match body {
-            Tagged {
-                item: Value::Primitive(Primitive::String(body_str)),
-                ..
-            } => {
-                let mut s = surf::post(location).body_string(body_str.to_string());
-                if let Some(login) = login {
-                    s = s.set_header("Authorization", format!("Basic {}", login));
-                }
-                s.await
-            }
-            Tagged {
-                item: Value::Primitive(Primitive::Binary(b)),
-                ..
-            } => {
-                let mut s = surf::post(location).body_bytes(b);
-                if let Some(login) = login {
-                    s = s.set_header("Authorization", format!("Basic {}", login));
-                }
-                s.await
-            }
-            Tagged { item, tag } => {
-                if let Some(converter) = registry.get_command("to-json") {
-                    let new_args = RawCommandArgs {
-                        host: raw_args.host,
-                        shell_manager: raw_args.shell_manager,
-                        call_info: UnevaluatedCallInfo {
-                            args: crate::parser::hir::Call {
-                                head: raw_args.call_info.args.head,
-                                positional: None,
-                                named: None,
-                            },
-                            source: raw_args.call_info.source,
-                            source_map: raw_args.call_info.source_map,
-                            name_tag: raw_args.call_info.name_tag,
-                        },
+        let (body, default_content_type) = if !options.form.is_empty() {
+            let boundary = format!("nushell-form-{}", uuid::Uuid::new_v4().to_simple());
+            (
+                PostBody::Bytes(multipart_body(&options.form, &boundary)),
+                Some(multipart_content_type(
+                    options
+                        .content_type
+                        .as_ref()
+                        .map(|content_type| &content_type[..]),
+                    &boundary,
+                )),
+            )
+        } else {
+            match body {
+                Tagged {
+                    item: Value::Primitive(Primitive::String(body_str)),
+                    ..
+                } => (PostBody::Text(body_str.to_string()), None),
+                Tagged {
+                    item: Value::Primitive(Primitive::Binary(b)),
+                    ..
+                } => (PostBody::Bytes(b.clone()), None),
+                Tagged { item, tag } => {
+                    // Tables and rows are sent as JSON, unless form encoding was asked for
+                    let form_encoded = options
+                        .content_type
+                        .as_ref()
+                        .map(|content_type| content_type.starts_with(FORM_URLENCODED))
+                        .unwrap_or(false);
+                    let (converter_name, converted_type) = if form_encoded {
+                        ("to-url", FORM_URLENCODED)
+                    } else {
+                        ("to-json", "application/json")
                     };
-                    let mut result = converter.run(
-                        new_args.with_input(vec![item.clone().tagged(tag.clone())]),
-                        &registry,
-                        false,
-                    );
-                    let result_vec: Vec<Result<ReturnSuccess, ShellError>> =
-                        result.drain_vec().await;
-                    let mut result_string = String::new();
-                    for res in result_vec {
-                        match res {
-                            Ok(ReturnSuccess::Value(Tagged {
-                                item: Value::Primitive(Primitive::String(s)),
-                                ..
-                            })) => {
-                                result_string.push_str(&s);
-                            }
-                            _ => {
-                                return Err(ShellError::labeled_error(
-                                    "Save could not successfully save",
-                                    "unexpected data during save",
-                                    *tag,
-                                ));
+
+                    if let Some(converter) = registry.get_command(converter_name) {
+                        let new_args = RawCommandArgs {
+                            host: raw_args.host,
+                            shell_manager: raw_args.shell_manager,
+                            call_info: UnevaluatedCallInfo {
+                                args: crate::parser::hir::Call {
+                                    head: raw_args.call_info.args.head,
+                                    positional: None,
+                                    named: None,
+                                },
+                                source: raw_args.call_info.source,
+                                source_map: raw_args.call_info.source_map,
+                                name_tag: raw_args.call_info.name_tag,
+                                scope: raw_args.call_info.scope,
+                            },
+                        };
+                        let mut result = converter.run(
+                            new_args.with_input(vec![item.clone().tagged(tag.clone())]),
+                            &registry,
+                            false,
+                        );
+                        let result_vec: Vec<Result<ReturnSuccess, ShellError>> =
+                            result.drain_vec().await;
+                        let mut result_string = String::new();
+                        for res in result_vec {
+                            match res {
+                                Ok(ReturnSuccess::Value(Tagged {
+                                    item: Value::Primitive(Primitive::String(s)),
+                                    ..
+                                })) => {
+                                    result_string.push_str(&s);
+                                }
+                                _ => {
+                                    return Err(ShellError::labeled_error(
+                                        "Save could not successfully save",
+                                        "unexpected data during save",
+                                        *tag,
+                                    ));
+                                }
                             }
                         }
-                    }
-
-                    let mut s = surf::post(location).body_string(result_string);
 
-                    if let Some(login) = login {
-                        s = s.set_header("Authorization", format!("Basic {}", login));
+                        (
+                            PostBody::Text(result_string),
+                            Some(converted_type.to_string()),
+                        )
+                    } else {
+                        return Err(ShellError::labeled_error(
+                            "Could not automatically convert table",
+                            "needs manual conversion",
+                            *tag,
+                        ));
                     }
-                    s.await
-                } else {
-                    return Err(ShellError::labeled_error(
-                        "Could not automatically convert table",
-                        "needs manual conversion",
-                        *tag,
-                    ));
                 }
             }
         };
+
+        let mut s = match body {
+            PostBody::Text(text) => surf::post(location).body_string(text),
+            PostBody::Bytes(bytes) => surf::post(location).body_bytes(bytes),
+        };
+
+        if let Some(login) = login {
+            s = s.set_header("Authorization", format!("Basic {}", login));
+        }
+
+        // An explicit --content-type wins over the type implied by the body, except that the
+        // type of a multipart body already has it, along with the boundary
+        let content_type = if options.form.is_empty() {
+            options.content_type.clone().or(default_content_type)
+        } else {
+            default_content_type
+        };
+
+        if let Some(content_type) = content_type {
+            s = s.set_header("Content-Type", content_type);
+        }
+
+        let response = s.await;
         match response {
             Ok(mut r) => match r.headers().get("content-type") {
                 Some(content_type) => {
@@ -262,6 +409,18 @@ pub async fn post(
                             tag,
                             SpanSource::Url(location.to_string()),
                         )),
+                        (mime::APPLICATION, mime::WWW_FORM_URLENCODED) => Ok((
+                            Some("url".to_string()),
+                            Value::string(r.body_string().await.map_err(|_| {
+                                ShellError::labeled_error(
+                                    "Could not load text from remote url",
+                                    "could not load",
+                                    tag,
+                                )
+                            })?),
+                            tag,
+                            SpanSource::Url(location.to_string()),
+                        )),
                         (mime::APPLICATION, mime::OCTET_STREAM) => {
                             let buf: Vec<u8> = r.body_bytes().await.map_err(|_| {
                                 ShellError::labeled_error(
@@ -363,3 +522,35 @@ pub async fn post(
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{multipart_body, multipart_content_type};
+
+    #[test]
+    fn encodes_form_fields_as_multipart() {
+        let fields = vec![
+            ("name".to_string(), "nu".to_string()),
+            ("edition".to_string(), "2018".to_string()),
+        ];
+
+        assert_eq!(
+            String::from_utf8(multipart_body(&fields, "XYZ")).unwrap(),
+            "--XYZ\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nnu\r\n\
+             --XYZ\r\nContent-Disposition: form-data; name=\"edition\"\r\n\r\n2018\r\n\
+             --XYZ--\r\n"
+        );
+    }
+
+    #[test]
+    fn keeps_the_boundary_on_an_explicit_content_type() {
+        assert_eq!(
+            multipart_content_type(Some("multipart/mixed"), "XYZ"),
+            "multipart/mixed; boundary=XYZ"
+        );
+        assert_eq!(
+            multipart_content_type(None, "XYZ"),
+            "multipart/form-data; boundary=XYZ"
+        );
+    }
+}
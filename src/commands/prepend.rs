@@ -0,0 +1,59 @@
+use crate::commands::append::rows_from;
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Prepend;
+
+#[derive(Deserialize)]
+pub struct PrependArgs {
+    row: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Prepend {
+    fn name(&self) -> &str {
+        "prepend"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("prepend").required("row value", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Prepend the given row(s) to the front of the table. A list adds each of its items as a row, and a block adds what it evaluates to."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, prepend)?.run()
+    }
+}
+
+fn prepend(
+    PrependArgs { row }: PrependArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        match rows_from(row, &commands) {
+            Ok(before) => {
+                for row in before {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+            Err(err) => yield Err(err),
+        }
+
+        let mut values = input.values;
+
+        while let Some(value) = values.next().await {
+            yield ReturnSuccess::value(value);
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
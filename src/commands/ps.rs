@@ -0,0 +1,75 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+use futures::stream::TryStreamExt;
+use heim::process::{self as process, Process, ProcessResult};
+use heim::units::{information, ratio, Ratio};
+use std::time::Duration;
+use std::usize;
+
+pub struct PS;
+
+impl WholeStreamCommand for PS {
+    fn name(&self) -> &str {
+        "ps"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("ps")
+    }
+
+    fn usage(&self) -> &str {
+        "View information about system processes."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        ps(args, registry)
+    }
+}
+
+async fn usage(process: Process) -> ProcessResult<(process::Process, Ratio)> {
+    let usage_1 = process.cpu_usage().await?;
+    futures_timer::Delay::new(Duration::from_millis(100)).await?;
+    let usage_2 = process.cpu_usage().await?;
+
+    Ok((process, usage_2 - usage_1))
+}
+
+fn ps(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+
+    let stream = async_stream_block! {
+        // Every process is sampled twice, so measure them all at once rather than one by one
+        let processes = process::processes()
+            .map_ok(|process| usage(process))
+            .try_buffer_unordered(usize::MAX);
+        pin_utils::pin_mut!(processes);
+
+        while let Some(res) = processes.next().await {
+            if let Ok((process, usage)) = res {
+                let mut dict = TaggedDictBuilder::new(tag);
+                dict.insert("pid", Value::int(process.pid()));
+                if let Ok(name) = process.name().await {
+                    dict.insert("name", Value::string(name));
+                }
+                if let Ok(status) = process.status().await {
+                    dict.insert("status", Value::string(format!("{:?}", status)));
+                }
+                dict.insert("cpu", Value::number(usage.get::<ratio::percent>()));
+                if let Ok(memory) = process.memory().await {
+                    dict.insert("mem", Value::bytes(memory.rss().get::<information::byte>()));
+                }
+                yield ReturnSuccess::value(dict.into_tagged_value());
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
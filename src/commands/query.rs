@@ -0,0 +1,155 @@
+use crate::commands::from_sqlite::convert_sqlite_row_to_nu_value;
+use crate::commands::to_sqlite::sqlite_input_stream_to_bytes;
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::Connection;
+use std::io::Write;
+
+pub struct Query;
+
+#[derive(Deserialize)]
+pub struct QueryArgs {
+    sql: Tagged<String>,
+    rest: Vec<Tagged<Value>>,
+}
+
+impl WholeStreamCommand for Query {
+    fn name(&self) -> &str {
+        "query"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("query")
+            .required("sql", SyntaxShape::String)
+            .rest(SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Run SQL against an SQLite database and create a table of the results, with any further arguments bound to the ? parameters in order (eg. open db.sqlite | query \"select * from users where age > ?\" 30)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, query)?.run()
+    }
+}
+
+fn sql_parameter(value: &Tagged<Value>) -> Result<SqlValue, ShellError> {
+    Ok(match value.item() {
+        Value::Primitive(Primitive::Nothing) => SqlValue::Null,
+        Value::Primitive(Primitive::Boolean(b)) => SqlValue::Integer(*b as i64),
+        Value::Primitive(Primitive::Int(i)) => SqlValue::Integer(
+            i.tagged(value.tag)
+                .coerce_into("converting to an SQL parameter")?,
+        ),
+        Value::Primitive(Primitive::Decimal(d)) => match d.to_f64() {
+            Some(f) if f.is_finite() => SqlValue::Real(f),
+            _ => {
+                return Err(ShellError::labeled_error(
+                    "Could not convert to an SQL parameter",
+                    "decimal is too large for a float parameter",
+                    value.tag,
+                ))
+            }
+        },
+        Value::Primitive(Primitive::Binary(b)) => SqlValue::Blob(b.clone()),
+        Value::Primitive(_) => SqlValue::Text(value.as_string()?),
+        other => {
+            return Err(ShellError::type_error(
+                "a parameter that is a single value",
+                value.copy_tag(other.type_name()),
+            ))
+        }
+    })
+}
+
+/// The database as bytes: either as it was read with `open --raw`, or rebuilt from the tables
+/// `open` turned it into.
+fn database_bytes(input: Vec<Tagged<Value>>, name: Tag) -> Result<Vec<u8>, ShellError> {
+    match &input[..] {
+        [Tagged {
+            item: Value::Primitive(Primitive::Binary(bytes)),
+            ..
+        }] => Ok(bytes.clone()),
+        [] => Err(ShellError::labeled_error(
+            "Expected a database from pipeline",
+            "requires an SQLite database as input",
+            name,
+        )),
+        _ => match sqlite_input_stream_to_bytes(input) {
+            Ok(Tagged {
+                item: Value::Primitive(Primitive::Binary(bytes)),
+                ..
+            }) => Ok(bytes),
+            _ => Err(ShellError::labeled_error(
+                "Expected a database from pipeline",
+                "requires an SQLite database as input",
+                name,
+            )),
+        },
+    }
+}
+
+fn run_query(
+    bytes: Vec<u8>,
+    sql: &str,
+    parameters: &[SqlValue],
+    tag: Tag,
+) -> Result<Vec<Tagged<Value>>, String> {
+    // Like from-sqlite, the database has to be a file for rusqlite to open it
+    let mut tempfile = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+    tempfile.write_all(&bytes).map_err(|e| e.to_string())?;
+
+    let conn = Connection::open(tempfile.path()).map_err(|e| e.to_string())?;
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let mut rows = stmt.query(parameters).map_err(|e| e.to_string())?;
+
+    let mut out = vec![];
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        out.push(convert_sqlite_row_to_nu_value(row, tag).map_err(|e| e.to_string())?);
+    }
+
+    Ok(out)
+}
+
+fn query(
+    QueryArgs { sql, rest }: QueryArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let parameters = rest
+        .iter()
+        .map(sql_parameter)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let stream = async_stream_block! {
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let bytes = match database_bytes(input, name) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                yield Err(err);
+                return;
+            }
+        };
+
+        match run_query(bytes, &sql.item, &parameters, name) {
+            Ok(rows) => {
+                for row in rows {
+                    yield ReturnSuccess::value(row);
+                }
+            }
+            Err(reason) => yield Err(ShellError::labeled_error(
+                "Could not run query",
+                reason,
+                sql.tag(),
+            )),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
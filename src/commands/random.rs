@@ -0,0 +1,195 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use bigdecimal::BigDecimal;
+use rand::distributions::Uniform;
+use rand::Rng;
+
+pub struct Random;
+
+#[derive(Deserialize)]
+pub struct RandomArgs {
+    range: Option<Tagged<Value>>,
+    integer: bool,
+    decimal: bool,
+    #[serde(rename(deserialize = "bool"))]
+    boolean: bool,
+    uuid: bool,
+    dice: Option<Tagged<u64>>,
+    sides: Option<Tagged<u64>>,
+}
+
+impl WholeStreamCommand for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("random")
+            .optional("range", SyntaxShape::Any)
+            .switch("integer")
+            .switch("decimal")
+            .switch("bool")
+            .switch("uuid")
+            .named("dice", SyntaxShape::Int)
+            .named("sides", SyntaxShape::Int)
+    }
+
+    fn usage(&self) -> &str {
+        "Generate a random --integer (the default) or --decimal in an inclusive range like 1..10, a --bool, a --uuid, or roll a number of --dice with the given --sides (6 by default)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, random)?.run()
+    }
+}
+
+/// Read `from..to`, falling back to `default` for either end that's left off.
+fn parse_range<T: std::str::FromStr + PartialOrd + Copy>(
+    range: &Option<Tagged<Value>>,
+    default: (T, T),
+) -> Result<(T, T), ShellError> {
+    let range = match range {
+        Some(range) => range,
+        None => return Ok(default),
+    };
+
+    let invalid =
+        || ShellError::labeled_error("Invalid range", "expected a range like 1..10", range.tag());
+
+    let text = range.as_string().map_err(|_| invalid())?;
+    let dots = text.find("..").ok_or_else(invalid)?;
+
+    let bound = |bound: &str, default: T| -> Result<T, ShellError> {
+        if bound.is_empty() {
+            Ok(default)
+        } else {
+            bound.parse::<T>().map_err(|_| invalid())
+        }
+    };
+
+    let from = bound(&text[..dots], default.0)?;
+    let to = bound(&text[dots + 2..], default.1)?;
+
+    if from > to {
+        Err(invalid())
+    } else {
+        Ok((from, to))
+    }
+}
+
+/// Read a `from..to` range of decimals. Both ends, and the distance between them, have to be
+/// finite for a number to be picked from it.
+fn parse_decimal_range(range: &Option<Tagged<Value>>) -> Result<(f64, f64), ShellError> {
+    let (from, to): (f64, f64) = parse_range(range, (0.0, 1.0))?;
+
+    match range {
+        Some(range) if !(to - from).is_finite() => Err(ShellError::labeled_error(
+            "Invalid range",
+            "expected a range between finite numbers",
+            range.tag(),
+        )),
+        _ => Ok((from, to)),
+    }
+}
+
+fn random(
+    RandomArgs {
+        range,
+        integer,
+        decimal,
+        boolean,
+        uuid,
+        dice,
+        sides,
+    }: RandomArgs,
+    RunnableContext { name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let modes = [integer, decimal, boolean, uuid, dice.is_some()];
+    if modes.iter().filter(|mode| **mode).count() > 1 {
+        return Err(ShellError::labeled_error(
+            "Can only generate one kind of value",
+            "use only one of --integer, --decimal, --bool, --uuid or --dice",
+            name,
+        ));
+    }
+
+    let mut rng = rand::thread_rng();
+
+    let values: Vec<Value> = if let Some(dice) = dice {
+        let sides = sides.map(|sides| sides.item).unwrap_or(6);
+        if sides == 0 {
+            return Err(ShellError::labeled_error(
+                "Invalid dice",
+                "dice need at least one side",
+                name,
+            ));
+        }
+
+        let roll = Uniform::new_inclusive(1, sides);
+        (0..dice.item)
+            .map(|_| Value::int(rng.sample(roll)))
+            .collect()
+    } else if decimal {
+        let (from, to) = parse_decimal_range(&range)?;
+        let number = rng.sample(Uniform::new_inclusive(from, to));
+
+        match BigDecimal::from_f64(number) {
+            Some(number) => vec![Value::decimal(number)],
+            None => return Err(ShellError::string("Could not generate a decimal")),
+        }
+    } else if boolean {
+        vec![Value::boolean(rng.gen::<bool>())]
+    } else if uuid {
+        vec![Value::string(uuid::Uuid::new_v4().to_string())]
+    } else {
+        let (from, to) = parse_range(&range, (0, i64::max_value()))?;
+        vec![Value::int(rng.sample(Uniform::new_inclusive(from, to)))]
+    };
+
+    let stream = values
+        .into_iter()
+        .map(|value| ReturnSuccess::value(value.tagged(name)))
+        .collect::<VecDeque<_>>();
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_decimal_range, parse_range};
+    use crate::prelude::*;
+
+    fn range(text: &str) -> Option<Tagged<Value>> {
+        Some(Value::string(text).tagged_unknown())
+    }
+
+    #[test]
+    fn reads_ranges_with_open_ends() {
+        assert_eq!(parse_range(&range("1..10"), (0, 100)).unwrap(), (1, 10));
+        assert_eq!(parse_range(&range("..10"), (0, 100)).unwrap(), (0, 10));
+        assert_eq!(
+            parse_range(&range("1.5.."), (0.0, 2.0)).unwrap(),
+            (1.5, 2.0)
+        );
+        assert_eq!(parse_range(&None, (0, 100)).unwrap(), (0, 100));
+    }
+
+    #[test]
+    fn rejects_backwards_ranges() {
+        assert!(parse_range(&range("10..1"), (0, 100)).is_err());
+        assert!(parse_range(&range("10"), (0, 100)).is_err());
+    }
+
+    #[test]
+    fn rejects_decimal_ranges_that_are_not_finite() {
+        assert_eq!(parse_decimal_range(&range("1.5..2.5")).unwrap(), (1.5, 2.5));
+        assert!(parse_decimal_range(&range("1..inf")).is_err());
+        assert!(parse_decimal_range(&range("NaN..NaN")).is_err());
+        assert!(parse_decimal_range(&range("-1e308..1e308")).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Range;
+
+#[derive(Deserialize)]
+pub struct RangeArgs {
+    area: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Range {
+    fn name(&self) -> &str {
+        "range"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("range").required("rows", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Return only the rows in the given inclusive range (eg. range 5..10, range ..10 or range 5..)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, range)?.run()
+    }
+}
+
+/// Read `from..to`, where either end may be left off. A single row number selects just that row.
+fn parse_range(value: &Tagged<Value>) -> Result<(usize, Option<usize>), ShellError> {
+    let invalid =
+        || ShellError::labeled_error("Invalid range", "expected a range like 5..10", value.tag());
+
+    let text = match value.item() {
+        Value::Primitive(Primitive::Int(_)) | Value::Primitive(Primitive::String(_)) => {
+            value.as_string()?
+        }
+        _ => return Err(invalid()),
+    };
+
+    let bound = |bound: &str| -> Result<Option<usize>, ShellError> {
+        if bound.is_empty() {
+            Ok(None)
+        } else {
+            bound.parse::<usize>().map(Some).map_err(|_| invalid())
+        }
+    };
+
+    match text.find("..") {
+        Some(dots) => {
+            let from = bound(&text[..dots])?.unwrap_or(0);
+            let to = bound(&text[dots + 2..])?;
+
+            match to {
+                Some(to) if to < from => Err(invalid()),
+                _ => Ok((from, to)),
+            }
+        }
+        None => {
+            let row = bound(&text)?.ok_or_else(invalid)?;
+            Ok((row, Some(row)))
+        }
+    }
+}
+
+fn range(
+    RangeArgs { area }: RangeArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let (from, to) = parse_range(&area)?;
+
+    let rows = input.values.skip(from as u64);
+
+    Ok(match to {
+        Some(to) => OutputStream::from_input(rows.take((to - from + 1) as u64)),
+        None => OutputStream::from_input(rows),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_range;
+    use crate::prelude::*;
+
+    fn parse(range: &str) -> Option<(usize, Option<usize>)> {
+        parse_range(&Value::string(range).tagged_unknown()).ok()
+    }
+
+    #[test]
+    fn parses_closed_and_open_ranges() {
+        assert_eq!(parse("5..10"), Some((5, Some(10))));
+        assert_eq!(parse("..10"), Some((0, Some(10))));
+        assert_eq!(parse("5.."), Some((5, None)));
+        assert_eq!(parse("3"), Some((3, Some(3))));
+    }
+
+    #[test]
+    fn rejects_backwards_and_malformed_ranges() {
+        assert_eq!(parse("10..5"), None);
+        assert_eq!(parse("a..b"), None);
+        assert_eq!(parse("-1..2"), None);
+    }
+}
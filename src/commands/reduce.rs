@@ -0,0 +1,81 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::evaluate::Scope;
+use crate::prelude::*;
+
+pub struct Reduce;
+
+#[derive(Deserialize)]
+pub struct ReduceArgs {
+    block: value::Block,
+    fold: Option<Tagged<Value>>,
+}
+
+impl WholeStreamCommand for Reduce {
+    fn name(&self) -> &str {
+        "reduce"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("reduce")
+            .required("block", SyntaxShape::Block)
+            .named("fold", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Aggregate the table to a single value. The block sees the accumulator as $acc and the row index as $index."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, reduce)?.run()
+    }
+}
+
+fn fold_values(
+    block: &value::Block,
+    initial: Option<Tagged<Value>>,
+    values: Vec<Tagged<Value>>,
+    registry: &CommandRegistry,
+) -> Result<Option<Tagged<Value>>, ShellError> {
+    let mut values = values.into_iter().enumerate();
+
+    // Without an initial value, the first row starts off the accumulator
+    let mut acc = match initial.or_else(|| values.next().map(|(_, first)| first)) {
+        Some(acc) => acc,
+        None => return Ok(None),
+    };
+
+    for (index, item) in values {
+        let index = Value::int(index).tagged(item.tag());
+        let scope = Scope::new(item)
+            .with_var("acc", acc)
+            .with_var("index", index);
+
+        acc = block.invoke_in_scope(&scope, registry)?;
+    }
+
+    Ok(Some(acc))
+}
+
+pub fn reduce(
+    ReduceArgs { block, fold }: ReduceArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        match fold_values(&block, fold, values, &commands) {
+            Ok(Some(result)) => yield ReturnSuccess::value(result),
+            Ok(None) => {}
+            Err(err) => yield Err(err),
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -1,21 +1,27 @@
 use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
 use crate::errors::ShellError;
 use crate::parser::CommandRegistry;
 use crate::prelude::*;
 
 pub struct Reverse;
 
+#[derive(Deserialize)]
+pub struct ReverseArgs {
+    columns: bool,
+}
+
 impl WholeStreamCommand for Reverse {
     fn name(&self) -> &str {
         "reverse"
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("reverse")
+        Signature::build("reverse").switch("columns")
     }
 
     fn usage(&self) -> &str {
-        "Reverses the table."
+        "Reverses the table. With --columns, reverses the order of the columns in each row instead."
     }
 
     fn run(
@@ -23,13 +29,35 @@ impl WholeStreamCommand for Reverse {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        reverse(args, registry)
+        args.process(registry, reverse)?.run()
+    }
+}
+
+fn reverse_columns(value: Tagged<Value>) -> Tagged<Value> {
+    match value {
+        Tagged {
+            item: Value::Row(dict),
+            tag,
+        } => {
+            let mut out = TaggedDictBuilder::new(tag);
+
+            for (key, value) in dict.entries.into_iter().rev() {
+                out.insert_tagged(key, value);
+            }
+
+            out.into_tagged_value()
+        }
+        other => other,
     }
 }
 
-fn reverse(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let (input, _args) = args.parts();
+fn reverse(
+    ReverseArgs { columns }: ReverseArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if columns {
+        return Ok(input.values.map(reverse_columns).from_input_stream());
+    }
 
     let output = input.values.collect::<Vec<_>>();
 
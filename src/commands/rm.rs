@@ -11,6 +11,8 @@ pub struct Remove;
 pub struct RemoveArgs {
     pub target: Tagged<PathBuf>,
     pub recursive: Tagged<bool>,
+    #[serde(rename(deserialize = "dry-run"))]
+    pub dry_run: Tagged<bool>,
 }
 
 impl PerItemCommand for Remove {
@@ -22,6 +24,7 @@ impl PerItemCommand for Remove {
         Signature::build("rm")
             .required("path", SyntaxShape::Pattern)
             .switch("recursive")
+            .switch("dry-run")
     }
 
     fn usage(&self) -> &str {
@@ -1,3 +1,4 @@
+use crate::commands::remote::{self, Remote};
 use crate::commands::{UnevaluatedCallInfo, WholeStreamCommand};
 use crate::data::Value;
 use crate::errors::ShellError;
@@ -8,14 +9,21 @@ pub struct Save;
 
 macro_rules! process_string {
     ($input:ident, $name_tag:ident) => {{
-        let mut result_string = String::new();
+        let mut result: Vec<u8> = Vec::new();
         for res in $input {
             match res {
                 Tagged {
                     item: Value::Primitive(Primitive::String(s)),
                     ..
                 } => {
-                    result_string.push_str(&s);
+                    result.extend_from_slice(s.as_bytes());
+                }
+                // Binary data, like an opened image, is written back as it was read
+                Tagged {
+                    item: Value::Primitive(Primitive::Binary(b)),
+                    ..
+                } => {
+                    result.extend(b);
                 }
                 _ => {
                     yield core::task::Poll::Ready(Err(ShellError::labeled_error(
@@ -26,7 +34,7 @@ macro_rules! process_string {
                 }
             }
         }
-        Ok(result_string.into_bytes())
+        Ok(result)
     }};
 }
 
@@ -84,6 +92,7 @@ macro_rules! process_binary_return_success {
 pub struct SaveArgs {
     path: Option<Tagged<PathBuf>>,
     raw: bool,
+    mode: Option<Tagged<Value>>,
 }
 
 impl WholeStreamCommand for Save {
@@ -92,13 +101,18 @@ impl WholeStreamCommand for Save {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("save")
+        let signature = Signature::build("save")
             .optional("path", SyntaxShape::Path)
-            .switch("raw")
+            .switch("raw");
+
+        #[cfg(unix)]
+        let signature = signature.named("mode", SyntaxShape::Any);
+
+        signature
     }
 
     fn usage(&self) -> &str {
-        "Save the contents of the pipeline to a file."
+        "Save the contents of the pipeline to a file, or upload it to an http(s)://, s3:// or ftp:// url."
     }
 
     fn run(
@@ -114,6 +128,7 @@ fn save(
     SaveArgs {
         path,
         raw: save_raw,
+        mode,
     }: SaveArgs,
     RunnableContext {
         input,
@@ -126,9 +141,15 @@ fn save(
     }: RunnableContext,
     raw_args: RawCommandArgs,
 ) -> Result<OutputStream, ShellError> {
-    let mut full_path = PathBuf::from(shell_manager.path());
+    let mut full_path = shell_manager.filesystem_path();
+    let mut remote_target: Option<(Remote, String)> = None;
     let name_tag = name;
 
+    let mode = match mode {
+        Some(ref mode) => Some(parse_mode(mode)?),
+        None => None,
+    };
+
     let source_map = source_map.clone();
     let stream = async_stream_block! {
         let input: Vec<Tagged<Value>> = input.values.collect().await;
@@ -139,7 +160,7 @@ fn save(
                 match source_map.get(&origin) {
                     Some(path) => match path {
                         SpanSource::File(file) => {
-                            full_path.push(Path::new(file));
+                            full_path.push(Path::new(&file));
                         }
                         _ => {
                             yield Err(ShellError::labeled_error(
@@ -166,13 +187,27 @@ fn save(
             }
         } else {
             if let Some(file) = path {
-                full_path.push(file.item());
+                let location = file.item().display().to_string();
+                match Remote::parse(&location) {
+                    Some(url) => remote_target = Some((url, location)),
+                    None => full_path.push(file.item()),
+                }
             }
         }
 
+        let extension = match &remote_target {
+            Some((url, _)) => url.extension(),
+            None => full_path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_string()),
+        };
+
         let content : Result<Vec<u8>, ShellError> = if !save_raw {
-            if let Some(extension) = full_path.extension() {
-                let command_name = format!("to-{}", extension.to_str().unwrap());
+            // Text, like the output of `to-json --pretty 2`, has been converted already
+            if is_text(&input) {
+                process_string!(input, name_tag)
+            } else if let Some(extension) = extension {
+                let command_name = format!("to-{}", extension);
                 if let Some(converter) = registry.get_command(&command_name) {
                     let new_args = RawCommandArgs {
                         host,
@@ -186,6 +221,7 @@ fn save(
                             source: raw_args.call_info.source,
                             source_map: raw_args.call_info.source_map,
                             name_tag: raw_args.call_info.name_tag,
+                            scope: raw_args.call_info.scope,
                         }
                     };
                     let mut result = converter.run(new_args.with_input(input), &registry, false);
@@ -202,15 +238,28 @@ fn save(
                 process_string!(input, name_tag)
             }
         } else {
-            Ok(string_from(&input).into_bytes())
+            Ok(raw_bytes_from(&input))
         };
 
-        match content {
-            Ok(save_data) => match std::fs::write(full_path, save_data) {
-                Ok(o) => o,
+        match (content, remote_target) {
+            (Ok(save_data), Some((url, location))) => {
+                if let Err(reason) = remote::store(&url, &location, save_data).await {
+                    yield Err(ShellError::labeled_error(
+                        "Could not upload to url",
+                        reason,
+                        name_tag,
+                    ));
+                }
+            }
+            (Ok(save_data), None) => match std::fs::write(&full_path, save_data) {
+                Ok(()) => if let Some(mode) = mode {
+                    if let Err(e) = set_mode(&full_path, mode) {
+                        yield Err(ShellError::string(e.to_string()));
+                    }
+                },
                 Err(e) => yield Err(ShellError::string(e.to_string())),
             },
-            Err(e) => yield Err(ShellError::string(e.to_string())),
+            (Err(e), _) => yield Err(ShellError::string(e.to_string())),
         }
 
     };
@@ -218,6 +267,64 @@ fn save(
     Ok(OutputStream::new(stream))
 }
 
+fn parse_mode(mode: &Tagged<Value>) -> Result<u32, ShellError> {
+    let invalid = || {
+        ShellError::labeled_error(
+            "Invalid file mode",
+            "expected an octal mode like 755",
+            mode.tag(),
+        )
+    };
+
+    let text = mode.as_string().map_err(|_| invalid())?;
+
+    match u32::from_str_radix(&text, 8) {
+        Ok(bits) if bits <= 0o7777 => Ok(bits),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) -> std::io::Result<()> {
+    Ok(())
+}
+
+fn is_text(input: &[Tagged<Value>]) -> bool {
+    input.len() > 0
+        && input.iter().all(|value| match value.item {
+            Value::Primitive(Primitive::String(_)) => true,
+            _ => false,
+        })
+}
+
+/// What `save --raw` writes: binary data as it is, or otherwise each value as text on a line
+/// of its own.
+fn raw_bytes_from(input: &Vec<Tagged<Value>>) -> Vec<u8> {
+    let is_binary = |value: &Tagged<Value>| match value.item {
+        Value::Primitive(Primitive::Binary(_)) => true,
+        _ => false,
+    };
+
+    if input.len() > 0 && input.iter().all(is_binary) {
+        let mut save_data = vec![];
+        for i in input.iter() {
+            if let Value::Primitive(Primitive::Binary(b)) = &i.item {
+                save_data.extend_from_slice(b);
+            }
+        }
+        return save_data;
+    }
+
+    string_from(input).into_bytes()
+}
+
 fn string_from(input: &Vec<Tagged<Value>>) -> String {
     let mut save_data = String::new();
 
@@ -0,0 +1,111 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Seq;
+
+#[derive(Deserialize)]
+pub struct SeqArgs {
+    first: Tagged<i64>,
+    last: Option<Tagged<i64>>,
+    step: Option<Tagged<i64>>,
+    width: bool,
+}
+
+impl WholeStreamCommand for Seq {
+    fn name(&self) -> &str {
+        "seq"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("seq")
+            .required("first", SyntaxShape::Int)
+            .optional("last", SyntaxShape::Int)
+            .named("step", SyntaxShape::Int)
+            .switch("width")
+    }
+
+    fn usage(&self) -> &str {
+        "Count from first to last, or from 1 to first when given one number. With --width, numbers are padded with zeros to the same width."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, seq)?.run()
+    }
+}
+
+/// The numbers from `first` to `last` inclusive, counting by `step`, which may be negative.
+fn numbers(first: i64, last: i64, step: i64) -> impl Iterator<Item = i64> {
+    std::iter::successors(Some(first), move |n| n.checked_add(step)).take_while(move |n| {
+        if step > 0 {
+            *n <= last
+        } else {
+            *n >= last
+        }
+    })
+}
+
+fn seq(
+    SeqArgs {
+        first,
+        last,
+        step,
+        width,
+    }: SeqArgs,
+    RunnableContext { name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let (first, last) = match last {
+        Some(last) => (first.item, last.item),
+        None => (1, first.item),
+    };
+
+    let step = match step {
+        Some(Tagged { item: 0, tag }) => {
+            return Err(ShellError::labeled_error(
+                "Invalid step",
+                "step can't be zero",
+                tag,
+            ))
+        }
+        Some(step) => step.item,
+        None => 1,
+    };
+
+    let digits = first.to_string().len().max(last.to_string().len());
+
+    let stream = futures::stream::iter(numbers(first, last, step)).map(move |n| {
+        let value = if width {
+            Value::string(format!("{:0width$}", n, width = digits))
+        } else {
+            Value::int(n)
+        };
+
+        ReturnSuccess::value(value.tagged(name))
+    });
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::numbers;
+
+    #[test]
+    fn counts_up_and_down_by_step() {
+        assert_eq!(numbers(1, 10, 3).collect::<Vec<_>>(), vec![1, 4, 7, 10]);
+        assert_eq!(numbers(3, -1, -2).collect::<Vec<_>>(), vec![3, 1, -1]);
+        assert!(numbers(5, 1, 1).next().is_none());
+    }
+
+    #[test]
+    fn stops_instead_of_overflowing() {
+        assert_eq!(
+            numbers(i64::max_value() - 1, i64::max_value(), 5).count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Duration, NaiveDate};
+
+pub struct SeqDate;
+
+#[derive(Deserialize)]
+pub struct SeqDateArgs {
+    begin: Tagged<String>,
+    end: Tagged<String>,
+    days: Option<Tagged<i64>>,
+    format: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for SeqDate {
+    fn name(&self) -> &str {
+        "seq-date"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("seq-date")
+            .required("begin", SyntaxShape::String)
+            .required("end", SyntaxShape::String)
+            .named("days", SyntaxShape::Int)
+            .named("format", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "List the dates from begin to end, written like 2019-12-30 (eg. seq-date \"2019-12-30\" \"2020-01-02\"). Use --days to count by more than one day and --format for the output."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, seq_date)?.run()
+    }
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+fn parse_date(date: &Tagged<String>) -> Result<NaiveDate, ShellError> {
+    NaiveDate::parse_from_str(&date.item, DATE_FORMAT).map_err(|_| {
+        ShellError::labeled_error(
+            "Invalid date",
+            "expected a date like 2019-12-30",
+            date.tag(),
+        )
+    })
+}
+
+/// The dates from `begin` to `end` inclusive, `days` apart, which may be negative.
+fn dates(begin: NaiveDate, end: NaiveDate, days: i64) -> impl Iterator<Item = NaiveDate> {
+    std::iter::successors(Some(begin), move |date| {
+        date.checked_add_signed(Duration::days(days))
+    })
+    .take_while(move |date| if days > 0 { *date <= end } else { *date >= end })
+}
+
+fn seq_date(
+    SeqDateArgs {
+        begin,
+        end,
+        days,
+        format,
+    }: SeqDateArgs,
+    RunnableContext { name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let begin_date = parse_date(&begin)?;
+    let end_date = parse_date(&end)?;
+
+    let days = match days {
+        Some(Tagged { item: 0, tag }) => {
+            return Err(ShellError::labeled_error(
+                "Invalid step",
+                "days can't be zero",
+                tag,
+            ))
+        }
+        Some(days) => days.item,
+        None => 1,
+    };
+
+    let output_format = match format {
+        Some(format) => {
+            if StrftimeItems::new(&format.item).any(|item| item == Item::Error) {
+                return Err(ShellError::labeled_error(
+                    "Invalid date format",
+                    "expected a format like %Y-%m-%d",
+                    format.tag(),
+                ));
+            }
+            format.item
+        }
+        None => DATE_FORMAT.to_string(),
+    };
+
+    let stream = futures::stream::iter(dates(begin_date, end_date, days)).map(move |date| {
+        ReturnSuccess::value(Value::string(date.format(&output_format).to_string()).tagged(name))
+    });
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dates;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn lists_dates_across_months() {
+        let listed: Vec<_> = dates(
+            NaiveDate::from_ymd(2019, 12, 30),
+            NaiveDate::from_ymd(2020, 1, 2),
+            2,
+        )
+        .map(|date| date.to_string())
+        .collect();
+
+        assert_eq!(listed, vec!["2019-12-30", "2020-01-01"]);
+    }
+}
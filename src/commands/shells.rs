@@ -34,7 +34,7 @@ fn shells(args: CommandArgs, _registry: &CommandRegistry) -> Result<OutputStream
     for (index, shell) in args.shell_manager.shells.lock().unwrap().iter().enumerate() {
         let mut dict = TaggedDictBuilder::new(tag);
 
-        if index == args.shell_manager.current_shell {
+        if index == args.shell_manager.current_shell() {
             dict.insert(" ", "X".to_string());
         } else {
             dict.insert(" ", " ".to_string());
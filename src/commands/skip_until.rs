@@ -0,0 +1,63 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct SkipUntil;
+
+#[derive(Deserialize)]
+pub struct SkipUntilArgs {
+    condition: value::Block,
+}
+
+impl WholeStreamCommand for SkipUntil {
+    fn name(&self) -> &str {
+        "skip-until"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("skip-until")
+            .required("condition", SyntaxShape::Block)
+            .filter()
+    }
+
+    fn usage(&self) -> &str {
+        "Skips rows until the condition matches."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, skip_until)?.run()
+    }
+}
+
+pub fn skip_until(
+    SkipUntilArgs { condition }: SkipUntilArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+        let mut skipping = true;
+
+        while let Some(item) = values.next().await {
+            if skipping {
+                match condition.invoke(&item, &commands) {
+                    Ok(v) if v.is_true() => skipping = false,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        yield Err(e);
+                        continue;
+                    }
+                }
+            }
+
+            yield ReturnSuccess::value(item);
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -35,10 +35,12 @@ impl WholeStreamCommand for SkipWhile {
 
 pub fn skip_while(
     SkipWhileArgs { condition }: SkipWhileArgs,
-    RunnableContext { input, .. }: RunnableContext,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let objects = input.values.skip_while(move |item| {
-        let result = condition.invoke(&item);
+        let result = condition.invoke(&item, &commands);
 
         let return_value = match result {
             Ok(ref v) if v.is_true() => true,
@@ -0,0 +1,65 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Start;
+
+#[derive(Deserialize)]
+pub struct StartArgs {
+    rest: Vec<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Start {
+    fn name(&self) -> &str {
+        "start"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("start").rest(SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Run an external command in the background as a job (same as ending the line with &)."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, start)?.run()
+    }
+}
+
+pub fn start(
+    StartArgs { rest }: StartArgs,
+    RunnableContext {
+        shell_manager,
+        commands,
+        name,
+        ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    if rest.is_empty() {
+        return Err(ShellError::labeled_error(
+            "Start requires a command",
+            "needs a command to run",
+            name,
+        ));
+    }
+
+    let command = rest
+        .iter()
+        .map(|part| part.item.clone())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let id = commands.jobs().spawn(&command, &shell_manager.path())?;
+
+    let mut job = TaggedDictBuilder::new(name);
+    job.insert("id", Value::int(id as i64));
+    job.insert("command", Value::string(command));
+
+    Ok(OutputStream::one(job.into_tagged_value()))
+}
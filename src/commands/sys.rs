@@ -1,21 +1,47 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, TaggedDictBuilder};
+use crate::errors::ShellError;
+use crate::prelude::*;
 use std::ffi::OsStr;
 
-use futures::executor::block_on;
-use futures::stream::StreamExt;
 use heim::units::{frequency, information, thermodynamic_temperature, time};
 use heim::{disk, host, memory, net, sensors};
-use nu::{
-    serve_plugin, CallInfo, Plugin, Primitive, ReturnSuccess, ReturnValue, ShellError, Signature,
-    Tag, Tagged, TaggedDictBuilder, Value,
-};
-
-struct Sys;
-impl Sys {
-    fn new() -> Sys {
-        Sys
+
+pub struct Sys;
+
+impl WholeStreamCommand for Sys {
+    fn name(&self) -> &str {
+        "sys"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("sys")
+    }
+
+    fn usage(&self) -> &str {
+        "View information about the current system."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        sys(args, registry)
     }
 }
 
+fn sys(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let tag = args.name_tag();
+
+    let stream = async_stream_block! {
+        yield ReturnSuccess::value(sysinfo(tag).await);
+    };
+
+    Ok(stream.to_output_stream())
+}
+
 async fn cpu(tag: Tag) -> Option<Tagged<Value>> {
     match futures::future::try_join(heim::cpu::logical_count(), heim::cpu::frequency()).await {
         Ok((num_cpu, cpu_speed)) => {
@@ -279,7 +305,7 @@ async fn net(tag: Tag) -> Option<Value> {
     }
 }
 
-async fn sysinfo(tag: Tag) -> Vec<Tagged<Value>> {
+async fn sysinfo(tag: Tag) -> Tagged<Value> {
     let mut sysinfo = TaggedDictBuilder::with_capacity(tag, 7);
 
     let (host, cpu, disks, memory, temp) =
@@ -304,28 +330,5 @@ async fn sysinfo(tag: Tag) -> Vec<Tagged<Value>> {
         sysinfo.insert("battery", battery);
     }
 
-    vec![sysinfo.into_tagged_value()]
-}
-
-impl Plugin for Sys {
-    fn config(&mut self) -> Result<Signature, ShellError> {
-        Ok(Signature::build("sys")
-            .desc("View information about the current system.")
-            .filter())
-    }
-
-    fn begin_filter(&mut self, callinfo: CallInfo) -> Result<Vec<ReturnValue>, ShellError> {
-        Ok(block_on(sysinfo(callinfo.name_tag))
-            .into_iter()
-            .map(ReturnSuccess::value)
-            .collect())
-    }
-
-    fn filter(&mut self, _: Tagged<Value>) -> Result<Vec<ReturnValue>, ShellError> {
-        Ok(vec![])
-    }
-}
-
-fn main() {
-    serve_plugin(&mut Sys::new());
+    sysinfo.into_tagged_value()
 }
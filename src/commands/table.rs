@@ -1,5 +1,8 @@
+use crate::commands::format_filesize::FilesizeFormat;
 use crate::commands::WholeStreamCommand;
+use crate::data::config;
 use crate::errors::ShellError;
+use crate::format::table::TableTheme;
 use crate::format::TableView;
 use crate::prelude::*;
 use futures_async_stream::async_stream_block;
@@ -7,7 +10,9 @@ use futures_async_stream::async_stream_block;
 pub struct Table;
 
 #[derive(Deserialize)]
-pub struct TableArgs {}
+pub struct TableArgs {
+    theme: Option<Tagged<String>>,
+}
 
 impl WholeStreamCommand for Table {
     fn name(&self) -> &str {
@@ -15,7 +20,7 @@ impl WholeStreamCommand for Table {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("table")
+        Signature::build("table").named("theme", SyntaxShape::String)
     }
 
     fn usage(&self) -> &str {
@@ -31,12 +36,29 @@ impl WholeStreamCommand for Table {
     }
 }
 
-pub fn table(_args: TableArgs, context: RunnableContext) -> Result<OutputStream, ShellError> {
+pub fn table(
+    TableArgs { theme }: TableArgs,
+    context: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    // The config is read once for the whole table, rather than for each thing in it
+    let config = config::config(context.name)?;
+    let filesize = FilesizeFormat::from_config(&config);
+    let theme = match theme {
+        Some(name) => Some(TableTheme::from_registry(
+            config.get("table_themes"),
+            &name,
+        )?),
+        None => None,
+    };
+
     let stream = async_stream_block! {
         let input: Vec<Tagged<Value>> = context.input.into_vec().await;
         if input.len() > 0 {
             let mut host = context.host.lock().unwrap();
-            let view = TableView::from_list(&input);
+            let view = TableView::from_list(&input, filesize).map(|view| match &theme {
+                Some(theme) => view.with_theme(theme.clone()),
+                None => view,
+            });
             if let Some(view) = view {
                 handle_unexpected(&mut *host, |host| crate::format::print_view(&view, host));
             }
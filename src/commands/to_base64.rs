@@ -0,0 +1,52 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::Value;
+use crate::prelude::*;
+
+pub struct ToBase64;
+
+impl WholeStreamCommand for ToBase64 {
+    fn name(&self) -> &str {
+        "to-base64"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to-base64")
+    }
+
+    fn usage(&self) -> &str {
+        "Encode text or binary data as base64 text"
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        to_base64(args, registry)
+    }
+}
+
+fn to_base64(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+
+    let stream = args.input.values.map(move |value| {
+        let encoded = match value.item() {
+            Value::Primitive(Primitive::String(s)) => base64::encode(s),
+            Value::Primitive(Primitive::Binary(b)) => base64::encode(b),
+            _ => {
+                return Err(ShellError::labeled_error_with_secondary(
+                    "Expected text or binary data from pipeline",
+                    "requires text or binary input",
+                    name_tag,
+                    "value originates from here",
+                    value.tag(),
+                ))
+            }
+        };
+
+        ReturnSuccess::value(Value::string(encoded).tagged(value.tag()))
+    });
+
+    Ok(stream.to_output_stream())
+}
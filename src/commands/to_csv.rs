@@ -1,3 +1,4 @@
+use crate::commands::from_csv::single_byte;
 use crate::commands::WholeStreamCommand;
 use crate::data::{Primitive, Value};
 use crate::prelude::*;
@@ -8,6 +9,7 @@ pub struct ToCSV;
 #[derive(Deserialize)]
 pub struct ToCSVArgs {
     headerless: bool,
+    separator: Option<Tagged<String>>,
 }
 
 impl WholeStreamCommand for ToCSV {
@@ -16,11 +18,13 @@ impl WholeStreamCommand for ToCSV {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("to-csv").switch("headerless")
+        Signature::build("to-csv")
+            .switch("headerless")
+            .named("separator", SyntaxShape::String)
     }
 
     fn usage(&self) -> &str {
-        "Convert table into .csv text "
+        "Convert table into .csv text, with cells separated by --separator if given"
     }
 
     fn run(
@@ -42,6 +46,7 @@ pub fn value_to_csv_value(v: &Value) -> Value {
         Value::Primitive(Primitive::Path(x)) => Value::Primitive(Primitive::Path(x.clone())),
         Value::Primitive(Primitive::Bytes(b)) => Value::Primitive(Primitive::Bytes(b.clone())),
         Value::Primitive(Primitive::Date(d)) => Value::Primitive(Primitive::Date(d.clone())),
+        Value::Primitive(Primitive::Duration(seconds)) => Value::duration(*seconds),
         Value::Row(o) => Value::Row(o.clone()),
         Value::Table(l) => Value::Table(l.clone()),
         Value::Block(_) => Value::Primitive(Primitive::Nothing),
@@ -53,6 +58,7 @@ fn to_string_helper(v: &Value) -> Result<String, ShellError> {
     match v {
         Value::Primitive(Primitive::Date(d)) => Ok(d.to_string()),
         Value::Primitive(Primitive::Bytes(b)) => Ok(format!("{}", b)),
+        Value::Primitive(Primitive::Duration(_)) => Ok(v.as_string()?),
         Value::Primitive(Primitive::Boolean(_)) => Ok(v.as_string()?),
         Value::Primitive(Primitive::Decimal(_)) => Ok(v.as_string()?),
         Value::Primitive(Primitive::Int(_)) => Ok(v.as_string()?),
@@ -76,10 +82,12 @@ fn merge_descriptors(values: &[Tagged<Value>]) -> Vec<String> {
     ret
 }
 
-pub fn to_string(v: &Value) -> Result<String, ShellError> {
+pub fn to_string(v: &Value, separator: u8) -> Result<String, ShellError> {
     match v {
         Value::Row(o) => {
-            let mut wtr = WriterBuilder::new().from_writer(vec![]);
+            let mut wtr = WriterBuilder::new()
+                .delimiter(separator)
+                .from_writer(vec![]);
             let mut fields: VecDeque<String> = VecDeque::new();
             let mut values: VecDeque<String> = VecDeque::new();
 
@@ -99,7 +107,9 @@ pub fn to_string(v: &Value) -> Result<String, ShellError> {
             .map_err(|_| ShellError::string("Could not convert record"))?);
         }
         Value::Table(list) => {
-            let mut wtr = WriterBuilder::new().from_writer(vec![]);
+            let mut wtr = WriterBuilder::new()
+                .delimiter(separator)
+                .from_writer(vec![]);
 
             let merged_descriptors = merge_descriptors(&list);
             wtr.write_record(&merged_descriptors)
@@ -131,10 +141,14 @@ pub fn to_string(v: &Value) -> Result<String, ShellError> {
 }
 
 fn to_csv(
-    ToCSVArgs { headerless }: ToCSVArgs,
+    ToCSVArgs {
+        headerless,
+        separator,
+    }: ToCSVArgs,
     RunnableContext { input, name, .. }: RunnableContext,
 ) -> Result<OutputStream, ShellError> {
     let name_tag = name;
+    let separator = single_byte(separator, b',')?;
     let stream = async_stream_block! {
          let input: Vec<Tagged<Value>> = input.values.collect().await;
 
@@ -148,7 +162,7 @@ fn to_csv(
          };
 
          for value in to_process_input {
-             match to_string(&value_to_csv_value(&value.item)) {
+             match to_string(&value_to_csv_value(&value.item), separator) {
                  Ok(x) => {
                      let converted = if headerless {
                          x.lines().skip(1).collect()
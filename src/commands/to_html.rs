@@ -0,0 +1,143 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+
+pub struct ToHTML;
+
+impl WholeStreamCommand for ToHTML {
+    fn name(&self) -> &str {
+        "to-html"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to-html")
+    }
+
+    fn usage(&self) -> &str {
+        "Convert table into an .html table"
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        to_html(args, registry)
+    }
+}
+
+pub(crate) fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+fn cell_to_html(value: &Value) -> String {
+    match value {
+        Value::Row(_) => String::from("[row]"),
+        Value::Table(list) => format!("[table: {} rows]", list.len()),
+        Value::Block(_) => String::from("[block]"),
+        Value::Primitive(Primitive::Nothing) => String::new(),
+        Value::Primitive(Primitive::Date(d)) => escape_html(&d.to_string()),
+        Value::Primitive(Primitive::Bytes(b)) => escape_html(&format!("{}", b)),
+        other => match other.as_string() {
+            Ok(s) => escape_html(&s),
+            Err(_) => String::new(),
+        },
+    }
+}
+
+fn merge_descriptors(values: &[Tagged<Value>]) -> Vec<String> {
+    let mut ret = vec![];
+    for value in values {
+        for desc in value.data_descriptors() {
+            if !ret.contains(&desc) {
+                ret.push(desc);
+            }
+        }
+    }
+    ret
+}
+
+pub fn to_html_table(values: &[Tagged<Value>]) -> String {
+    let headers = merge_descriptors(values);
+    let mut out = String::from("<table>\n");
+
+    if !headers.is_empty() {
+        out.push_str("<tr>");
+        for header in &headers {
+            out.push_str(&format!("<th>{}</th>", escape_html(header)));
+        }
+        out.push_str("</tr>\n");
+    }
+
+    for value in values {
+        out.push_str("<tr>");
+        match &value.item {
+            Value::Row(_) => {
+                for header in &headers {
+                    let cell = match value.get_data_by_key(header) {
+                        Some(cell) => cell_to_html(cell),
+                        None => String::new(),
+                    };
+                    out.push_str(&format!("<td>{}</td>", cell));
+                }
+            }
+            other => out.push_str(&format!("<td>{}</td>", cell_to_html(other))),
+        }
+        out.push_str("</tr>\n");
+    }
+
+    out.push_str("</table>");
+    out
+}
+
+fn to_html(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+    let input = args.input;
+
+    let stream = async_stream_block! {
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
+
+        let converted = to_html_table(&input);
+
+        yield ReturnSuccess::value(Value::string(converted).tagged(name_tag));
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_html, to_html_table};
+    use crate::data::TaggedDictBuilder;
+    use crate::prelude::*;
+
+    #[test]
+    fn escapes_markup_in_cells() {
+        assert_eq!(
+            escape_html("<b>\"fish\" & 'chips'</b>"),
+            "&lt;b&gt;&quot;fish&quot; &amp; &#39;chips&#39;&lt;/b&gt;"
+        );
+
+        let mut row = TaggedDictBuilder::new(Tag::unknown());
+        row.insert("name", Value::string("<script>"));
+
+        assert_eq!(
+            to_html_table(&[row.into_tagged_value()]),
+            "<table>\n<tr><th>name</th></tr>\n<tr><td>&lt;script&gt;</td></tr>\n</table>"
+        );
+    }
+}
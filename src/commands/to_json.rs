@@ -1,9 +1,18 @@
 use crate::commands::WholeStreamCommand;
-use crate::data::{Primitive, Value};
+use crate::data::{Layout, Primitive, Value};
 use crate::prelude::*;
+use serde::Serialize;
 
 pub struct ToJSON;
 
+#[derive(Deserialize)]
+pub struct ToJSONArgs {
+    array: bool,
+    lines: bool,
+    pretty: Option<Tagged<u64>>,
+    raw: bool,
+}
+
 impl WholeStreamCommand for ToJSON {
     fn name(&self) -> &str {
         "to-json"
@@ -11,10 +20,14 @@ impl WholeStreamCommand for ToJSON {
 
     fn signature(&self) -> Signature {
         Signature::build("to-json")
+            .switch("array")
+            .switch("lines")
+            .named("pretty", SyntaxShape::Int)
+            .switch("raw")
     }
 
     fn usage(&self) -> &str {
-        "Convert table into .json text"
+        "Convert table into .json text. With --array, the output is always a JSON array, even for a single item. With --lines, each row becomes its own line of JSON (JSON Lines). --pretty indents nested values by the given number of spaces, while --raw keeps everything on one line."
     }
 
     fn run(
@@ -22,7 +35,22 @@ impl WholeStreamCommand for ToJSON {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_json(args, registry)
+        args.process(registry, to_json)?.run()
+    }
+}
+
+pub(crate) fn json_string(value: &serde_json::Value, layout: Layout) -> serde_json::Result<String> {
+    match layout {
+        Layout::Default | Layout::Compact => serde_json::to_string(value),
+        Layout::Indented(indent) => {
+            let indent = " ".repeat(indent);
+            let mut out = vec![];
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+            value.serialize(&mut serializer)?;
+
+            Ok(String::from_utf8(out).expect("serde_json only writes utf-8"))
+        }
     }
 }
 
@@ -33,6 +61,9 @@ pub fn value_to_json_value(v: &Tagged<Value>) -> Result<serde_json::Value, Shell
             serde_json::Number::from(b.to_u64().expect("What about really big numbers")),
         ),
         Value::Primitive(Primitive::Date(d)) => serde_json::Value::String(d.to_string()),
+        Value::Primitive(Primitive::Duration(seconds)) => {
+            serde_json::Value::Number(serde_json::Number::from(*seconds))
+        }
         Value::Primitive(Primitive::EndOfStream) => serde_json::Value::Null,
         Value::Primitive(Primitive::BeginningOfStream) => serde_json::Value::Null,
         Value::Primitive(Primitive::Decimal(f)) => serde_json::Value::Number(
@@ -78,14 +109,41 @@ fn json_list(input: &Vec<Tagged<Value>>) -> Result<Vec<serde_json::Value>, Shell
     Ok(out)
 }
 
-fn to_json(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_tag = args.name_tag();
+fn to_json(
+    ToJSONArgs {
+        array,
+        lines,
+        pretty,
+        raw,
+    }: ToJSONArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let layout = Layout::from_flags(pretty, raw, name_tag)?;
+
+    if lines {
+        // One document per row, written as each row arrives
+        let stream = input.values.map(move |value| {
+            let json = json_string(&value_to_json_value(&value)?, layout).map_err(|_| {
+                ShellError::labeled_error_with_secondary(
+                    "Expected a table with JSON-compatible structure from pipeline",
+                    "requires JSON-compatible input",
+                    name_tag,
+                    "originates from here",
+                    value.tag(),
+                )
+            })?;
+
+            ReturnSuccess::value(Value::string(json).tagged(name_tag))
+        });
+
+        return Ok(stream.to_output_stream());
+    }
     let stream = async_stream_block! {
-        let input: Vec<Tagged<Value>> = args.input.values.collect().await;
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
 
-        let to_process_input = if input.len() > 1 {
-            let tag = input[0].tag;
+        let to_process_input = if input.len() > 1 || array {
+            let tag = input.first().map(|first| first.tag).unwrap_or(name_tag);
             vec![Tagged { item: Value::Table(input), tag } ]
         } else if input.len() == 1 {
             input
@@ -96,7 +154,7 @@ fn to_json(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream
         for value in to_process_input {
             match value_to_json_value(&value) {
                 Ok(json_value) => {
-                    match serde_json::to_string(&json_value) {
+                    match json_string(&json_value, layout) {
                         Ok(x) => yield ReturnSuccess::value(
                             Value::Primitive(Primitive::String(x)).tagged(name_tag),
                         ),
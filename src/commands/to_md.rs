@@ -0,0 +1,183 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+
+pub struct ToMarkdown;
+
+#[derive(Deserialize)]
+pub struct ToMarkdownArgs {
+    pretty: bool,
+}
+
+impl WholeStreamCommand for ToMarkdown {
+    fn name(&self) -> &str {
+        "to-md"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to-md").switch("pretty")
+    }
+
+    fn usage(&self) -> &str {
+        "Convert table into a Markdown table. With --pretty, the columns are padded to line up."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, to_md)?.run()
+    }
+}
+
+fn escape_md(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn cell_to_md(value: &Value) -> String {
+    match value {
+        Value::Row(_) => String::from("[row]"),
+        Value::Table(list) => format!("[table: {} rows]", list.len()),
+        Value::Block(_) => String::from("[block]"),
+        Value::Primitive(Primitive::Nothing) => String::new(),
+        Value::Primitive(Primitive::Date(d)) => escape_md(&d.to_string()),
+        Value::Primitive(Primitive::Bytes(b)) => escape_md(&format!("{}", b)),
+        other => match other.as_string() {
+            Ok(s) => escape_md(&s),
+            Err(_) => String::new(),
+        },
+    }
+}
+
+fn merge_descriptors(values: &[Tagged<Value>]) -> Vec<String> {
+    let mut ret = vec![];
+    for value in values {
+        for desc in value.data_descriptors() {
+            if !ret.contains(&desc) {
+                ret.push(desc);
+            }
+        }
+    }
+    ret
+}
+
+fn md_line(cells: &[String], widths: &[usize]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| {
+            let padding = width.saturating_sub(cell.chars().count());
+            format!(" {}{} ", cell, " ".repeat(padding))
+        })
+        .collect();
+
+    format!("|{}|", cells.join("|"))
+}
+
+pub fn to_md_table(values: &[Tagged<Value>], pretty: bool) -> String {
+    let headers = merge_descriptors(values);
+
+    // Values that aren't rows have no columns to make a table of
+    if headers.is_empty() {
+        return values
+            .iter()
+            .map(|value| cell_to_md(value))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    let rows: Vec<Vec<String>> = values
+        .iter()
+        .map(|value| match &value.item {
+            Value::Row(_) => headers
+                .iter()
+                .map(|header| match value.get_data_by_key(header) {
+                    Some(cell) => cell_to_md(cell),
+                    None => String::new(),
+                })
+                .collect(),
+            other => {
+                let mut cells = vec![String::new(); headers.len()];
+                cells[0] = cell_to_md(other);
+                cells
+            }
+        })
+        .collect();
+
+    let headers: Vec<String> = headers.iter().map(|header| escape_md(header)).collect();
+
+    let widths: Vec<usize> = if pretty {
+        (0..headers.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .chain(std::iter::once(headers[column].chars().count()))
+                    .chain(std::iter::once(3))
+                    .max()
+                    .unwrap_or(3)
+            })
+            .collect()
+    } else {
+        vec![0; headers.len()]
+    };
+
+    let separator: Vec<String> = widths
+        .iter()
+        .map(|width| "-".repeat(std::cmp::max(*width, 3)))
+        .collect();
+
+    let mut lines = vec![md_line(&headers, &widths), md_line(&separator, &widths)];
+    lines.extend(rows.iter().map(|row| md_line(row, &widths)));
+
+    lines.join("\n")
+}
+
+fn to_md(
+    ToMarkdownArgs { pretty }: ToMarkdownArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let stream = async_stream_block! {
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
+
+        yield ReturnSuccess::value(Value::string(to_md_table(&input, pretty)).tagged(name_tag));
+    };
+
+    Ok(stream.to_output_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_md_table;
+    use crate::data::TaggedDictBuilder;
+    use crate::prelude::*;
+
+    fn rows() -> Vec<Tagged<Value>> {
+        vec![("nu", "2018"), ("a|b", "2015")]
+            .into_iter()
+            .map(|(name, edition)| {
+                let mut row = TaggedDictBuilder::new(Tag::unknown());
+                row.insert("name", Value::string(name));
+                row.insert("edition", Value::string(edition));
+                row.into_tagged_value()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn writes_markdown_tables() {
+        assert_eq!(
+            to_md_table(&rows(), false),
+            "| name | edition |\n| --- | --- |\n| nu | 2018 |\n| a\\|b | 2015 |"
+        );
+    }
+
+    #[test]
+    fn pretty_tables_line_up() {
+        assert_eq!(
+            to_md_table(&rows(), true),
+            "| name | edition |\n| ---- | ------- |\n| nu   | 2018    |\n| a\\|b | 2015    |"
+        );
+    }
+}
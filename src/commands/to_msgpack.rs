@@ -0,0 +1,68 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::interchange::Interchange;
+use crate::data::Value;
+use crate::prelude::*;
+
+pub struct ToMsgpack;
+
+impl WholeStreamCommand for ToMsgpack {
+    fn name(&self) -> &str {
+        "to-msgpack"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to-msgpack")
+    }
+
+    fn usage(&self) -> &str {
+        "Convert table into .msgpack (MessagePack) binary data."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        to_msgpack(args, registry)
+    }
+
+    fn is_binary(&self) -> bool {
+        true
+    }
+}
+
+pub fn value_to_msgpack_bytes(value: &Tagged<Value>) -> Result<Vec<u8>, String> {
+    rmp_serde::to_vec(&Interchange(value)).map_err(|err| err.to_string())
+}
+
+fn to_msgpack(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+    let stream = async_stream_block! {
+        let input: Vec<Tagged<Value>> = args.input.values.collect().await;
+
+        let to_process_input = if input.len() > 1 {
+            let tag = input[0].tag;
+            vec![Tagged { item: Value::Table(input), tag } ]
+        } else if input.len() == 1 {
+            input
+        } else {
+            vec![]
+        };
+
+        for value in to_process_input {
+            match value_to_msgpack_bytes(&value) {
+                Ok(x) => yield ReturnSuccess::value(Value::binary(x).tagged(name_tag)),
+                Err(reason) => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a table with MessagePack-compatible structure from pipeline",
+                    reason,
+                    name_tag,
+                    "originates from here".to_string(),
+                    value.tag(),
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
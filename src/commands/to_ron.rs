@@ -0,0 +1,68 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::interchange::Interchange;
+use crate::data::{Primitive, Value};
+use crate::prelude::*;
+use ron::ser::PrettyConfig;
+
+pub struct ToRON;
+
+impl WholeStreamCommand for ToRON {
+    fn name(&self) -> &str {
+        "to-ron"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("to-ron")
+    }
+
+    fn usage(&self) -> &str {
+        "Convert table into .ron (Rusty Object Notation) text."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        to_ron(args, registry)
+    }
+}
+
+pub fn value_to_ron_string(value: &Tagged<Value>) -> Result<String, String> {
+    ron::ser::to_string_pretty(&Interchange(value), PrettyConfig::default())
+        .map_err(|err| err.to_string())
+}
+
+fn to_ron(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+    let stream = async_stream_block! {
+        let input: Vec<Tagged<Value>> = args.input.values.collect().await;
+
+        let to_process_input = if input.len() > 1 {
+            let tag = input[0].tag;
+            vec![Tagged { item: Value::Table(input), tag } ]
+        } else if input.len() == 1 {
+            input
+        } else {
+            vec![]
+        };
+
+        for value in to_process_input {
+            match value_to_ron_string(&value) {
+                Ok(x) => yield ReturnSuccess::value(
+                    Value::Primitive(Primitive::String(x)).tagged(name_tag),
+                ),
+                Err(reason) => yield Err(ShellError::labeled_error_with_secondary(
+                    "Expected a table with RON-compatible structure from pipeline",
+                    reason,
+                    name_tag,
+                    "originates from here".to_string(),
+                    value.tag(),
+                )),
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
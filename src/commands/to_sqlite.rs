@@ -95,6 +95,7 @@ fn nu_value_to_sqlite_string(v: Value) -> String {
             Primitive::Boolean(true) => "1".into(),
             Primitive::Boolean(_) => "0".into(),
             Primitive::Date(d) => format!("'{}'", d),
+            Primitive::Duration(seconds) => format!("{}", seconds),
             Primitive::Path(p) => format!("'{}'", p.display().to_string().replace("'", "''")),
             Primitive::Binary(u) => format!("x'{}'", encode(u)),
             Primitive::BeginningOfStream => "NULL".into(),
@@ -155,7 +156,7 @@ fn generate_statements(table: Dictionary) -> Result<(String, String), std::io::E
     Ok((create, insert))
 }
 
-fn sqlite_input_stream_to_bytes(
+pub(crate) fn sqlite_input_stream_to_bytes(
     values: Vec<Tagged<Value>>,
 ) -> Result<Tagged<Value>, std::io::Error> {
     // FIXME: should probably write a sqlite virtual filesystem
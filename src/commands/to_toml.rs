@@ -1,9 +1,16 @@
 use crate::commands::WholeStreamCommand;
-use crate::data::{Primitive, Value};
+use crate::data::{Layout, Primitive, Value};
 use crate::prelude::*;
+use serde::Serialize;
 
 pub struct ToTOML;
 
+#[derive(Deserialize)]
+pub struct ToTOMLArgs {
+    pretty: Option<Tagged<u64>>,
+    raw: bool,
+}
+
 impl WholeStreamCommand for ToTOML {
     fn name(&self) -> &str {
         "to-toml"
@@ -11,10 +18,12 @@ impl WholeStreamCommand for ToTOML {
 
     fn signature(&self) -> Signature {
         Signature::build("to-toml")
+            .named("pretty", SyntaxShape::Int)
+            .switch("raw")
     }
 
     fn usage(&self) -> &str {
-        "Convert table into .toml text"
+        "Convert table into .toml text. --pretty puts each item of an array on its own line, indented by the given number of spaces, while --raw keeps arrays on one line."
     }
 
     fn run(
@@ -22,7 +31,7 @@ impl WholeStreamCommand for ToTOML {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_toml(args, registry)
+        args.process(registry, to_toml)?.run()
     }
 }
 
@@ -31,6 +40,7 @@ pub fn value_to_toml_value(v: &Tagged<Value>) -> Result<toml::Value, ShellError>
         Value::Primitive(Primitive::Boolean(b)) => toml::Value::Boolean(*b),
         Value::Primitive(Primitive::Bytes(b)) => toml::Value::Integer(*b as i64),
         Value::Primitive(Primitive::Date(d)) => toml::Value::String(d.to_string()),
+        Value::Primitive(Primitive::Duration(seconds)) => toml::Value::Integer(*seconds),
         Value::Primitive(Primitive::EndOfStream) => {
             toml::Value::String("<End of Stream>".to_string())
         }
@@ -73,11 +83,29 @@ fn collect_values(input: &Vec<Tagged<Value>>) -> Result<Vec<toml::Value>, ShellE
     Ok(out)
 }
 
-fn to_toml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_tag = args.name_tag();
+pub(crate) fn toml_string(value: &toml::Value, layout: Layout) -> Result<String, toml::ser::Error> {
+    match layout {
+        Layout::Default | Layout::Compact => toml::to_string(value),
+        Layout::Indented(indent) => {
+            let mut out = String::new();
+            let mut serializer = toml::Serializer::pretty(&mut out);
+            serializer.pretty_array_indent(indent);
+            value.serialize(&mut serializer)?;
+
+            Ok(out)
+        }
+    }
+}
+
+fn to_toml(
+    ToTOMLArgs { pretty, raw }: ToTOMLArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let layout = Layout::from_flags(pretty, raw, name_tag)?;
+
     let stream = async_stream_block! {
-        let input: Vec<Tagged<Value>> = args.input.values.collect().await;
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
 
         let to_process_input = if input.len() > 1 {
             let tag = input[0].tag;
@@ -91,7 +119,7 @@ fn to_toml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream
         for value in to_process_input {
             match value_to_toml_value(&value) {
                 Ok(toml_value) => {
-                    match toml::to_string(&toml_value) {
+                    match toml_string(&toml_value, layout) {
                         Ok(x) => yield ReturnSuccess::value(
                             Value::Primitive(Primitive::String(x)).tagged(name_tag),
                         ),
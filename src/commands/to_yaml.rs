@@ -1,9 +1,17 @@
 use crate::commands::WholeStreamCommand;
-use crate::data::{Primitive, Value};
+use crate::data::{Layout, Primitive, Value};
 use crate::prelude::*;
+use serde::de::Error;
 
 pub struct ToYAML;
 
+#[derive(Deserialize)]
+pub struct ToYAMLArgs {
+    documents: bool,
+    pretty: Option<Tagged<u64>>,
+    raw: bool,
+}
+
 impl WholeStreamCommand for ToYAML {
     fn name(&self) -> &str {
         "to-yaml"
@@ -11,10 +19,13 @@ impl WholeStreamCommand for ToYAML {
 
     fn signature(&self) -> Signature {
         Signature::build("to-yaml")
+            .switch("documents")
+            .named("pretty", SyntaxShape::Int)
+            .switch("raw")
     }
 
     fn usage(&self) -> &str {
-        "Convert table into .yaml/.yml text"
+        "Convert table into .yaml/.yml text. --pretty indents nested values by the given number of spaces, while --raw writes everything on one line in flow style."
     }
 
     fn run(
@@ -22,7 +33,7 @@ impl WholeStreamCommand for ToYAML {
         args: CommandArgs,
         registry: &CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        to_yaml(args, registry)
+        args.process(registry, to_yaml)?.run()
     }
 }
 
@@ -33,6 +44,9 @@ pub fn value_to_yaml_value(v: &Tagged<Value>) -> Result<serde_yaml::Value, Shell
             serde_yaml::Value::Number(serde_yaml::Number::from(b.to_f64().unwrap()))
         }
         Value::Primitive(Primitive::Date(d)) => serde_yaml::Value::String(d.to_string()),
+        Value::Primitive(Primitive::Duration(seconds)) => {
+            serde_yaml::Value::Number(serde_yaml::Number::from(*seconds))
+        }
         Value::Primitive(Primitive::EndOfStream) => serde_yaml::Value::Null,
         Value::Primitive(Primitive::BeginningOfStream) => serde_yaml::Value::Null,
         Value::Primitive(Primitive::Decimal(f)) => {
@@ -74,11 +88,158 @@ pub fn value_to_yaml_value(v: &Tagged<Value>) -> Result<serde_yaml::Value, Shell
     })
 }
 
-fn to_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
-    let args = args.evaluate_once(registry)?;
-    let name_tag = args.name_tag();
+/// A scalar, quoted the way serde_yaml would write it.
+fn yaml_scalar(value: &serde_yaml::Value) -> Result<String, serde_yaml::Error> {
+    let text = serde_yaml::to_string(value)?;
+    Ok(text.trim_start_matches("---").trim().to_string())
+}
+
+fn is_block(value: &serde_yaml::Value) -> bool {
+    match value {
+        serde_yaml::Value::Mapping(m) => !m.is_empty(),
+        serde_yaml::Value::Sequence(s) => !s.is_empty(),
+        _ => false,
+    }
+}
+
+/// Write a mapping or sequence in block style at `column`, nesting by `indent` spaces. Items of
+/// a sequence are marked by a dash padded out to the indent, so what follows lines up.
+fn write_yaml_block(
+    out: &mut String,
+    value: &serde_yaml::Value,
+    column: usize,
+    indent: usize,
+    first_line_written: bool,
+) -> Result<(), serde_yaml::Error> {
+    let mut pad = if first_line_written {
+        String::new()
+    } else {
+        " ".repeat(column)
+    };
+
+    match value {
+        serde_yaml::Value::Mapping(m) if !m.is_empty() => {
+            for (k, v) in m.iter() {
+                out.push_str(&pad);
+                out.push_str(&yaml_scalar(k)?);
+                out.push(':');
+
+                if is_block(v) {
+                    out.push('\n');
+                    write_yaml_block(out, v, column + indent, indent, false)?;
+                } else {
+                    out.push(' ');
+                    out.push_str(&yaml_scalar(v)?);
+                    out.push('\n');
+                }
+
+                pad = " ".repeat(column);
+            }
+        }
+        serde_yaml::Value::Sequence(s) if !s.is_empty() => {
+            let marker_width = std::cmp::max(indent, 2);
+
+            for v in s.iter() {
+                out.push_str(&pad);
+                out.push('-');
+                out.push_str(&" ".repeat(marker_width - 1));
+
+                if is_block(v) {
+                    write_yaml_block(out, v, column + marker_width, indent, true)?;
+                } else {
+                    out.push_str(&yaml_scalar(v)?);
+                    out.push('\n');
+                }
+
+                pad = " ".repeat(column);
+            }
+        }
+        scalar => {
+            out.push_str(&pad);
+            out.push_str(&yaml_scalar(scalar)?);
+            out.push('\n');
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn yaml_string(
+    value: &serde_yaml::Value,
+    layout: Layout,
+) -> Result<String, serde_yaml::Error> {
+    match layout {
+        Layout::Default => serde_yaml::to_string(value),
+        // Flow style YAML looks just like JSON, so serde_json can write it
+        Layout::Compact => serde_json::to_string(value).map_err(serde_yaml::Error::custom),
+        Layout::Indented(indent) => {
+            let mut out = String::from("---\n");
+            write_yaml_block(&mut out, value, 0, std::cmp::max(indent, 1), false)?;
+            Ok(out.trim_end().to_string())
+        }
+    }
+}
+
+fn values_to_yaml_documents(
+    values: &[Tagged<Value>],
+    layout: Layout,
+    name_tag: Tag,
+) -> Result<String, ShellError> {
+    let mut output = String::new();
+
+    for value in values {
+        let document = value_to_yaml_value(value)
+            .ok()
+            .and_then(|yaml_value| yaml_string(&yaml_value, layout).ok())
+            .ok_or_else(|| {
+                ShellError::labeled_error_with_secondary(
+                    "Expected a table with YAML-compatible structure from pipeline",
+                    "requires YAML-compatible input",
+                    name_tag,
+                    "originates from here".to_string(),
+                    value.tag(),
+                )
+            })?;
+
+        // Compact documents are written without a `---`, so they need one to stay apart
+        if !document.starts_with("---") {
+            output.push_str("---\n");
+        }
+        output.push_str(&document);
+        output.push_str("\n");
+    }
+
+    Ok(output)
+}
+
+fn to_yaml(
+    ToYAMLArgs {
+        documents,
+        pretty,
+        raw,
+    }: ToYAMLArgs,
+    RunnableContext { input, name, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let name_tag = name;
+    let layout = Layout::from_flags(pretty, raw, name_tag)?;
+
+    if documents {
+        let stream = async_stream_block! {
+            let input: Vec<Tagged<Value>> = input.values.collect().await;
+
+            match values_to_yaml_documents(&input, layout, name_tag) {
+                Ok(x) => yield ReturnSuccess::value(
+                    Value::Primitive(Primitive::String(x)).tagged(name_tag),
+                ),
+                Err(e) => yield Err(e),
+            }
+        };
+
+        return Ok(stream.to_output_stream());
+    }
+
     let stream = async_stream_block! {
-        let input: Vec<Tagged<Value>> = args.input.values.collect().await;
+        let input: Vec<Tagged<Value>> = input.values.collect().await;
 
         let to_process_input = if input.len() > 1 {
             let tag = input[0].tag;
@@ -92,7 +253,7 @@ fn to_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream
         for value in to_process_input {
             match value_to_yaml_value(&value) {
                 Ok(yaml_value) => {
-                    match serde_yaml::to_string(&yaml_value) {
+                    match yaml_string(&yaml_value, layout) {
                         Ok(x) => yield ReturnSuccess::value(
                             Value::Primitive(Primitive::String(x)).tagged(name_tag),
                         ),
@@ -115,3 +276,28 @@ fn to_yaml(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream
 
     Ok(stream.to_output_stream())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::yaml_string;
+    use crate::data::Layout;
+
+    #[test]
+    fn pretty_yaml_is_indented_as_asked() {
+        let value: serde_yaml::Value = serde_yaml::from_str(
+            "name: nu\nauthors:\n  - name: Andrés\n    email: andres@nushell.sh\n",
+        )
+        .unwrap();
+
+        let pretty = yaml_string(&value, Layout::Indented(4)).unwrap();
+
+        assert_eq!(
+            pretty,
+            "---\nname: nu\nauthors:\n    -   name: Andrés\n        email: andres@nushell.sh"
+        );
+        assert_eq!(
+            serde_yaml::from_str::<serde_yaml::Value>(&pretty).unwrap(),
+            value
+        );
+    }
+}
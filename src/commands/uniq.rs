@@ -0,0 +1,84 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use std::collections::BTreeMap;
+
+pub struct Uniq;
+
+#[derive(Deserialize)]
+pub struct UniqArgs {
+    count: bool,
+}
+
+impl WholeStreamCommand for Uniq {
+    fn name(&self) -> &str {
+        "uniq"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("uniq").switch("count")
+    }
+
+    fn usage(&self) -> &str {
+        "Removes duplicate rows or values, keeping the first occurrence. With --count, adds a count of the occurrences."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, uniq)?.run()
+    }
+}
+
+fn with_count(value: Tagged<Value>, count: usize) -> Tagged<Value> {
+    let tag = value.tag();
+    let mut out = TaggedDictBuilder::new(tag);
+
+    match value.item {
+        Value::Row(dict) => {
+            for (key, value) in dict.entries {
+                out.insert_tagged(key, value);
+            }
+        }
+        other => out.insert("value", other),
+    }
+
+    out.insert("count", Value::int(count as i64));
+    out.into_tagged_value()
+}
+
+pub fn uniq(
+    UniqArgs { count }: UniqArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let values: Vec<Tagged<Value>> = input.values.collect().await;
+
+        // Values are compared without their tags, and keep the order they first appeared in
+        let mut seen: BTreeMap<Value, usize> = BTreeMap::new();
+        let mut uniques: Vec<(Tagged<Value>, usize)> = vec![];
+
+        for value in values {
+            match seen.get(&value.item) {
+                Some(&idx) => uniques[idx].1 += 1,
+                None => {
+                    seen.insert(value.item.clone(), uniques.len());
+                    uniques.push((value, 1));
+                }
+            }
+        }
+
+        for (value, occurrences) in uniques {
+            if count {
+                yield ReturnSuccess::value(with_count(value, occurrences));
+            } else {
+                yield ReturnSuccess::value(value);
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,81 @@
+use crate::commands::WholeStreamCommand;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Update;
+
+#[derive(Deserialize)]
+pub struct UpdateArgs {
+    column: Tagged<String>,
+    value: Tagged<Value>,
+}
+
+impl WholeStreamCommand for Update {
+    fn name(&self) -> &str {
+        "update"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("update")
+            .required("column", SyntaxShape::String)
+            .required("value", SyntaxShape::Any)
+    }
+
+    fn usage(&self) -> &str {
+        "Update an existing column to have a new value. The value may be a block computed from the row (eg. update name { $it.nickname })."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, update)?.run()
+    }
+}
+
+/// Compute the value to store in a row. Blocks are evaluated with the row bound
+/// as `$it`; any other value is used as-is.
+pub(crate) fn value_for_row(
+    value: &Tagged<Value>,
+    row: &Tagged<Value>,
+    registry: &CommandRegistry,
+) -> Result<Value, ShellError> {
+    match value.item() {
+        Value::Block(block) => match block.invoke(row, registry) {
+            Ok(result) => Ok(result.item),
+            Err(err) => Err(ShellError::labeled_error_with_secondary(
+                "Could not evaluate block",
+                format!("{}", err),
+                value.tag(),
+                "for this row",
+                row.tag(),
+            )),
+        },
+        other => Ok(other.clone()),
+    }
+}
+
+pub fn update(
+    UpdateArgs { column, value }: UpdateArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = input.values.map(move |row| {
+        let replacement = value_for_row(&value, &row, &commands)?;
+
+        match row.replace_data_at_path(row.tag(), &column.item, replacement) {
+            Some(updated) => ReturnSuccess::value(updated),
+            None => Err(ShellError::labeled_error_with_secondary(
+                "Unknown column",
+                "column not found",
+                column.tag(),
+                "in this row",
+                row.tag(),
+            )),
+        }
+    });
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,50 @@
+use crate::commands::from_base64::text_or_binary;
+use crate::commands::WholeStreamCommand;
+use crate::data::Value;
+use crate::prelude::*;
+use percent_encoding::percent_decode_str;
+
+pub struct UrlDecode;
+
+impl WholeStreamCommand for UrlDecode {
+    fn name(&self) -> &str {
+        "url-decode"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("url-decode")
+    }
+
+    fn usage(&self) -> &str {
+        "Decode percent-encoded text, into binary data when the result isn't valid utf-8"
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        url_decode(args, registry)
+    }
+}
+
+fn url_decode(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+
+    let stream = args.input.values.map(move |value| match value.item() {
+        Value::Primitive(Primitive::String(s)) => {
+            let bytes: Vec<u8> = percent_decode_str(s).collect();
+            ReturnSuccess::value(text_or_binary(bytes).tagged(value.tag()))
+        }
+        _ => Err(ShellError::labeled_error_with_secondary(
+            "Expected text from pipeline",
+            "requires text input",
+            name_tag,
+            "value originates from here",
+            value.tag(),
+        )),
+    });
+
+    Ok(stream.to_output_stream())
+}
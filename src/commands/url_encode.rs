@@ -0,0 +1,62 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::Value;
+use crate::prelude::*;
+use percent_encoding::{percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+pub struct UrlEncode;
+
+/// Everything but the characters a URL never needs escaped: letters, digits, `-`, `_`, `.` and `~`.
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+impl WholeStreamCommand for UrlEncode {
+    fn name(&self) -> &str {
+        "url-encode"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("url-encode")
+    }
+
+    fn usage(&self) -> &str {
+        "Percent-encode text or binary data so it can be used as part of a url"
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        url_encode(args, registry)
+    }
+}
+
+fn url_encode(args: CommandArgs, registry: &CommandRegistry) -> Result<OutputStream, ShellError> {
+    let args = args.evaluate_once(registry)?;
+    let name_tag = args.name_tag();
+
+    let stream = args.input.values.map(move |value| {
+        let bytes = match value.item() {
+            Value::Primitive(Primitive::String(s)) => s.as_bytes(),
+            Value::Primitive(Primitive::Binary(b)) => &b[..],
+            _ => {
+                return Err(ShellError::labeled_error_with_secondary(
+                    "Expected text or binary data from pipeline",
+                    "requires text or binary input",
+                    name_tag,
+                    "value originates from here",
+                    value.tag(),
+                ))
+            }
+        };
+
+        let encoded = percent_encode(bytes, URL_COMPONENT).to_string();
+
+        ReturnSuccess::value(Value::string(encoded).tagged(value.tag()))
+    });
+
+    Ok(stream.to_output_stream())
+}
@@ -0,0 +1,100 @@
+use crate::cli::view_block;
+use crate::commands::WholeStreamCommand;
+use crate::context::Context;
+use crate::errors::ShellError;
+use crate::prelude::*;
+use futures::channel::mpsc;
+use notify::{DebouncedEvent, RecursiveMode, Watcher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub struct Watch;
+
+#[derive(Deserialize)]
+pub struct WatchArgs {
+    path: Tagged<PathBuf>,
+    block: value::Block,
+}
+
+impl WholeStreamCommand for Watch {
+    fn name(&self) -> &str {
+        "watch"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("watch")
+            .required("path", SyntaxShape::Path)
+            .required("block", SyntaxShape::Block)
+    }
+
+    fn usage(&self) -> &str {
+        "Run the pipeline in a block each time files under the path change, showing its output as it runs (eg. watch src { cargo build })."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, watch)?.run()
+    }
+}
+
+fn is_change(event: &DebouncedEvent) -> bool {
+    match event {
+        DebouncedEvent::Create(_)
+        | DebouncedEvent::Write(_)
+        | DebouncedEvent::Chmod(_)
+        | DebouncedEvent::Remove(_)
+        | DebouncedEvent::Rename(_, _) => true,
+        _ => false,
+    }
+}
+
+pub fn watch(
+    WatchArgs { path, block }: WatchArgs,
+    RunnableContext {
+        shell_manager,
+        commands,
+        ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let full_path = PathBuf::from(shell_manager.path()).join(&path.item);
+
+    let (watcher_tx, watcher_rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::watcher(watcher_tx, Duration::from_millis(500)).map_err(|e| {
+        ShellError::labeled_error("Could not watch path", e.to_string(), path.tag())
+    })?;
+
+    watcher
+        .watch(&full_path, RecursiveMode::Recursive)
+        .map_err(|e| {
+            ShellError::labeled_error("Could not watch path", e.to_string(), path.tag())
+        })?;
+
+    // notify reports changes on a blocking channel, so forward them from a thread of their own
+    let (changes_tx, mut changes) = mpsc::unbounded();
+    std::thread::spawn(move || {
+        let _watcher = watcher;
+
+        for event in watcher_rx {
+            if is_change(&event) && changes_tx.unbounded_send(()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut context = Context::from_parts(commands, shell_manager);
+
+    // The watch never ends, so each run is viewed as it happens rather than handed on
+    let stream = async_stream_block! {
+        while let Some(()) = changes.next().await {
+            if let Err(err) = view_block(&block, &mut context).await {
+                let err: ReturnValue = Err(err);
+                yield err;
+            }
+        }
+    };
+
+    Ok(stream.to_output_stream())
+}
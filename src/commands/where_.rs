@@ -1,59 +1,79 @@
-use crate::commands::PerItemCommand;
+use crate::commands::WholeStreamCommand;
 use crate::errors::ShellError;
+use crate::evaluate::Scope;
 use crate::parser::hir::SyntaxShape;
 use crate::parser::registry;
 use crate::prelude::*;
 
 pub struct Where;
 
-impl PerItemCommand for Where {
+#[derive(Deserialize)]
+pub struct WhereArgs {
+    condition: value::Block,
+    #[serde(rename(deserialize = "with-prev"))]
+    with_prev: bool,
+}
+
+impl WholeStreamCommand for Where {
     fn name(&self) -> &str {
         "where"
     }
 
     fn signature(&self) -> registry::Signature {
-        Signature::build("where").required("condition", SyntaxShape::Block)
+        Signature::build("where")
+            .required("condition", SyntaxShape::Block)
+            .switch("with-prev")
     }
 
     fn usage(&self) -> &str {
-        "Filter table to match the condition."
+        "Filter table to match the condition. With --with-prev, the preceding row is available as $prev, which is nothing for the first row."
     }
 
     fn run(
         &self,
-        call_info: &CallInfo,
-        _registry: &registry::CommandRegistry,
-        _raw_args: &RawCommandArgs,
-        input: Tagged<Value>,
+        args: CommandArgs,
+        registry: &registry::CommandRegistry,
     ) -> Result<OutputStream, ShellError> {
-        let input_clone = input.clone();
-        let condition = call_info.args.expect_nth(0)?;
-        let stream = match condition {
-            Tagged {
-                item: Value::Block(block),
-                ..
-            } => {
-                let result = block.invoke(&input_clone);
-                match result {
-                    Ok(v) => {
-                        if v.is_true() {
-                            VecDeque::from(vec![Ok(ReturnSuccess::Value(input_clone))])
-                        } else {
-                            VecDeque::new()
-                        }
+        args.process(registry, where_)?.run()
+    }
+}
+
+pub fn where_(
+    WhereArgs {
+        condition,
+        with_prev,
+    }: WhereArgs,
+    RunnableContext {
+        input, commands, ..
+    }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let stream = async_stream_block! {
+        let mut values = input.values;
+        let mut prev: Option<Tagged<Value>> = None;
+
+        while let Some(row) = values.next().await {
+            let result = if with_prev {
+                // The first row has no predecessor, so its `$prev` is nothing
+                let previous = prev
+                    .replace(row.clone())
+                    .unwrap_or_else(|| Value::nothing().tagged(row.tag()));
+
+                let scope = Scope::new(row.clone()).with_var("prev", previous);
+                condition.invoke_in_scope(&scope, &commands)
+            } else {
+                condition.invoke(&row, &commands)
+            };
+
+            match result {
+                Ok(v) => {
+                    if v.is_true() {
+                        yield ReturnSuccess::value(row);
                     }
-                    Err(e) => return Err(e),
                 }
+                Err(e) => yield Err(e),
             }
-            Tagged { tag, .. } => {
-                return Err(ShellError::labeled_error(
-                    "Expected a condition",
-                    "where needs a condition",
-                    *tag,
-                ))
-            }
-        };
+        }
+    };
 
-        Ok(stream.to_output_stream())
-    }
+    Ok(stream.to_output_stream())
 }
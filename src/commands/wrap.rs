@@ -0,0 +1,51 @@
+use crate::commands::WholeStreamCommand;
+use crate::data::TaggedDictBuilder;
+use crate::errors::ShellError;
+use crate::prelude::*;
+
+pub struct Wrap;
+
+#[derive(Deserialize)]
+pub struct WrapArgs {
+    column: Option<Tagged<String>>,
+}
+
+impl WholeStreamCommand for Wrap {
+    fn name(&self) -> &str {
+        "wrap"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("wrap").optional("column", SyntaxShape::String)
+    }
+
+    fn usage(&self) -> &str {
+        "Wrap each value into a row with a single column, named Column unless another name is given."
+    }
+
+    fn run(
+        &self,
+        args: CommandArgs,
+        registry: &CommandRegistry,
+    ) -> Result<OutputStream, ShellError> {
+        args.process(registry, wrap)?.run()
+    }
+}
+
+pub fn wrap(
+    WrapArgs { column }: WrapArgs,
+    RunnableContext { input, .. }: RunnableContext,
+) -> Result<OutputStream, ShellError> {
+    let column = column
+        .map(|column| column.item)
+        .unwrap_or_else(|| "Column".to_string());
+
+    let stream = input.values.map(move |value| {
+        let mut dict = TaggedDictBuilder::new(value.tag());
+        dict.insert_tagged(column.clone(), value);
+
+        ReturnSuccess::value(dict.into_tagged_value())
+    });
+
+    Ok(stream.to_output_stream())
+}
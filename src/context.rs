@@ -1,4 +1,7 @@
+use crate::commands::alias::AliasDefinition;
 use crate::commands::{Command, UnevaluatedCallInfo};
+use crate::evaluate::Scope;
+use crate::jobs::JobManager;
 use crate::parser::hir;
 use crate::prelude::*;
 
@@ -17,20 +20,22 @@ pub enum SpanSource {
     Source(Text),
 }
 
+/// Where each origin's text came from. Clones share the map, so a command further down a
+/// streaming pipeline sees the sources added by the commands before it.
 #[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct SourceMap(HashMap<Uuid, SpanSource>);
+pub struct SourceMap(Arc<Mutex<HashMap<Uuid, SpanSource>>>);
 
 impl SourceMap {
     pub fn insert(&mut self, uuid: Uuid, span_source: SpanSource) {
-        self.0.insert(uuid, span_source);
+        self.0.lock().unwrap().insert(uuid, span_source);
     }
 
-    pub fn get(&self, uuid: &Uuid) -> Option<&SpanSource> {
-        self.0.get(uuid)
+    pub fn get(&self, uuid: &Uuid) -> Option<SpanSource> {
+        self.0.lock().unwrap().get(uuid).cloned()
     }
 
     pub fn new() -> SourceMap {
-        SourceMap(HashMap::new())
+        SourceMap(Arc::new(Mutex::new(HashMap::new())))
     }
 }
 
@@ -38,15 +43,19 @@ impl SourceMap {
 pub struct CommandRegistry {
     #[new(value = "Arc::new(Mutex::new(IndexMap::default()))")]
     registry: Arc<Mutex<IndexMap<String, Arc<Command>>>>,
+    #[new(value = "Arc::new(Mutex::new(IndexMap::default()))")]
+    aliases: Arc<Mutex<IndexMap<String, AliasDefinition>>>,
+    #[new(value = "Arc::new(Mutex::new(IndexMap::default()))")]
+    variables: Arc<Mutex<IndexMap<String, Tagged<Value>>>>,
+    #[new(default)]
+    jobs: JobManager,
+    /// The shells of the context the registry was made for, so a command in parentheses runs
+    /// in the shell it was typed in
+    #[new(default)]
+    shell_manager: Arc<Mutex<Option<ShellManager>>>,
 }
 
 impl CommandRegistry {
-    pub(crate) fn empty() -> CommandRegistry {
-        CommandRegistry {
-            registry: Arc::new(Mutex::new(IndexMap::default())),
-        }
-    }
-
     pub(crate) fn get_command(&self, name: &str) -> Option<Arc<Command>> {
         let registry = self.registry.lock().unwrap();
 
@@ -68,6 +77,49 @@ impl CommandRegistry {
         let registry = self.registry.lock().unwrap();
         registry.keys().cloned().collect()
     }
+
+    pub(crate) fn add_alias(&self, name: impl Into<String>, alias: AliasDefinition) {
+        let mut aliases = self.aliases.lock().unwrap();
+        aliases.insert(name.into(), alias);
+    }
+
+    pub(crate) fn get_alias(&self, name: &str) -> Option<AliasDefinition> {
+        let aliases = self.aliases.lock().unwrap();
+
+        aliases.get(name).cloned()
+    }
+
+    pub(crate) fn set_variable(&self, name: impl Into<String>, value: Tagged<Value>) {
+        let mut variables = self.variables.lock().unwrap();
+        variables.insert(name.into(), value);
+    }
+
+    pub(crate) fn get_variable(&self, name: &str) -> Option<Tagged<Value>> {
+        let variables = self.variables.lock().unwrap();
+
+        variables.get(name).cloned()
+    }
+
+    pub(crate) fn variables(&self) -> Vec<(String, Tagged<Value>)> {
+        let variables = self.variables.lock().unwrap();
+
+        variables
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+
+    pub(crate) fn jobs(&self) -> &JobManager {
+        &self.jobs
+    }
+
+    fn set_shell_manager(&self, shell_manager: ShellManager) {
+        *self.shell_manager.lock().unwrap() = Some(shell_manager);
+    }
+
+    pub(crate) fn shell_manager(&self) -> Option<ShellManager> {
+        self.shell_manager.lock().unwrap().clone()
+    }
 }
 
 #[derive(Clone)]
@@ -76,6 +128,13 @@ pub struct Context {
     pub(crate) source_map: SourceMap,
     host: Arc<Mutex<dyn Host + Send>>,
     pub(crate) shell_manager: ShellManager,
+    current_errors: Arc<Mutex<Vec<ShellError>>>,
+    scope: Scope,
+    /// Whether an external command exiting with an error fails its line, as it does in scripts.
+    /// At the prompt a `grep` that finds nothing isn't worth an error.
+    pub(crate) fail_on_exit_code: bool,
+    /// External commands piped into another, waited for once the command they feed is done
+    pub(crate) upstream_externals: Arc<Mutex<Vec<(subprocess::Popen, Tag)>>>,
 }
 
 impl Context {
@@ -85,20 +144,67 @@ impl Context {
 
     pub(crate) fn basic() -> Result<Context, Box<dyn Error>> {
         let registry = CommandRegistry::new();
+        let shell_manager = ShellManager::basic(registry.clone())?;
+        registry.set_shell_manager(shell_manager.clone());
+
         Ok(Context {
-            registry: registry.clone(),
+            registry,
             source_map: SourceMap::new(),
             host: Arc::new(Mutex::new(crate::env::host::BasicHost)),
-            shell_manager: ShellManager::basic(registry)?,
+            shell_manager,
+            current_errors: Arc::new(Mutex::new(vec![])),
+            scope: Scope::empty(),
+            fail_on_exit_code: false,
+            upstream_externals: Arc::new(Mutex::new(vec![])),
         })
     }
 
+    /// A context over the registry and shells a command was run with, so that the command
+    /// can run pipelines of its own.
+    pub(crate) fn from_parts(registry: CommandRegistry, shell_manager: ShellManager) -> Context {
+        Context {
+            registry,
+            source_map: SourceMap::new(),
+            host: Arc::new(Mutex::new(crate::env::host::BasicHost)),
+            shell_manager,
+            current_errors: Arc::new(Mutex::new(vec![])),
+            scope: Scope::empty(),
+            fail_on_exit_code: false,
+            upstream_externals: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Run the commands of this context in `scope`, as when they are in parentheses in a block.
+    pub(crate) fn with_scope(mut self, scope: Scope) -> Context {
+        self.scope = scope;
+        self
+    }
+
     pub(crate) fn with_host(&mut self, block: impl FnOnce(&mut dyn Host)) {
         let mut host = self.host.lock().unwrap();
 
         block(&mut *host)
     }
 
+    /// Keep an error from a stage whose output is streamed, so it is reported once the
+    /// pipeline has run.
+    pub(crate) fn error(&mut self, error: ShellError) {
+        self.current_errors.lock().unwrap().push(error);
+    }
+
+    /// The first error kept while running a pipeline, clearing the rest.
+    pub(crate) fn take_error(&mut self) -> Option<ShellError> {
+        let mut errors = self.current_errors.lock().unwrap();
+        let first = if errors.is_empty() {
+            None
+        } else {
+            Some(errors.remove(0))
+        };
+        errors.clear();
+
+        first
+    }
+
     pub fn add_commands(&mut self, commands: Vec<Arc<Command>>) {
         for command in commands {
             self.registry.insert(command.name().to_string(), command);
@@ -143,6 +249,7 @@ impl Context {
             source: source.clone(),
             source_map,
             name_tag,
+            scope: self.scope.clone(),
         }
     }
 
@@ -3,7 +3,9 @@ pub(crate) mod command;
 pub(crate) mod config;
 pub(crate) mod dict;
 pub(crate) mod files;
+pub(crate) mod interchange;
 pub(crate) mod into;
+pub(crate) mod layout;
 pub(crate) mod meta;
 pub(crate) mod types;
 
@@ -11,3 +13,4 @@ pub(crate) use base::{Primitive, Value};
 pub(crate) use command::command_dict;
 pub(crate) use dict::{Dictionary, TaggedDictBuilder, TaggedListBuilder};
 pub(crate) use files::dir_entry_dict;
+pub(crate) use layout::Layout;
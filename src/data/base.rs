@@ -2,7 +2,7 @@ use crate::context::CommandRegistry;
 use crate::data::TaggedDictBuilder;
 use crate::errors::ShellError;
 use crate::evaluate::{evaluate_baseline_expr, Scope};
-use crate::parser::{hir, Operator};
+use crate::parser::{hir, Operator, Pipeline};
 use crate::prelude::*;
 use crate::Text;
 use chrono::{DateTime, Utc};
@@ -77,6 +77,8 @@ pub enum Primitive {
     Pattern(String),
     Boolean(bool),
     Date(DateTime<Utc>),
+    /// A span of time, in seconds
+    Duration(i64),
     Path(PathBuf),
     #[serde(with = "serde_bytes")]
     Binary(Vec<u8>),
@@ -114,6 +116,7 @@ impl Primitive {
             String(_) => "string",
             Boolean(_) => "boolean",
             Date(_) => "date",
+            Duration(_) => "duration",
             Binary(_) => "binary",
         }
         .to_string()
@@ -134,6 +137,7 @@ impl Primitive {
             String(string) => write!(f, "{:?}", string),
             Boolean(boolean) => write!(f, "{}", boolean),
             Date(date) => write!(f, "{}", date),
+            Duration(seconds) => write!(f, "{}", seconds),
             Binary(binary) => write!(f, "{:?}", binary),
         }
     }
@@ -181,18 +185,59 @@ impl Primitive {
             },
             Primitive::Binary(_) => format!("<binary>"),
             Primitive::Date(d) => format!("{}", d.humanize()),
+            Primitive::Duration(seconds) => duration_to_string(*seconds),
         }
     }
 
     pub fn style(&self) -> &'static str {
         match self {
             Primitive::Bytes(0) => "c", // centre 'missing' indicator
-            Primitive::Int(_) | Primitive::Bytes(_) | Primitive::Decimal(_) => "r",
+            Primitive::Int(_)
+            | Primitive::Bytes(_)
+            | Primitive::Decimal(_)
+            | Primitive::Duration(_) => "r",
             _ => "",
         }
     }
 }
 
+/// Write a number of seconds the way a person would read it, like `1d 2h 3m 4s`.
+pub(crate) fn duration_to_string(seconds: i64) -> String {
+    if seconds == 0 {
+        return "0s".to_string();
+    }
+
+    let sign = if seconds < 0 { "-" } else { "" };
+    let mut remaining = seconds.abs();
+    let mut parts = vec![];
+
+    for (size, suffix) in &[(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")] {
+        let amount = remaining / size;
+        remaining %= size;
+
+        if amount > 0 {
+            parts.push(format!("{}{}", amount, suffix));
+        }
+    }
+
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// Why an arithmetic operator could not produce a value.
+#[derive(Debug)]
+pub(crate) enum ComputeError {
+    /// The operands' types can't be combined by the operator
+    Coerce(String, String),
+    /// The result doesn't fit in its type, named here
+    OutOfRange(&'static str),
+}
+
+impl From<(String, String)> for ComputeError {
+    fn from((left, right): (String, String)) -> ComputeError {
+        ComputeError::Coerce(left, right)
+    }
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq, Clone, new, Serialize)]
 pub struct Operation {
     pub(crate) left: Value,
@@ -205,14 +250,51 @@ pub struct Block {
     pub(crate) expressions: Vec<hir::Expression>,
     pub(crate) source: Text,
     pub(crate) tag: Tag,
+    /// The pipeline the block was written as, which commands like `watch` run
+    #[serde(default)]
+    pub(crate) pipeline: Option<Tagged<Pipeline>>,
+    /// Variables bound with `let` when the block was created
+    #[new(default)]
+    #[serde(default)]
+    pub(crate) vars: Vec<(String, Tagged<Value>)>,
 }
 
 impl Block {
-    pub fn invoke(&self, value: &Tagged<Value>) -> Result<Tagged<Value>, ShellError> {
-        let scope = Scope::new(value.clone());
+    pub(crate) fn with_vars(mut self, vars: Vec<(String, Tagged<Value>)>) -> Block {
+        self.vars = vars;
+        self
+    }
 
+    pub fn invoke(
+        &self,
+        value: &Tagged<Value>,
+        registry: &CommandRegistry,
+    ) -> Result<Tagged<Value>, ShellError> {
+        self.invoke_in_scope(&Scope::new(value.clone()), registry)
+    }
+
+    /// Evaluate the block's expressions in `scope`, running any commands in them, like the
+    /// `date now` of `(date now)`, from `registry`.
+    pub(crate) fn invoke_in_scope(
+        &self,
+        scope: &Scope,
+        registry: &CommandRegistry,
+    ) -> Result<Tagged<Value>, ShellError> {
         if self.expressions.len() == 0 {
-            return Ok(Value::nothing().tagged(self.tag));
+            return match &self.pipeline {
+                Some(pipeline) if pipeline.parts.len() > 1 => Err(ShellError::labeled_error(
+                    "A block with a pipeline in it can't be evaluated as an expression",
+                    "pipeline",
+                    self.tag,
+                )),
+                _ => Ok(Value::nothing().tagged(self.tag)),
+            };
+        }
+
+        // The variables bound when the block was created, unless the scope has its own
+        let mut scope = scope.clone();
+        for (name, value) in self.vars.iter() {
+            scope = scope.with_default_var(name.clone(), value.clone());
         }
 
         let mut last = None;
@@ -220,7 +302,7 @@ impl Block {
         for expr in self.expressions.iter() {
             last = Some(evaluate_baseline_expr(
                 &expr,
-                &CommandRegistry::empty(),
+                registry,
                 &scope,
                 &self.source,
             )?)
@@ -455,6 +537,42 @@ impl Value {
         Some(Tagged::from_item(current, tag))
     }
 
+    /// Follow a column path where a `*` segment maps the rest of the path over every
+    /// row of a table, collecting the results into a new table.
+    pub(crate) fn get_data_by_column_path(
+        &self,
+        tag: Tag,
+        path: &[&str],
+    ) -> Result<Tagged<Value>, ShellError> {
+        match path.split_first() {
+            None => Ok(self.clone().tagged(tag)),
+            Some((&"*", rest)) => match self {
+                Value::Table(rows) => {
+                    let mut out = vec![];
+
+                    for row in rows {
+                        out.push(row.get_data_by_column_path(tag, rest)?);
+                    }
+
+                    Ok(Value::Table(out).tagged(tag))
+                }
+                other => Err(ShellError::labeled_error(
+                    "Wildcard over a non-list",
+                    format!("expected a list, found {}", other.type_name()),
+                    tag,
+                )),
+            },
+            Some((name, rest)) => match self.get_data_by_key(name) {
+                Some(next) => next.get_data_by_column_path(tag, rest),
+                None => Err(ShellError::labeled_error(
+                    "Unknown column",
+                    format!("no column named '{}'", name),
+                    tag,
+                )),
+            },
+        }
+    }
+
     pub fn insert_data_at_path(
         &self,
         tag: Tag,
@@ -582,31 +700,172 @@ impl Value {
         operator: &Operator,
         other: &Value,
     ) -> Result<bool, (String, String)> {
-        match operator {
+        // Nothing, like the `$prev` of a first row, only equals nothing and is neither before
+        // nor after anything
+        if self.is_nothing() || other.is_nothing() {
+            let both = self.is_nothing() && other.is_nothing();
+
+            return Ok(match operator {
+                Operator::Equal => both,
+                Operator::NotEqual => !both,
+                _ => false,
+            });
+        }
+
+        let coerced = coerce_compare(self, other)?;
+        let ordering = coerced.compare();
+
+        use std::cmp::Ordering;
+
+        let result = match (operator, ordering) {
+            (Operator::Equal, Ordering::Equal) => true,
+            (Operator::NotEqual, Ordering::Less) | (Operator::NotEqual, Ordering::Greater) => true,
+            (Operator::LessThan, Ordering::Less) => true,
+            (Operator::GreaterThan, Ordering::Greater) => true,
+            (Operator::GreaterThanOrEqual, Ordering::Greater)
+            | (Operator::GreaterThanOrEqual, Ordering::Equal) => true,
+            (Operator::LessThanOrEqual, Ordering::Less)
+            | (Operator::LessThanOrEqual, Ordering::Equal) => true,
+            _ => false,
+        };
+
+        Ok(result)
+    }
+
+    /// Apply an arithmetic operator. Bytes and durations stay what they are when combined with
+    /// numbers, strings can be joined with `+`, and dates move by a duration or a number of
+    /// seconds (or subtract to the duration between them). Division by zero is left to the
+    /// caller to report.
+    pub(crate) fn compute(
+        &self,
+        operator: &Operator,
+        other: &Value,
+    ) -> Result<Value, ComputeError> {
+        use Primitive::*;
+
+        let (left, right) = match (self, other) {
+            (Value::Primitive(left), Value::Primitive(right)) => (left, right),
+            _ => return Err((self.type_name(), other.type_name()).into()),
+        };
+
+        match (left, right, operator) {
+            (String(left), String(right), Operator::Plus) => {
+                Ok(Value::string(format!("{}{}", left, right)))
+            }
+            (Date(left), Date(right), Operator::Minus) => {
+                Ok(Value::duration((*left - *right).num_seconds()))
+            }
+            (Date(date), Duration(_), Operator::Plus)
+            | (Date(date), Duration(_), Operator::Minus)
+            | (Date(date), Int(_), Operator::Plus)
+            | (Date(date), Int(_), Operator::Minus)
+            | (Date(date), Decimal(_), Operator::Plus)
+            | (Date(date), Decimal(_), Operator::Minus) => {
+                let duration = match right {
+                    Duration(seconds) => Some(*seconds),
+                    Int(seconds) => seconds.to_i64(),
+                    Decimal(seconds) => seconds.to_i64(),
+                    _ => None,
+                }
+                .and_then(|seconds| seconds.checked_mul(1000))
+                .map(chrono::Duration::milliseconds)
+                .ok_or(ComputeError::OutOfRange("date"))?;
+
+                let moved = if *operator == Operator::Plus {
+                    date.checked_add_signed(duration)
+                } else {
+                    date.checked_sub_signed(duration)
+                };
+
+                match moved {
+                    Some(moved) => Ok(Value::Primitive(Date(moved))),
+                    None => Err(ComputeError::OutOfRange("date")),
+                }
+            }
+            (Duration(left), Duration(right), Operator::Plus) => left
+                .checked_add(*right)
+                .map(Value::duration)
+                .ok_or(ComputeError::OutOfRange("duration")),
+            (Duration(left), Duration(right), Operator::Minus) => left
+                .checked_sub(*right)
+                .map(Value::duration)
+                .ok_or(ComputeError::OutOfRange("duration")),
+            (String(_), _, _) | (_, String(_), _) => {
+                Err((left.type_name(), right.type_name()).into())
+            }
             _ => {
-                let coerced = coerce_compare(self, other)?;
-                let ordering = coerced.compare();
-
-                use std::cmp::Ordering;
-
-                let result = match (operator, ordering) {
-                    (Operator::Equal, Ordering::Equal) => true,
-                    (Operator::NotEqual, Ordering::Less)
-                    | (Operator::NotEqual, Ordering::Greater) => true,
-                    (Operator::LessThan, Ordering::Less) => true,
-                    (Operator::GreaterThan, Ordering::Greater) => true,
-                    (Operator::GreaterThanOrEqual, Ordering::Greater)
-                    | (Operator::GreaterThanOrEqual, Ordering::Equal) => true,
-                    (Operator::LessThanOrEqual, Ordering::Less)
-                    | (Operator::LessThanOrEqual, Ordering::Equal) => true,
+                let result = match coerce_compare_primitive(left, right)? {
+                    CompareValues::Ints(left, right) => match operator {
+                        Operator::Plus => Value::int(left + right),
+                        Operator::Minus => Value::int(left - right),
+                        Operator::Multiply => Value::int(left * right),
+                        Operator::Divide if (&left % &right).is_zero() => Value::int(left / right),
+                        Operator::Divide => {
+                            Value::decimal(BigDecimal::new(left, 0) / BigDecimal::new(right, 0))
+                        }
+                        Operator::Modulo => Value::int(left % right),
+                        _ => return Err((self.type_name(), other.type_name()).into()),
+                    },
+                    CompareValues::Decimals(left, right) => match operator {
+                        Operator::Plus => Value::decimal(left + right),
+                        Operator::Minus => Value::decimal(left - right),
+                        Operator::Multiply => Value::decimal(left * right),
+                        Operator::Divide => Value::decimal(left / right),
+                        Operator::Modulo => {
+                            let quotient = (&left / &right).with_scale(0);
+                            Value::decimal(left - right * quotient)
+                        }
+                        _ => return Err((self.type_name(), other.type_name()).into()),
+                    },
+                    CompareValues::String(..) | CompareValues::Date(..) => {
+                        return Err((self.type_name(), other.type_name()).into())
+                    }
+                };
+
+                // Sizes keep reading as sizes, unless one is divided by another
+                let sized = match (left, right, operator) {
+                    (Bytes(_), Bytes(_), Operator::Divide) => false,
+                    (Bytes(_), _, _) | (_, Bytes(_), _) => true,
                     _ => false,
                 };
 
-                Ok(result)
+                // Durations too, so a duration over a duration is a plain ratio
+                let timed = match (left, right, operator) {
+                    (Duration(_), Duration(_), Operator::Divide) => false,
+                    (Duration(_), _, _) | (_, Duration(_), _) => true,
+                    _ => false,
+                };
+
+                Ok(match result {
+                    Value::Primitive(Int(ref int)) if sized => match int.to_u64() {
+                        Some(bytes) => Value::bytes(bytes),
+                        None => result,
+                    },
+                    Value::Primitive(Int(ref int)) if timed => match int.to_i64() {
+                        Some(seconds) => Value::duration(seconds),
+                        None => return Err(ComputeError::OutOfRange("duration")),
+                    },
+                    Value::Primitive(Decimal(ref decimal)) if timed => match decimal.to_i64() {
+                        Some(seconds) => Value::duration(seconds),
+                        None => return Err(ComputeError::OutOfRange("duration")),
+                    },
+                    result => result,
+                })
             }
         }
     }
 
+    /// Whether dividing by this value would fail.
+    pub(crate) fn is_zero(&self) -> bool {
+        match self {
+            Value::Primitive(Primitive::Int(int)) => int.is_zero(),
+            Value::Primitive(Primitive::Decimal(decimal)) => decimal.is_zero(),
+            Value::Primitive(Primitive::Bytes(bytes)) => *bytes == 0,
+            Value::Primitive(Primitive::Duration(seconds)) => *seconds == 0,
+            _ => false,
+        }
+    }
+
     pub(crate) fn as_string(&self) -> Result<String, ShellError> {
         match self {
             Value::Primitive(Primitive::String(s)) => Ok(s.clone()),
@@ -614,6 +873,7 @@ impl Value {
             Value::Primitive(Primitive::Decimal(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Int(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Bytes(x)) => Ok(format!("{}", x)),
+            Value::Primitive(Primitive::Duration(x)) => Ok(format!("{}", x)),
             Value::Primitive(Primitive::Path(x)) => Ok(format!("{}", x.display())),
             // TODO: this should definitely be more general with better errors
             other => Err(ShellError::string(format!(
@@ -623,6 +883,24 @@ impl Value {
         }
     }
 
+    pub(crate) fn is_nothing(&self) -> bool {
+        match self {
+            Value::Primitive(Primitive::Nothing) => true,
+            _ => false,
+        }
+    }
+
+    /// Nothing, or a string, table or row without anything in it.
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            Value::Primitive(Primitive::Nothing) => true,
+            Value::Primitive(Primitive::String(s)) => s.is_empty(),
+            Value::Table(rows) => rows.is_empty(),
+            Value::Row(dict) => dict.entries.is_empty(),
+            _ => false,
+        }
+    }
+
     pub(crate) fn is_true(&self) -> bool {
         match self {
             Value::Primitive(Primitive::Boolean(true)) => true,
@@ -671,6 +949,10 @@ impl Value {
         Value::Primitive(Primitive::Boolean(s.into()))
     }
 
+    pub fn duration(seconds: impl Into<i64>) -> Value {
+        Value::Primitive(Primitive::Duration(seconds.into()))
+    }
+
     pub fn system_date(s: SystemTime) -> Value {
         Value::Primitive(Primitive::Date(s.into()))
     }
@@ -736,6 +1018,7 @@ enum CompareValues {
     Ints(BigInt, BigInt),
     Decimals(BigDecimal, BigDecimal),
     String(String, String),
+    Date(DateTime<Utc>, DateTime<Utc>),
 }
 
 impl CompareValues {
@@ -744,6 +1027,7 @@ impl CompareValues {
             CompareValues::Ints(left, right) => left.cmp(right),
             CompareValues::Decimals(left, right) => left.cmp(right),
             CompareValues::String(left, right) => left.cmp(right),
+            CompareValues::Date(left, right) => left.cmp(right),
         }
     }
 }
@@ -780,6 +1064,18 @@ fn coerce_compare_primitive(
             CompareValues::Decimals(BigDecimal::from(*left), right.clone())
         }
         (String(left), String(right)) => CompareValues::String(left.clone(), right.clone()),
+        (Date(left), Date(right)) => CompareValues::Date(*left, *right),
+        (Duration(left), Duration(right)) => {
+            CompareValues::Ints(BigInt::from(*left), BigInt::from(*right))
+        }
+        (Duration(left), Int(right)) => CompareValues::Ints(BigInt::from(*left), right.clone()),
+        (Int(left), Duration(right)) => CompareValues::Ints(left.clone(), BigInt::from(*right)),
+        (Duration(left), Decimal(right)) => {
+            CompareValues::Decimals(BigDecimal::from(*left), right.clone())
+        }
+        (Decimal(left), Duration(right)) => {
+            CompareValues::Decimals(left.clone(), BigDecimal::from(*right))
+        }
         _ => return Err((left.type_name(), right.type_name())),
     })
 }
@@ -126,3 +126,101 @@ fn touch(path: &Path) -> io::Result<()> {
         Err(e) => Err(e),
     }
 }
+
+fn expect_one_of(value: &Tagged<Value>, choices: &[&str]) -> Option<String> {
+    match value.as_string() {
+        Ok(ref s) if choices.contains(&s.as_str()) => None,
+        Ok(s) => Some(format!(
+            "expected one of {}, found {}",
+            choices.join(", "),
+            s
+        )),
+        Err(_) => Some(format!(
+            "expected one of {}, found {}",
+            choices.join(", "),
+            value.type_name()
+        )),
+    }
+}
+
+fn expect_string(value: &Tagged<Value>) -> Option<String> {
+    match value.item() {
+        Value::Primitive(Primitive::String(_)) => None,
+        other => Some(format!("expected a string, found {}", other.type_name())),
+    }
+}
+
+fn expect_boolean(value: &Tagged<Value>) -> Option<String> {
+    match value.item() {
+        Value::Primitive(Primitive::Boolean(_)) => None,
+        Value::Primitive(Primitive::String(s)) if s == "true" || s == "false" => None,
+        other => Some(format!(
+            "expected true or false, found {}",
+            other.type_name()
+        )),
+    }
+}
+
+fn expect_integer(value: &Tagged<Value>) -> Option<String> {
+    match value.item() {
+        Value::Primitive(Primitive::Int(_)) => None,
+        other => Some(format!("expected an integer, found {}", other.type_name())),
+    }
+}
+
+fn expect_list_of_strings(value: &Tagged<Value>) -> Option<String> {
+    match value.item() {
+        Value::Table(list) => list
+            .iter()
+            .filter_map(expect_string)
+            .next()
+            .map(|problem| format!("every entry must be a string ({})", problem)),
+        other => Some(format!(
+            "expected a list of strings, found {}",
+            other.type_name()
+        )),
+    }
+}
+
+fn expect_row(value: &Tagged<Value>) -> Option<String> {
+    match value.item() {
+        Value::Row(_) => None,
+        other => Some(format!("expected a table, found {}", other.type_name())),
+    }
+}
+
+/// Validate the known configuration keys. Each problem found is returned as a
+/// `(key, severity, problem)` triple, where severity is either "error" or "warning".
+/// Keys we don't know about are reported as warnings, since plugins may rely on them.
+pub fn check(config: &IndexMap<String, Tagged<Value>>) -> Vec<(String, &'static str, String)> {
+    let mut problems = vec![];
+
+    for (key, value) in config.iter() {
+        let problem = match key.as_str() {
+            "edit_mode" => expect_one_of(value, &["vi", "emacs"]),
+            "table_mode" => expect_one_of(value, &["light", "normal"]),
+            "filesize_format" => expect_one_of(value, &["binary", "decimal", "bytes"]),
+            "single_row_view" => expect_one_of(value, &["vertical", "table"]),
+            "ctrlc_exit" | "color" => expect_boolean(value),
+            "prompt" | "prompt_command" => expect_string(value),
+            "plugin_dirs" => expect_list_of_strings(value),
+            "table_themes" => expect_row(value),
+            "max_rows" | "history_size" => expect_integer(value),
+            "history_path" => expect_string(value),
+            _ => {
+                problems.push((
+                    key.clone(),
+                    "warning",
+                    "unknown configuration key".to_string(),
+                ));
+                continue;
+            }
+        };
+
+        if let Some(problem) = problem {
+            problems.push((key.clone(), "error", problem));
+        }
+    }
+
+    problems
+}
@@ -12,6 +12,7 @@ pub enum FileType {
 pub(crate) fn dir_entry_dict(
     filename: &std::path::Path,
     metadata: &std::fs::Metadata,
+    full: bool,
     tag: impl Into<Tag>,
 ) -> Result<Tagged<Value>, ShellError> {
     let mut dict = TaggedDictBuilder::new(tag);
@@ -33,14 +34,25 @@ pub(crate) fn dir_entry_dict(
 
     dict.insert("size", Value::bytes(metadata.len() as u64));
 
-    match metadata.created() {
-        Ok(c) => dict.insert("created", Value::system_date(c)),
-        Err(_) => {}
-    }
+    if full {
+        match metadata.created() {
+            Ok(c) => dict.insert("created", Value::system_date(c)),
+            Err(_) => {}
+        }
 
-    match metadata.accessed() {
-        Ok(a) => dict.insert("accessed", Value::system_date(a)),
-        Err(_) => {}
+        match metadata.accessed() {
+            Ok(a) => dict.insert("accessed", Value::system_date(a)),
+            Err(_) => {}
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            dict.insert(
+                "permissions",
+                Value::string(permissions_string(metadata.permissions().mode())),
+            );
+        }
     }
 
     match metadata.modified() {
@@ -50,3 +62,30 @@ pub(crate) fn dir_entry_dict(
 
     Ok(dict.into_tagged_value())
 }
+
+/// Render a unix mode the way `ls -l` does, eg. `rwxr-xr--`.
+#[cfg(unix)]
+fn permissions_string(mode: u32) -> String {
+    let flags = ['r', 'w', 'x'];
+
+    (0..9)
+        .map(|bit| {
+            if mode & (0o400 >> bit) != 0 {
+                flags[bit % 3]
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::permissions_string;
+
+    #[test]
+    fn renders_unix_permissions() {
+        assert_eq!(permissions_string(0o755), "rwxr-xr-x");
+        assert_eq!(permissions_string(0o100640), "rw-r-----");
+    }
+}
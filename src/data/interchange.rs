@@ -0,0 +1,150 @@
+use crate::data::{Primitive, TaggedDictBuilder, Value};
+use crate::prelude::*;
+use serde::de::{DeserializeSeed, Error as DeError, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Error as SerError, SerializeMap, SerializeSeq};
+use std::fmt;
+
+/// Serializes a value with the closest types of a self-describing format like MessagePack or
+/// CBOR: rows become maps, tables arrays, and binary data stays binary. Dates are written as
+/// text and durations as seconds.
+pub(crate) struct Interchange<'a>(pub(crate) &'a Tagged<Value>);
+
+impl<'a> serde::Serialize for Interchange<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.item() {
+            Value::Primitive(Primitive::Boolean(b)) => serializer.serialize_bool(*b),
+            Value::Primitive(Primitive::Bytes(b)) => serializer.serialize_u64(
+                b.to_u64()
+                    .ok_or_else(|| S::Error::custom("file size is too big to serialize"))?,
+            ),
+            Value::Primitive(Primitive::Date(d)) => serializer.serialize_str(&d.to_string()),
+            Value::Primitive(Primitive::Duration(seconds)) => serializer.serialize_i64(*seconds),
+            Value::Primitive(Primitive::Decimal(f)) => serializer.serialize_f64(
+                f.to_f64()
+                    .ok_or_else(|| S::Error::custom("decimal is too big to serialize"))?,
+            ),
+            Value::Primitive(Primitive::Int(i)) => match i.to_i64() {
+                Some(i) => serializer.serialize_i64(i),
+                None => serializer.serialize_u64(
+                    i.to_u64()
+                        .ok_or_else(|| S::Error::custom("integer is too big to serialize"))?,
+                ),
+            },
+            Value::Primitive(Primitive::Nothing)
+            | Value::Primitive(Primitive::EndOfStream)
+            | Value::Primitive(Primitive::BeginningOfStream)
+            | Value::Block(_) => serializer.serialize_unit(),
+            Value::Primitive(Primitive::Pattern(s)) | Value::Primitive(Primitive::String(s)) => {
+                serializer.serialize_str(s)
+            }
+            Value::Primitive(Primitive::Path(p)) => {
+                serializer.serialize_str(&p.display().to_string())
+            }
+            Value::Primitive(Primitive::Binary(b)) => serializer.serialize_bytes(b),
+            Value::Table(l) => {
+                let mut seq = serializer.serialize_seq(Some(l.len()))?;
+                for value in l {
+                    seq.serialize_element(&Interchange(value))?;
+                }
+                seq.end()
+            }
+            Value::Row(o) => {
+                let mut map = serializer.serialize_map(Some(o.entries.len()))?;
+                for (k, v) in o.entries.iter() {
+                    map.serialize_entry(k, &Interchange(v))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Deserializes any value of a self-describing format straight into a `Value` carrying the
+/// given tag. Maps become rows, so their keys have to be text or numbers.
+#[derive(Clone, Copy)]
+pub(crate) struct InterchangeSeed(pub(crate) Tag);
+
+impl<'de> DeserializeSeed<'de> for InterchangeSeed {
+    type Value = Tagged<Value>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for InterchangeSeed {
+    type Value = Tagged<Value>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a value")
+    }
+
+    fn visit_bool<E: DeError>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Value::boolean(v).tagged(self.0))
+    }
+
+    fn visit_i64<E: DeError>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Value::number(v).tagged(self.0))
+    }
+
+    fn visit_u64<E: DeError>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Value::number(v).tagged(self.0))
+    }
+
+    fn visit_f64<E: DeError>(self, v: f64) -> Result<Self::Value, E> {
+        if v.is_finite() {
+            Ok(Value::number(v).tagged(self.0))
+        } else {
+            Ok(Value::nothing().tagged(self.0))
+        }
+    }
+
+    fn visit_str<E: DeError>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Value::string(v).tagged(self.0))
+    }
+
+    fn visit_bytes<E: DeError>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Value::binary(v.to_vec()).tagged(self.0))
+    }
+
+    fn visit_unit<E: DeError>(self) -> Result<Self::Value, E> {
+        Ok(Value::nothing().tagged(self.0))
+    }
+
+    fn visit_none<E: DeError>(self) -> Result<Self::Value, E> {
+        self.visit_unit()
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut values = vec![];
+
+        while let Some(value) = seq.next_element_seed(self)? {
+            values.push(value);
+        }
+
+        Ok(Value::Table(values).tagged(self.0))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut row = TaggedDictBuilder::new(self.0);
+
+        while let Some(key) = map.next_key_seed(self)? {
+            let key = key
+                .as_string()
+                .map_err(|_| A::Error::custom("map keys have to be text or numbers"))?;
+            row.insert_tagged(key, map.next_value_seed(self)?);
+        }
+
+        Ok(row.into_tagged_value())
+    }
+}
@@ -0,0 +1,29 @@
+use crate::prelude::*;
+
+/// How a serializer lays out its text: the format's usual layout, as compact as it can be, or
+/// with nesting indented by a number of spaces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Layout {
+    Default,
+    Compact,
+    Indented(usize),
+}
+
+impl Layout {
+    pub(crate) fn from_flags(
+        pretty: Option<Tagged<u64>>,
+        raw: bool,
+        name: Tag,
+    ) -> Result<Layout, ShellError> {
+        match (pretty, raw) {
+            (Some(_), true) => Err(ShellError::labeled_error(
+                "Can not be both pretty and raw",
+                "use only one of --pretty or --raw",
+                name,
+            )),
+            (Some(indent), false) => Ok(Layout::Indented(indent.item as usize)),
+            (None, true) => Ok(Layout::Compact),
+            (None, false) => Ok(Layout::Default),
+        }
+    }
+}
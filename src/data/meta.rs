@@ -96,8 +96,8 @@ impl<T> Tagged<T> {
 
     pub fn origin_name(&self, source_map: &SourceMap) -> Option<String> {
         match source_map.get(&self.tag.origin) {
-            Some(SpanSource::File(file)) => Some(file.clone()),
-            Some(SpanSource::Url(url)) => Some(url.clone()),
+            Some(SpanSource::File(file)) => Some(file),
+            Some(SpanSource::Url(url)) => Some(url),
             _ => None,
         }
     }
@@ -1,14 +1,16 @@
-use crate::data::base::Block;
+use crate::cli::run_invocation;
+use crate::data::base::{Block, ComputeError};
+use crate::data::TaggedDictBuilder;
 use crate::errors::ArgumentError;
 use crate::parser::{
     hir::{self, Expression, RawExpression},
-    CommandRegistry, Text,
+    CommandRegistry, Operator, Text,
 };
 use crate::prelude::*;
 use derive_new::new;
 use indexmap::IndexMap;
 
-#[derive(new)]
+#[derive(Clone, Debug, new)]
 pub struct Scope {
     it: Tagged<Value>,
     #[new(default)]
@@ -29,6 +31,40 @@ impl Scope {
             vars: IndexMap::new(),
         }
     }
+
+    pub(crate) fn with_var(mut self, name: impl Into<String>, value: Tagged<Value>) -> Scope {
+        self.vars.insert(name.into(), value);
+        self
+    }
+
+    pub(crate) fn with_default_var(
+        mut self,
+        name: impl Into<String>,
+        value: Tagged<Value>,
+    ) -> Scope {
+        self.vars.entry(name.into()).or_insert(value);
+        self
+    }
+
+    /// This scope nested in `outer`: the variables it doesn't bind, and `$it` when it has no row
+    /// of its own, come from `outer`.
+    pub(crate) fn inside(mut self, outer: &Scope) -> Scope {
+        if self.it.is_nothing() {
+            self.it = outer.it.clone();
+        }
+
+        for (name, value) in outer.vars.iter() {
+            self = self.with_default_var(name.clone(), value.clone());
+        }
+
+        self
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Scope {
+        Scope::empty()
+    }
 }
 
 pub(crate) fn evaluate_baseline_expr(
@@ -48,12 +84,40 @@ pub(crate) fn evaluate_baseline_expr(
         RawExpression::Synthetic(hir::Synthetic::String(s)) => {
             Ok(Value::string(s).tagged_unknown())
         }
-        RawExpression::Variable(var) => evaluate_reference(var, scope, source),
+        RawExpression::Variable(var) => evaluate_reference(var, registry, scope, source),
         RawExpression::ExternalCommand(external) => evaluate_external(external, scope, source),
         RawExpression::Binary(binary) => {
             let left = evaluate_baseline_expr(binary.left(), registry, scope, source)?;
             let right = evaluate_baseline_expr(binary.right(), registry, scope, source)?;
 
+            if binary.op().is_arithmetic() {
+                match binary.op().item {
+                    Operator::Divide | Operator::Modulo if right.is_zero() => {
+                        return Err(ShellError::labeled_error(
+                            "Division by zero",
+                            "division by zero",
+                            binary.op().tag(),
+                        ))
+                    }
+                    _ => {}
+                }
+
+                return match left.compute(binary.op(), &*right) {
+                    Ok(value) => Ok(value.tagged(expr.tag())),
+                    Err(ComputeError::Coerce(left_type, right_type)) => {
+                        Err(ShellError::coerce_error(
+                            binary.left().copy_tag(left_type),
+                            binary.right().copy_tag(right_type),
+                        ))
+                    }
+                    Err(ComputeError::OutOfRange(kind)) => Err(ShellError::labeled_error(
+                        "Out of range",
+                        format!("{} out of range", kind),
+                        binary.op().tag(),
+                    )),
+                };
+            }
+
             match left.compare(binary.op(), &*right) {
                 Ok(result) => Ok(Value::boolean(result).tagged(expr.tag())),
                 Err((left_type, right_type)) => Err(ShellError::coerce_error(
@@ -72,17 +136,33 @@ pub(crate) fn evaluate_baseline_expr(
 
             Ok(Value::Table(exprs).tagged(expr.tag()))
         }
-        RawExpression::Block(block) => {
-            Ok(
-                Value::Block(Block::new(block.clone(), source.clone(), expr.tag()))
-                    .tagged(expr.tag()),
+        RawExpression::Block(block, pipeline) => Ok(Value::Block(
+            Block::new(
+                block.clone(),
+                source.clone(),
+                expr.tag(),
+                pipeline.as_ref().map(|pipeline| (**pipeline).clone()),
             )
-        }
+            .with_vars(registry.variables()),
+        )
+        .tagged(expr.tag())),
+        RawExpression::Invocation(pipeline) => run_invocation(pipeline, registry, scope, source),
         RawExpression::Path(path) => {
             let value = evaluate_baseline_expr(path.head(), registry, scope, source)?;
+
+            if path.tail().iter().any(|name| name.item == "*") {
+                let names: Vec<&str> = path.tail().iter().map(|name| name.item.as_str()).collect();
+                return value.get_data_by_column_path(expr.tag(), &names);
+            }
+
             let mut item = value;
 
             for name in path.tail() {
+                // Nothing has no columns to look into, so what's in them is nothing too
+                if item.is_nothing() {
+                    break;
+                }
+
                 let next = item.get_data_by_key(name);
 
                 match next {
@@ -128,19 +208,36 @@ fn evaluate_literal(literal: Tagged<&hir::Literal>, source: &Text) -> Tagged<Val
 
 fn evaluate_reference(
     name: &hir::Variable,
+    registry: &CommandRegistry,
     scope: &Scope,
     source: &Text,
 ) -> Result<Tagged<Value>, ShellError> {
     match name {
         hir::Variable::It(tag) => Ok(scope.it.item.clone().tagged(*tag)),
-        hir::Variable::Other(tag) => Ok(scope
-            .vars
-            .get(tag.slice(source))
-            .map(|v| v.clone())
-            .unwrap_or_else(|| Value::nothing().tagged(*tag))),
+        hir::Variable::Other(tag) => match scope.vars.get(tag.slice(source)) {
+            Some(value) => Ok(value.clone()),
+            None => match registry.get_variable(tag.slice(source)) {
+                Some(value) => Ok(value.item.tagged(*tag)),
+                None if tag.slice(source) == "nu" => Ok(nu_variable(*tag)),
+                None => Ok(Value::nothing().tagged(*tag)),
+            },
+        },
     }
 }
 
+/// The built-in `$nu` variable, giving access to the process environment as `$nu.env`.
+fn nu_variable(tag: Tag) -> Tagged<Value> {
+    let mut env = TaggedDictBuilder::new(tag);
+    for (key, value) in std::env::vars() {
+        env.insert(key, Value::string(value));
+    }
+
+    let mut nu = TaggedDictBuilder::new(tag);
+    nu.insert_tagged("env", env.into_tagged_value());
+
+    nu.into_tagged_value()
+}
+
 fn evaluate_external(
     external: &hir::ExternalCommand,
     _scope: &Scope,
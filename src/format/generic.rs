@@ -1,3 +1,4 @@
+use crate::commands::format_filesize::FilesizeFormat;
 use crate::data::Value;
 use crate::format::{EntriesView, RenderView, TableView};
 use crate::prelude::*;
@@ -14,7 +15,8 @@ impl RenderView for GenericView<'_> {
         match self.value {
             Value::Primitive(p) => Ok(host.stdout(&p.format(None))),
             Value::Table(l) => {
-                let view = TableView::from_list(l);
+                let config = crate::data::config::config(Tag::unknown())?;
+                let view = TableView::from_list(l, FilesizeFormat::from_config(&config));
 
                 if let Some(view) = view {
                     view.render_view(host)?;
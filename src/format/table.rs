@@ -1,7 +1,9 @@
+use crate::commands::format_filesize::FilesizeFormat;
 use crate::data::Value;
 use crate::format::RenderView;
 use crate::prelude::*;
 use derive_new::new;
+use indexmap::IndexMap;
 use textwrap::fill;
 
 use prettytable::format::{FormatBuilder, LinePosition, LineSeparator};
@@ -14,13 +16,140 @@ pub struct TableView {
 
     // List of rows of cells, each containing value and prettytable style-string:
     entries: Vec<Vec<(String, &'static str)>>,
+
+    // Type name of each cell, used to look up per-type colors from the theme:
+    types: Vec<Vec<String>>,
+
+    // Theme to render with. When missing, the theme is read from `table_mode`:
+    theme: Option<TableTheme>,
 }
 
-enum TableMode {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TableMode {
     Light,
     Normal,
 }
 
+impl TableMode {
+    fn from_name(name: &str) -> Option<TableMode> {
+        match name {
+            "light" => Some(TableMode::Light),
+            "normal" => Some(TableMode::Normal),
+            _ => None,
+        }
+    }
+}
+
+/// Concrete style settings for rendering a table: the border style, the color of
+/// the header row, and the foreground color used for each value type.
+#[derive(Debug, Clone)]
+pub struct TableTheme {
+    pub(crate) mode: TableMode,
+    pub(crate) header_color: color::Color,
+    pub(crate) type_colors: IndexMap<String, &'static str>,
+}
+
+impl Default for TableTheme {
+    fn default() -> TableTheme {
+        TableTheme {
+            mode: TableMode::Normal,
+            header_color: color::GREEN,
+            type_colors: IndexMap::new(),
+        }
+    }
+}
+
+fn color_from_name(name: &str) -> Option<(color::Color, &'static str)> {
+    match name {
+        "black" => Some((color::BLACK, "Fd")),
+        "red" => Some((color::RED, "Fr")),
+        "green" => Some((color::GREEN, "Fg")),
+        "yellow" => Some((color::YELLOW, "Fy")),
+        "blue" => Some((color::BLUE, "Fb")),
+        "magenta" => Some((color::MAGENTA, "Fm")),
+        "cyan" => Some((color::CYAN, "Fc")),
+        "white" => Some((color::WHITE, "Fw")),
+        _ => None,
+    }
+}
+
+impl TableTheme {
+    /// The theme used when no name is given, following the `table_mode` setting.
+    pub(crate) fn from_config() -> Result<TableTheme, ShellError> {
+        let mode = crate::data::config::config(Tag::unknown())?
+            .get("table_mode")
+            .map(|s| match s.as_string().unwrap().as_ref() {
+                "light" => TableMode::Light,
+                _ => TableMode::Normal,
+            })
+            .unwrap_or(TableMode::Normal);
+
+        Ok(TableTheme {
+            mode,
+            ..TableTheme::default()
+        })
+    }
+
+    pub(crate) fn from_registry(
+        themes: Option<&Tagged<Value>>,
+        name: &Tagged<String>,
+    ) -> Result<TableTheme, ShellError> {
+        let theme = match themes.and_then(|themes| themes.get_data_by_key(&name.item)) {
+            Some(theme) => theme,
+            None => {
+                return Err(ShellError::labeled_error(
+                    "Unknown table theme",
+                    format!("no theme named {} in table_themes", name.item),
+                    name.tag(),
+                ))
+            }
+        };
+
+        let mut resolved = TableTheme::default();
+
+        if let Some(border) = theme.get_data_by_key("border") {
+            let border = border.as_string()?;
+            resolved.mode = TableMode::from_name(&border).ok_or_else(|| {
+                ShellError::labeled_error(
+                    "Invalid table theme",
+                    format!("border must be light or normal, found {}", border),
+                    name.tag(),
+                )
+            })?;
+        }
+
+        if let Some(header) = theme.get_data_by_key("header") {
+            let header = header.as_string()?;
+            resolved.header_color = color_from_name(&header)
+                .map(|(color, _)| color)
+                .ok_or_else(|| unknown_color(&header, name.tag()))?;
+        }
+
+        if let Some(Tagged {
+            item: Value::Row(colors),
+            ..
+        }) = theme.get_data_by_key("colors")
+        {
+            for (type_name, value) in colors.entries.iter() {
+                let value = value.as_string()?;
+                let (_, spec) =
+                    color_from_name(&value).ok_or_else(|| unknown_color(&value, name.tag()))?;
+                resolved.type_colors.insert(type_name.clone(), spec);
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+fn unknown_color(color: &str, tag: Tag) -> ShellError {
+    ShellError::labeled_error(
+        "Invalid table theme",
+        format!("unknown color {}", color),
+        tag,
+    )
+}
+
 impl TableView {
     fn merge_descriptors(values: &[Tagged<Value>]) -> Vec<String> {
         let mut ret = vec![];
@@ -34,7 +163,15 @@ impl TableView {
         ret
     }
 
-    pub fn from_list(values: &[Tagged<Value>]) -> Option<TableView> {
+    /// Write a cell the way it's shown in the table, with file sizes following `filesize_format`.
+    fn format_cell(value: &Value, header: Option<&String>, filesize: FilesizeFormat) -> String {
+        match value {
+            Value::Primitive(Primitive::Bytes(bytes)) => filesize.format(*bytes),
+            _ => value.format_leaf(header),
+        }
+    }
+
+    pub fn from_list(values: &[Tagged<Value>], filesize: FilesizeFormat) -> Option<TableView> {
         if values.len() == 0 {
             return None;
         }
@@ -46,6 +183,7 @@ impl TableView {
         }
 
         let mut entries = vec![];
+        let mut types = vec![];
 
         for (idx, value) in values.iter().enumerate() {
             let mut row: Vec<(String, &'static str)> = match value {
@@ -58,20 +196,33 @@ impl TableView {
                     .map(|(i, d)| {
                         let data = value.get_data(d);
                         return (
-                            data.borrow().format_leaf(Some(&headers[i])),
+                            TableView::format_cell(data.borrow(), Some(&headers[i]), filesize),
                             data.borrow().style_leaf(),
                         );
                     })
                     .collect(),
-                x => vec![(x.format_leaf(None), x.style_leaf())],
+                x => vec![(TableView::format_cell(x, None, filesize), x.style_leaf())],
+            };
+
+            let mut row_types: Vec<String> = match value {
+                Tagged {
+                    item: Value::Row(..),
+                    ..
+                } => headers
+                    .iter()
+                    .map(|d| value.get_data(d).borrow().type_name())
+                    .collect(),
+                x => vec![x.type_name()],
             };
 
             if values.len() > 1 {
                 // Indices are black, bold, right-aligned:
                 row.insert(0, (format!("{}", idx.to_string()), "Fdbr"));
+                row_types.insert(0, String::new());
             }
 
             entries.push(row);
+            types.push(row_types);
         }
 
         let mut max_per_column = vec![];
@@ -192,7 +343,19 @@ impl TableView {
             }
         }
 
-        Some(TableView { headers, entries })
+        Some(TableView {
+            headers,
+            entries,
+            types,
+            theme: None,
+        })
+    }
+
+    pub(crate) fn with_theme(self, theme: TableTheme) -> TableView {
+        TableView {
+            theme: Some(theme),
+            ..self
+        }
     }
 }
 
@@ -204,15 +367,12 @@ impl RenderView for TableView {
 
         let mut table = Table::new();
 
-        let table_mode = crate::data::config::config(Tag::unknown())?
-            .get("table_mode")
-            .map(|s| match s.as_string().unwrap().as_ref() {
-                "light" => TableMode::Light,
-                _ => TableMode::Normal,
-            })
-            .unwrap_or(TableMode::Normal);
+        let theme = match &self.theme {
+            Some(theme) => theme.clone(),
+            None => TableTheme::from_config()?,
+        };
 
-        match table_mode {
+        match theme.mode {
             TableMode::Light => {
                 table.set_format(
                     FormatBuilder::new()
@@ -239,17 +399,21 @@ impl RenderView for TableView {
             .iter()
             .map(|h| {
                 Cell::new(h)
-                    .with_style(Attr::ForegroundColor(color::GREEN))
+                    .with_style(Attr::ForegroundColor(theme.header_color))
                     .with_style(Attr::Bold)
             })
             .collect();
 
         table.set_titles(Row::new(header));
 
-        for row in &self.entries {
+        for (row, types) in self.entries.iter().zip(self.types.iter()) {
             table.add_row(Row::new(
                 row.iter()
-                    .map(|(v, s)| Cell::new(v).style_spec(s))
+                    .zip(types.iter())
+                    .map(|((v, s), t)| match theme.type_colors.get(t) {
+                        Some(color) => Cell::new(v).style_spec(&format!("{}{}", s, color)),
+                        None => Cell::new(v).style_spec(s),
+                    })
                     .collect(),
             ));
         }
@@ -259,3 +423,49 @@ impl RenderView for TableView {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{TableMode, TableTheme, TableView};
+    use crate::commands::format_filesize::FilesizeFormat;
+    use crate::data::{TaggedDictBuilder, Value};
+    use crate::prelude::*;
+
+    fn registry() -> Tagged<Value> {
+        let mut mytheme = TaggedDictBuilder::new(Tag::unknown());
+        mytheme.insert("border", Value::string("light"));
+        mytheme.insert("header", Value::string("blue"));
+
+        let mut themes = TaggedDictBuilder::new(Tag::unknown());
+        themes.insert_tagged("mytheme", mytheme);
+        themes.into_tagged_value()
+    }
+
+    #[test]
+    fn named_theme_applies_its_border_style() {
+        let theme =
+            TableTheme::from_registry(Some(&registry()), &"mytheme".to_string().tagged_unknown())
+                .unwrap();
+
+        assert_eq!(theme.mode, TableMode::Light);
+    }
+
+    #[test]
+    fn unknown_theme_name_is_an_error() {
+        assert!(TableTheme::from_registry(
+            Some(&registry()),
+            &"nosuchtheme".to_string().tagged_unknown(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn file_sizes_follow_the_chosen_format() {
+        let size = Value::bytes(1_572_864u64);
+        let cell = |format| TableView::format_cell(&size, None, format);
+
+        assert_eq!(cell(FilesizeFormat::Binary), "1.5 MiB");
+        assert_eq!(cell(FilesizeFormat::Decimal), "1.6 MB");
+        assert_eq!(cell(FilesizeFormat::Bytes), "1572864 B");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::prelude::*;
+use indexmap::IndexMap;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::thread::JoinHandle;
+
+/// A pipeline running in the background, with everything it prints captured so it
+/// can be retrieved once the job is brought back with `fg`.
+pub(crate) struct Job {
+    pub(crate) command: String,
+    child: Child,
+    output: Arc<Mutex<Vec<u8>>>,
+    readers: Vec<JoinHandle<()>>,
+    killed: bool,
+}
+
+impl Job {
+    pub(crate) fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    pub(crate) fn status(&mut self) -> String {
+        if self.killed {
+            return "killed".to_string();
+        }
+
+        match self.child.try_wait() {
+            Ok(None) => "running".to_string(),
+            Ok(Some(status)) => match status.code() {
+                Some(code) => format!("done ({})", code),
+                None => "done".to_string(),
+            },
+            Err(_) => "unknown".to_string(),
+        }
+    }
+
+    pub(crate) fn output(&self) -> String {
+        String::from_utf8_lossy(&self.output.lock().unwrap()).to_string()
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct JobManager {
+    jobs: Arc<Mutex<IndexMap<usize, Job>>>,
+    next_id: Arc<Mutex<usize>>,
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut process = Command::new("cmd");
+        process.args(&["/C", command]);
+        process
+    } else {
+        let mut process = Command::new("sh");
+        process.args(&["-c", command]);
+        process
+    }
+}
+
+fn capture(mut source: impl Read + Send + 'static, output: Arc<Mutex<Vec<u8>>>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut buffer = [0; 4096];
+
+        while let Ok(read) = source.read(&mut buffer) {
+            if read == 0 {
+                break;
+            }
+            output.lock().unwrap().extend_from_slice(&buffer[..read]);
+        }
+    })
+}
+
+impl JobManager {
+    /// Run an external pipeline through the system shell in the background, returning its job id.
+    pub(crate) fn spawn(&self, command: &str, cwd: &str) -> Result<usize, ShellError> {
+        let mut child = shell_command(command)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let output = Arc::new(Mutex::new(vec![]));
+        let mut readers = vec![];
+
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(capture(stdout, output.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(capture(stderr, output.clone()));
+        }
+
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            *next_id
+        };
+
+        self.jobs.lock().unwrap().insert(
+            id,
+            Job {
+                command: command.to_string(),
+                child,
+                output,
+                readers,
+                killed: false,
+            },
+        );
+
+        Ok(id)
+    }
+
+    /// Call `block` with every job, in the order they were started.
+    pub(crate) fn each<T>(&self, mut block: impl FnMut(usize, &mut Job) -> T) -> Vec<T> {
+        let mut jobs = self.jobs.lock().unwrap();
+
+        jobs.iter_mut().map(|(id, job)| block(*id, job)).collect()
+    }
+
+    /// Wait for a job to finish, then remove it and hand back what it printed.
+    pub(crate) fn wait(&self, id: usize) -> Option<String> {
+        let mut job = self.jobs.lock().unwrap().shift_remove(&id)?;
+
+        let _ = job.child.wait();
+        for reader in job.readers.drain(..) {
+            let _ = reader.join();
+        }
+
+        Some(job.output())
+    }
+
+    pub(crate) fn kill(&self, id: usize) -> Option<Result<(), ShellError>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs.get_mut(&id)?;
+
+        job.killed = true;
+        Some(job.child.kill().map_err(ShellError::from))
+    }
+}
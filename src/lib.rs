@@ -14,21 +14,24 @@ mod evaluate;
 mod format;
 mod fuzzysearch;
 mod git;
+mod jobs;
 mod parser;
 mod plugin;
+mod prompt;
 mod shell;
 mod stream;
 mod traits;
 mod utils;
 
 pub use crate::commands::command::{CallInfo, ReturnSuccess, ReturnValue};
+pub use crate::commands::first::percentage;
 pub use crate::context::{SourceMap, SpanSource};
 pub use crate::env::host::BasicHost;
 pub use crate::parser::hir::SyntaxShape;
 pub use crate::parser::parse::token_tree_builder::TokenTreeBuilder;
-pub use crate::plugin::{serve_plugin, Plugin};
+pub use crate::plugin::{config_value, serve_plugin, Plugin};
 pub use crate::utils::{AbsoluteFile, AbsolutePath, RelativePath};
-pub use cli::cli;
+pub use cli::{cli, run_script};
 pub use data::base::{Primitive, Value};
 pub use data::config::{config_path, APP_INFO};
 pub use data::dict::{Dictionary, TaggedDictBuilder};
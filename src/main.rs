@@ -19,6 +19,32 @@ fn main() -> Result<(), Box<dyn Error>> {
                 .multiple(true)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("color")
+                .long("color")
+                .help("Always color output, regardless of config or NO_COLOR")
+                .conflicts_with("no-color"),
+        )
+        .arg(
+            Arg::with_name("no-color")
+                .long("no-color")
+                .help("Never color output, regardless of config or NO_COLOR"),
+        )
+        .arg(
+            Arg::with_name("commands")
+                .short("c")
+                .long("commands")
+                .value_name("COMMANDS")
+                .help("Run the given pipeline and exit")
+                .takes_value(true)
+                .conflicts_with("script"),
+        )
+        .arg(
+            Arg::with_name("script")
+                .value_name("SCRIPT")
+                .help("Run each line of the script file and exit")
+                .index(1),
+        )
         .get_matches();
 
     let loglevel = match matches.value_of("loglevel") {
@@ -50,6 +76,33 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     builder.try_init()?;
 
-    futures::executor::block_on(nu::cli())?;
+    let color = if matches.is_present("color") {
+        Some(true)
+    } else if matches.is_present("no-color") {
+        Some(false)
+    } else {
+        None
+    };
+
+    let script = match (matches.value_of("commands"), matches.value_of("script")) {
+        (Some(commands), _) => Some(vec![commands.to_string()]),
+        (None, Some(path)) => Some(
+            std::fs::read_to_string(path)?
+                .lines()
+                .map(|line| line.to_string())
+                .collect(),
+        ),
+        (None, None) => None,
+    };
+
+    match script {
+        Some(lines) => {
+            if !futures::executor::block_on(nu::run_script(lines, color))? {
+                std::process::exit(1);
+            }
+        }
+        None => futures::executor::block_on(nu::cli(color))?,
+    }
+
     Ok(())
 }
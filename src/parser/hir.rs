@@ -5,7 +5,7 @@ pub(crate) mod external_command;
 pub(crate) mod named;
 pub(crate) mod path;
 
-use crate::parser::{registry, Unit};
+use crate::parser::{registry, Pipeline, Unit};
 use crate::prelude::*;
 use derive_new::new;
 use getset::Getters;
@@ -80,14 +80,17 @@ impl ToDebug for Call {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum RawExpression {
     Literal(Literal),
     ExternalWord,
     Synthetic(Synthetic),
     Variable(Variable),
     Binary(Box<Binary>),
-    Block(Vec<Expression>),
+    /// The expressions of a block, along with the pipeline it was written as
+    Block(Vec<Expression>, Option<Box<Tagged<Pipeline>>>),
+    /// A command run for its output, like `(date now)`
+    Invocation(Box<Tagged<Pipeline>>),
     List(Vec<Expression>),
     Path(Box<Path>),
 
@@ -121,6 +124,7 @@ impl RawExpression {
             RawExpression::List(..) => "list",
             RawExpression::Binary(..) => "binary",
             RawExpression::Block(..) => "block",
+            RawExpression::Invocation(..) => "command",
             RawExpression::Path(..) => "path",
             RawExpression::Boolean(..) => "boolean",
             RawExpression::ExternalCommand(..) => "external",
@@ -187,7 +191,7 @@ impl ToDebug for Expression {
             RawExpression::Variable(Variable::Other(s)) => write!(f, "${}", s.slice(source)),
             RawExpression::Binary(b) => write!(f, "{}", b.debug(source)),
             RawExpression::ExternalCommand(c) => write!(f, "^{}", c.name().slice(source)),
-            RawExpression::Block(exprs) => {
+            RawExpression::Block(exprs, _) => {
                 write!(f, "{{ ")?;
 
                 for expr in exprs {
@@ -196,6 +200,7 @@ impl ToDebug for Expression {
 
                 write!(f, "}}")
             }
+            RawExpression::Invocation(pipeline) => write!(f, "({})", pipeline.tag().slice(source)),
             RawExpression::List(exprs) => {
                 write!(f, "[ ")?;
 
@@ -6,7 +6,8 @@ use crate::parser::{
         baseline_parse_single_token, baseline_parse_token_as_number, baseline_parse_token_as_path,
         baseline_parse_token_as_pattern, baseline_parse_token_as_string,
     },
-    DelimitedNode, Delimiter, PathNode, RawToken, TokenNode,
+    CallNode, DelimitedNode, Delimiter, Operator, PathNode, Pipeline, PipelineElement, RawToken,
+    TokenNode,
 };
 use crate::{Tag, Tagged, TaggedItem, Text};
 use derive_new::new;
@@ -42,6 +43,7 @@ pub enum SyntaxShape {
     Member,
     Variable,
     Number,
+    Int,
     Path,
     Pattern,
     Binary,
@@ -59,6 +61,7 @@ impl std::fmt::Display for SyntaxShape {
             SyntaxShape::Member => write!(f, "Member"),
             SyntaxShape::Variable => write!(f, "Variable"),
             SyntaxShape::Number => write!(f, "Number"),
+            SyntaxShape::Int => write!(f, "Int"),
             SyntaxShape::Path => write!(f, "Path"),
             SyntaxShape::Pattern => write!(f, "Pattern"),
             SyntaxShape::Binary => write!(f, "Binary"),
@@ -114,11 +117,12 @@ pub fn baseline_parse_next_expr(
             ))
         }
 
-        (SyntaxShape::Number, TokenNode::Token(token)) => {
+        (SyntaxShape::Number, TokenNode::Token(token))
+        | (SyntaxShape::Int, TokenNode::Token(token)) => {
             return Ok(baseline_parse_token_as_number(token, source)?);
         }
 
-        (SyntaxShape::Number, token) => {
+        (SyntaxShape::Number, token) | (SyntaxShape::Int, token) => {
             return Err(ShellError::type_error(
                 "Numeric",
                 token.type_name().tagged(token.tag()),
@@ -127,6 +131,11 @@ pub fn baseline_parse_next_expr(
 
         // TODO: More legit member processing
         (SyntaxShape::Member, TokenNode::Token(token)) => {
+            // Members may hold `*` wildcards, which lex as glob patterns
+            if let RawToken::GlobPattern = token.item {
+                return Ok(hir::Expression::bare(token.tag()));
+            }
+
             return baseline_parse_token_as_string(token, source);
         }
 
@@ -148,41 +157,63 @@ pub fn baseline_parse_next_expr(
 
     let first = baseline_parse_semantic_token(next, context, source)?;
 
-    let possible_op = tokens.peek();
+    let mut operands = vec![];
+    let mut ops = vec![];
+    let mut last = next;
 
-    let op = match possible_op {
-        Some(TokenNode::Operator(op)) => op.clone(),
-        _ => return Ok(first),
-    };
+    while let Some(op) = tokens.peek().and_then(|token| as_operator(token, source)) {
+        // Outside of a block, a word like `-` is only an operator between two things that
+        // can be added up, so `echo a - b` echoes three words
+        let needs_operands = match (syntax_type, tokens.peek()) {
+            (SyntaxShape::Block, _) => false,
+            (_, Some(TokenNode::Token(_))) => true,
+            _ => false,
+        };
 
-    tokens.next();
+        if needs_operands {
+            let mut lookahead = tokens.clone();
+            lookahead.next();
 
-    let second = match tokens.next() {
-        None => {
-            return Err(ShellError::labeled_error(
-                "Expected something after an operator",
-                "operator",
-                op.tag(),
-            ))
+            let next_is_operand = lookahead.next().map(is_arithmetic_operand).unwrap_or(false);
+
+            if !is_arithmetic_operand(last) || !next_is_operand {
+                break;
+            }
         }
-        Some(token) => baseline_parse_semantic_token(token, context, source)?,
-    };
+
+        tokens.next();
+
+        let operand = match tokens.next() {
+            None => {
+                return Err(ShellError::labeled_error(
+                    "Expected something after an operator",
+                    "operator",
+                    op.tag(),
+                ))
+            }
+            Some(token) => {
+                last = token;
+                baseline_parse_semantic_token(token, context, source)?
+            }
+        };
+
+        ops.push(op);
+        operands.push(operand);
+    }
+
+    if ops.is_empty() {
+        return Ok(first);
+    }
 
     // We definitely have a binary expression here -- let's see if we should coerce it into a block
 
     match syntax_type {
         SyntaxShape::Any => {
-            let tag = first.tag().until(second.tag());
-            let binary = hir::Binary::new(first, op, second);
-            let binary = hir::RawExpression::Binary(Box::new(binary));
-            let binary = binary.tagged(tag);
-
-            Ok(binary)
+            operands.insert(0, first);
+            Ok(binary_tree(operands, ops))
         }
 
         SyntaxShape::Block => {
-            let tag = first.tag().until(second.tag());
-
             let path: Tagged<hir::RawExpression> = match first {
                 Tagged {
                     item: hir::RawExpression::Literal(hir::Literal::Bare),
@@ -215,6 +246,10 @@ pub fn baseline_parse_next_expr(
                 Tagged {
                     item: hir::RawExpression::Variable(..),
                     ..
+                }
+                | Tagged {
+                    item: hir::RawExpression::Path(..),
+                    ..
                 } => first,
                 Tagged { tag, item } => {
                     return Err(ShellError::labeled_error(
@@ -225,11 +260,11 @@ pub fn baseline_parse_next_expr(
                 }
             };
 
-            let binary = hir::Binary::new(path, op, second);
-            let binary = hir::RawExpression::Binary(Box::new(binary));
-            let binary = binary.tagged(tag);
+            operands.insert(0, path);
+            let binary = binary_tree(operands, ops);
+            let tag = binary.tag();
 
-            let block = hir::RawExpression::Block(vec![binary]);
+            let block = hir::RawExpression::Block(vec![binary], None);
             let block = block.tagged(tag);
 
             Ok(block)
@@ -242,6 +277,63 @@ pub fn baseline_parse_next_expr(
     }
 }
 
+/// Operators like `+` and `mod` also lex as words (`-` and `/` are paths, `*` a glob), so
+/// they are only read as operators between two operands.
+fn as_operator(token: &TokenNode, source: &Text) -> Option<Tagged<Operator>> {
+    match token {
+        TokenNode::Operator(op) => Some(op.clone()),
+        TokenNode::Token(token) => match token.tag().slice(source).parse::<Operator>() {
+            Ok(op) if op.is_arithmetic() => Some(op.tagged(token.tag())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Numbers, sizes, variables, paths into them and parenthesized expressions can be operands
+/// of arithmetic. Bare words,
+/// like the `notes.txt` of `cp notes.txt /`, can't.
+fn is_arithmetic_operand(token: &TokenNode) -> bool {
+    match token {
+        TokenNode::Token(token) => match token.item {
+            RawToken::Number(_) | RawToken::Size(..) | RawToken::Variable(_) => true,
+            _ => false,
+        },
+        TokenNode::Path(_) => true,
+        TokenNode::Delimited(delimited) => delimited.delimiter() == &Delimiter::Paren,
+        _ => false,
+    }
+}
+
+/// Combine `operands[0] ops[0] operands[1] ...` into a tree, with the loosest binding
+/// operator at the root. Operators of the same precedence associate to the left.
+fn binary_tree(
+    mut operands: Vec<hir::Expression>,
+    mut ops: Vec<Tagged<Operator>>,
+) -> hir::Expression {
+    if ops.is_empty() {
+        return operands.remove(0);
+    }
+
+    let (split, _) = ops
+        .iter()
+        .enumerate()
+        .rev()
+        .min_by_key(|(_, op)| op.precedence())
+        .unwrap();
+
+    let right_operands = operands.split_off(split + 1);
+    let right_ops = ops.split_off(split + 1);
+    let op = ops.pop().unwrap();
+
+    let left = binary_tree(operands, ops);
+    let right = binary_tree(right_operands, right_ops);
+
+    let tag = left.tag().until(right.tag());
+    let binary = hir::Binary::new(left, op, right);
+    hir::RawExpression::Binary(Box::new(binary)).tagged(tag)
+}
+
 pub fn baseline_parse_semantic_token(
     token: &TokenNode,
     context: &Context,
@@ -275,17 +367,55 @@ pub fn baseline_parse_delimited(
     match token.delimiter() {
         Delimiter::Brace => {
             let children = token.children();
-            let exprs = baseline_parse_tokens(
+
+            // A pipeline can only be run, not evaluated as an expression
+            let exprs = match children.first() {
+                Some(TokenNode::Pipeline(_)) => vec![],
+                _ => baseline_parse_tokens(
+                    &mut TokensIterator::new(children),
+                    context,
+                    source,
+                    SyntaxShape::Any,
+                )?,
+            };
+
+            let expr = hir::RawExpression::Block(exprs, block_pipeline(children).map(Box::new));
+            Ok(expr.tagged(token.tag()))
+        }
+        Delimiter::Paren => {
+            let children = trim_whitespace(token.children());
+
+            // A command in parentheses, like `(date now)`, is run for its output
+            let is_command = match children.first() {
+                Some(TokenNode::Token(Tagged {
+                    item: RawToken::Bare,
+                    tag,
+                })) => context.has_command(tag.slice(source)),
+                _ => false,
+            };
+
+            if is_command {
+                let pipeline = block_pipeline(children).unwrap();
+                let expr = hir::RawExpression::Invocation(Box::new(pipeline));
+                return Ok(expr.tagged(token.tag()));
+            }
+
+            let mut exprs = baseline_parse_tokens(
                 &mut TokensIterator::new(children),
                 context,
                 source,
                 SyntaxShape::Any,
             )?;
 
-            let expr = hir::RawExpression::Block(exprs);
-            Ok(expr.tagged(token.tag()))
+            match exprs.len() {
+                1 => Ok(exprs.remove(0)),
+                _ => Err(ShellError::labeled_error(
+                    "Expected a single expression or command in parentheses",
+                    "parenthesized expression",
+                    token.tag(),
+                )),
+            }
         }
-        Delimiter::Paren => unimplemented!(),
         Delimiter::Square => {
             let children = token.children();
             let exprs = baseline_parse_tokens(
@@ -301,6 +431,40 @@ pub fn baseline_parse_delimited(
     }
 }
 
+fn trim_whitespace(children: &[TokenNode]) -> &[TokenNode] {
+    let is_whitespace = |child: &TokenNode| match child {
+        TokenNode::Whitespace(_) => true,
+        _ => false,
+    };
+
+    let start = children
+        .iter()
+        .position(|child| !is_whitespace(child))
+        .unwrap_or_else(|| children.len());
+    let end = children
+        .iter()
+        .rposition(|child| !is_whitespace(child))
+        .map(|end| end + 1)
+        .unwrap_or(start);
+
+    &children[start..end]
+}
+
+/// The pipeline a block was written as. A block without a `|` in it is a pipeline of one call.
+fn block_pipeline(children: &[TokenNode]) -> Option<Tagged<Pipeline>> {
+    match children.split_first() {
+        None => None,
+        Some((TokenNode::Pipeline(pipeline), _)) => Some(pipeline.clone()),
+        Some((head, tail)) => {
+            let tag = head.tag().until(children[children.len() - 1].tag());
+            let call = CallNode::new(Box::new(head.clone()), tail.to_vec()).tagged(tag);
+            let element = PipelineElement::new(None, None, call, None);
+
+            Some(Pipeline::new(vec![element], None).tagged(tag))
+        }
+    }
+}
+
 pub fn baseline_parse_path(
     token: &Tagged<PathNode>,
     context: &Context,
@@ -6,9 +6,7 @@ use getset::Getters;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(
-    Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Getters, Serialize, Deserialize, new,
-)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, Serialize, Deserialize, new)]
 #[get = "pub(crate)"]
 pub struct Path {
     head: Expression,
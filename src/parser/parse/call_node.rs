@@ -1,9 +1,10 @@
 use crate::parser::TokenNode;
 use crate::traits::ToDebug;
 use getset::Getters;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, Serialize, Deserialize)]
 pub struct CallNode {
     #[get = "pub(crate)"]
     head: Box<TokenNode>,
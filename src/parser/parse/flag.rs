@@ -9,7 +9,9 @@ pub enum FlagKind {
     Longhand,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Getters, new)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Getters, Serialize, Deserialize, new,
+)]
 #[get = "pub(crate)"]
 pub struct Flag {
     kind: FlagKind,
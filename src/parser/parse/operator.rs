@@ -11,6 +11,11 @@ pub enum Operator {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
 }
 
 impl ToDebug for Operator {
@@ -32,6 +37,32 @@ impl Operator {
             Operator::GreaterThan => ">",
             Operator::LessThanOrEqual => "<=",
             Operator::GreaterThanOrEqual => ">=",
+            Operator::Plus => "+",
+            Operator::Minus => "-",
+            Operator::Multiply => "*",
+            Operator::Divide => "/",
+            Operator::Modulo => "mod",
+        }
+    }
+
+    pub fn is_arithmetic(&self) -> bool {
+        match *self {
+            Operator::Plus
+            | Operator::Minus
+            | Operator::Multiply
+            | Operator::Divide
+            | Operator::Modulo => true,
+            _ => false,
+        }
+    }
+
+    /// How tightly the operator binds. Comparisons bind loosest, so that
+    /// `size > 1mb + 512kb` compares against the sum.
+    pub fn precedence(&self) -> u8 {
+        match *self {
+            Operator::Multiply | Operator::Divide | Operator::Modulo => 3,
+            Operator::Plus | Operator::Minus => 2,
+            _ => 1,
         }
     }
 }
@@ -52,6 +83,11 @@ impl FromStr for Operator {
             ">" => Ok(Operator::GreaterThan),
             "<=" => Ok(Operator::LessThanOrEqual),
             ">=" => Ok(Operator::GreaterThanOrEqual),
+            "+" => Ok(Operator::Plus),
+            "-" => Ok(Operator::Minus),
+            "*" => Ok(Operator::Multiply),
+            "/" => Ok(Operator::Divide),
+            "mod" => Ok(Operator::Modulo),
             _ => Err(()),
         }
     }
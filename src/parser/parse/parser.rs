@@ -154,6 +154,12 @@ pub fn raw_number(input: NomSpan) -> IResult<NomSpan, Tagged<RawNumber>> {
     trace_step(input, "raw_decimal", move |input| {
         let (input, neg) = opt(tag("-"))(input)?;
         let (input, head) = digit1(input)?;
+
+        // `..` starts a range (eg. `5..10`) rather than a decimal
+        if input.fragment.starts_with("..") {
+            return Ok((input, RawNumber::int((start, input.offset, input.extra))));
+        }
+
         let dot: IResult<NomSpan, NomSpan, (NomSpan, nom::error::ErrorKind)> = tag(".")(input);
 
         let input = match dot {
@@ -326,6 +332,19 @@ pub fn member(input: NomSpan) -> IResult<NomSpan, TokenNode> {
     })
 }
 
+pub fn wildcard(input: NomSpan) -> IResult<NomSpan, TokenNode> {
+    trace_step(input, "wildcard", move |input| {
+        let start = input.offset;
+        let (input, _) = tag("*")(input)?;
+        let end = input.offset;
+
+        Ok((
+            input,
+            TokenTreeBuilder::tagged_member((start, end, input.extra)),
+        ))
+    })
+}
+
 pub fn flag(input: NomSpan) -> IResult<NomSpan, TokenNode> {
     trace_step(input, "flag", move |input| {
         let start = input.offset;
@@ -358,6 +377,8 @@ pub fn raw_unit(input: NomSpan) -> IResult<NomSpan, Tagged<Unit>> {
     trace_step(input, "raw_unit", move |input| {
         let start = input.offset;
         let (input, unit) = alt((
+            // Units of time, which `date` arithmetic counts in seconds
+            alt((tag("sec"), tag("min"), tag("hr"), tag("day"), tag("wk"))),
             tag("B"),
             tag("b"),
             tag("KB"),
@@ -404,6 +425,25 @@ pub fn size(input: NomSpan) -> IResult<NomSpan, TokenNode> {
                 input,
                 TokenTreeBuilder::tagged_size((number.item, *size), (start, end, input.extra)),
             ))
+        } else if input.fragment.starts_with('%') {
+            let (input, _) = tag("%")(input)?;
+            let end = input.offset;
+
+            // Percentages (eg. `first 10%`) are handed to commands as bare words
+            Ok((
+                input,
+                TokenTreeBuilder::tagged_bare((start, end, input.extra)),
+            ))
+        } else if input.fragment.starts_with("..") {
+            let (input, _) = tag("..")(input)?;
+            let (input, _) = opt(raw_number)(input)?;
+            let end = input.offset;
+
+            // So are ranges (eg. `range 5..10` or `random --decimal 1.5..2.5`)
+            Ok((
+                input,
+                TokenTreeBuilder::tagged_bare((start, end, input.extra)),
+            ))
         } else {
             let end = input.offset;
 
@@ -565,21 +605,69 @@ pub fn delimited_brace(input: NomSpan) -> IResult<NomSpan, TokenNode> {
         let left = input.offset;
         let (input, _) = char('{')(input)?;
         let (input, _) = opt(space1)(input)?;
+        let start = input.offset;
         let (input, items) = opt(token_list)(input)?;
+        let head_end = input.offset;
+        let (input, rest) = many0(tuple((opt(space1), tag("|"), opt(space1), raw_call)))(input)?;
+        let end = input.offset;
         let (input, _) = opt(space1)(input)?;
         let (input, _) = char('}')(input)?;
         let right = input.offset;
 
+        let items = match items {
+            // A block like `{ ls | get name }` holds a whole pipeline
+            Some(items) if !rest.is_empty() => {
+                let head = TokenTreeBuilder::tagged_call(items, (start, head_end, input.extra));
+
+                vec![TokenTreeBuilder::tagged_pipeline(
+                    (make_braced_call_list(head, rest), None),
+                    (start, end, input.extra),
+                )]
+            }
+            None if !rest.is_empty() => {
+                return Err(Err::Error(error_position!(
+                    input,
+                    nom::error::ErrorKind::Tag
+                )))
+            }
+            items => items.unwrap_or_else(|| vec![]),
+        };
+
         Ok((
             input,
-            TokenTreeBuilder::tagged_brace(
-                items.unwrap_or_else(|| vec![]),
-                (left, right, input.extra),
-            ),
+            TokenTreeBuilder::tagged_brace(items, (left, right, input.extra)),
         ))
     })
 }
 
+/// The whitespace before each `|` in a braced pipeline ends the call before it.
+fn make_braced_call_list(
+    head: Tagged<CallNode>,
+    rest: Vec<(Option<NomSpan>, NomSpan, Option<NomSpan>, Tagged<CallNode>)>,
+) -> Vec<PipelineElement> {
+    let mut out = vec![];
+    let mut pipe = None;
+    let mut pre_ws = None;
+    let mut call = head;
+
+    for (post_ws, next_pipe, next_pre_ws, next_call) in rest {
+        out.push(PipelineElement::new(
+            pipe,
+            pre_ws,
+            call,
+            post_ws.map(Tag::from),
+        ));
+
+        pipe = Some(Tag::from(next_pipe));
+        pre_ws = next_pre_ws.map(Tag::from);
+        call = next_call;
+    }
+
+    out.push(PipelineElement::new(pipe, pre_ws, call, None));
+
+    out
+}
+
 pub fn raw_call(input: NomSpan) -> IResult<NomSpan, Tagged<CallNode>> {
     trace_step(input, "raw_call", move |input| {
         let left = input.offset;
@@ -598,7 +686,7 @@ pub fn path(input: NomSpan) -> IResult<NomSpan, TokenNode> {
         let left = input.offset;
         let (input, head) = node1(input)?;
         let (input, _) = tag(".")(input)?;
-        let (input, tail) = separated_list(tag("."), alt((member, string)))(input)?;
+        let (input, tail) = separated_list(tag("."), alt((member, wildcard, string)))(input)?;
         let right = input.offset;
 
         Ok((
@@ -723,6 +811,7 @@ fn is_start_bare_char(c: char) -> bool {
         '/' => true,
         '_' => true,
         '-' => true,
+        '=' => true,
         '~' => true,
         _ => false,
     }
@@ -865,6 +954,37 @@ mod tests {
             parsers [ size ]
             "10GB" -> 0..4 { Size(RawNumber::int((0, 2, test_uuid())).item, Unit::GB) }
         }
+
+        assert_leaf! {
+            parsers [ size ]
+            "7day" -> 0..4 { Size(RawNumber::int((0, 1, test_uuid())).item, Unit::Day) }
+        }
+    }
+
+    #[test]
+    fn test_percentage() {
+        assert_leaf! {
+            parsers [ size ]
+            "50%" -> 0..3 { Bare }
+        }
+    }
+
+    #[test]
+    fn test_range() {
+        assert_leaf! {
+            parsers [ size ]
+            "5..10" -> 0..5 { Bare }
+        }
+
+        assert_leaf! {
+            parsers [ size ]
+            "5.." -> 0..3 { Bare }
+        }
+
+        assert_leaf! {
+            parsers [ size ]
+            "1.5..2.5" -> 0..8 { Bare }
+        }
     }
 
     #[test]
@@ -1014,6 +1134,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_braced_pipeline() {
+        assert_eq!(
+            apply(node, "node", "{ ls | get name }"),
+            build_token(b::braced(vec![b::pipeline(vec![
+                (None, b::call(b::bare("ls"), vec![]), Some(" ")),
+                (
+                    Some(" "),
+                    b::call(b::bare("get"), vec![b::sp(), b::bare("name")]),
+                    None
+                )
+            ])]))
+        );
+
+        assert_eq!(
+            apply(node, "node", "{ ls | each { echo $it } }"),
+            build_token(b::braced(vec![b::pipeline(vec![
+                (None, b::call(b::bare("ls"), vec![]), Some(" ")),
+                (
+                    Some(" "),
+                    b::call(
+                        b::bare("each"),
+                        vec![
+                            b::sp(),
+                            b::braced(vec![b::bare("echo"), b::sp(), b::var("it")])
+                        ]
+                    ),
+                    None
+                )
+            ])]))
+        );
+    }
+
     #[test]
     fn test_delimited_square() {
         assert_eq!(
@@ -3,9 +3,10 @@ use crate::traits::ToDebug;
 use crate::{Tag, Tagged};
 use derive_new::new;
 use getset::Getters;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, new)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, new)]
 pub struct Pipeline {
     pub(crate) parts: Vec<PipelineElement>,
     pub(crate) post_ws: Option<Tag>,
@@ -25,7 +26,7 @@ impl ToDebug for Pipeline {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, new)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, Serialize, Deserialize, new)]
 pub struct PipelineElement {
     pub pipe: Option<Tag>,
     pub pre_ws: Option<Tag>,
@@ -5,9 +5,10 @@ use crate::{Tag, Tagged, Text};
 use derive_new::new;
 use enum_utils::FromStr;
 use getset::Getters;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum TokenNode {
     Token(Token),
 
@@ -183,7 +184,7 @@ impl TokenNode {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, new)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, Serialize, Deserialize, new)]
 #[get = "pub(crate)"]
 pub struct DelimitedNode {
     delimiter: Delimiter,
@@ -200,14 +201,14 @@ impl DelimitedNode {
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, FromStr)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, FromStr, Serialize, Deserialize)]
 pub enum Delimiter {
     Paren,
     Brace,
     Square,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, new)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Getters, Serialize, Deserialize, new)]
 #[get = "pub(crate)"]
 pub struct PathNode {
     head: Box<TokenNode>,
@@ -1,10 +1,11 @@
 use crate::parser::parse::unit::*;
 use crate::prelude::*;
 use crate::{Tagged, Text};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum RawToken {
     Number(RawNumber),
     Size(RawNumber, Unit),
@@ -16,7 +17,7 @@ pub enum RawToken {
     Bare,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum RawNumber {
     Int(Tag),
     Decimal(Tag),
@@ -11,6 +11,11 @@ pub enum Unit {
     GB,
     TB,
     PB,
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
 }
 
 impl Unit {
@@ -22,20 +27,54 @@ impl Unit {
             Unit::GB => "GB",
             Unit::TB => "TB",
             Unit::PB => "PB",
+            Unit::Second => "sec",
+            Unit::Minute => "min",
+            Unit::Hour => "hr",
+            Unit::Day => "day",
+            Unit::Week => "wk",
+        }
+    }
+
+    /// The number of bytes in one of this unit, or for units of time, the number of seconds.
+    pub(crate) fn multiplier(&self) -> u64 {
+        match self {
+            Unit::B => 1,
+            Unit::KB => 1024,
+            Unit::MB => 1024 * 1024,
+            Unit::GB => 1024 * 1024 * 1024,
+            Unit::TB => 1024 * 1024 * 1024 * 1024,
+            Unit::PB => 1024 * 1024 * 1024 * 1024 * 1024,
+            Unit::Second => 1,
+            Unit::Minute => 60,
+            Unit::Hour => 60 * 60,
+            Unit::Day => 24 * 60 * 60,
+            Unit::Week => 7 * 24 * 60 * 60,
+        }
+    }
+
+    pub(crate) fn is_time(self) -> bool {
+        match self {
+            Unit::Second | Unit::Minute | Unit::Hour | Unit::Day | Unit::Week => true,
+            _ => false,
         }
     }
 
     pub(crate) fn compute(&self, size: &Number) -> Value {
-        let size = size.clone();
-
-        Value::number(match self {
-            Unit::B => size,
-            Unit::KB => size * 1024,
-            Unit::MB => size * 1024 * 1024,
-            Unit::GB => size * 1024 * 1024 * 1024,
-            Unit::TB => size * 1024 * 1024 * 1024 * 1024,
-            Unit::PB => size * 1024 * 1024 * 1024 * 1024 * 1024,
-        })
+        let amount = size.clone() * Number::from(self.multiplier());
+
+        if !self.is_time() {
+            return Value::number(amount);
+        }
+
+        let seconds = match &amount {
+            Number::Int(int) => int.to_i64(),
+            Number::Decimal(decimal) => decimal.to_i64(),
+        };
+
+        match seconds {
+            Some(seconds) => Value::duration(seconds),
+            None => Value::number(amount),
+        }
     }
 }
 
@@ -55,6 +94,11 @@ impl FromStr for Unit {
             "GB" | "gb" | "Gb" => Ok(Unit::GB),
             "TB" | "tb" | "Tb" => Ok(Unit::TB),
             "PB" | "pb" | "Pb" => Ok(Unit::PB),
+            "sec" => Ok(Unit::Second),
+            "min" => Ok(Unit::Minute),
+            "hr" => Ok(Unit::Hour),
+            "day" => Ok(Unit::Day),
+            "wk" => Ok(Unit::Week),
             _ => Err(()),
         }
     }
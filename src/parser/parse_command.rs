@@ -142,8 +142,9 @@ fn parse_command_tail(
         trace!("Processing positional {:?}", arg);
 
         match arg {
+            // Flags pulled out above are still in `tail`, so look for a token that's left
             PositionalType::Mandatory(..) => {
-                if tail.len() == 0 {
+                if tail.peek().is_none() {
                     return Err(ShellError::argument_error(
                         config.name.clone(),
                         ArgumentError::MissingMandatoryPositional(arg.name().to_string()),
@@ -153,7 +154,7 @@ fn parse_command_tail(
             }
 
             PositionalType::Optional(..) => {
-                if tail.len() == 0 {
+                if tail.peek().is_none() {
                     break;
                 }
             }
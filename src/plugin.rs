@@ -1,12 +1,23 @@
 use crate::Signature;
 use crate::Tagged;
-use crate::{CallInfo, ReturnValue, ShellError, Value};
+use crate::{CallInfo, Dictionary, ReturnValue, ShellError, Value};
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::io;
+use std::io::{self, BufRead, Write};
 
 pub trait Plugin {
     fn config(&mut self) -> Result<Signature, ShellError>;
 
+    /// The config keys the plugin wants. nu is asked for each of them during the
+    /// config handshake, and only these are ever sent to the plugin.
+    fn config_keys(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Receives the values of `config_keys` that are set, before `config` is
+    /// asked for the plugin's signature.
+    fn configure(&mut self, _config: Dictionary) {}
+
     fn begin_filter(&mut self, _call_info: CallInfo) -> Result<Vec<ReturnValue>, ShellError> {
         Ok(vec![])
     }
@@ -19,59 +30,21 @@ pub trait Plugin {
         Ok(vec![])
     }
 
-    fn sink(&mut self, _call_info: CallInfo, _input: Vec<Tagged<Value>>) {}
+    fn sink(
+        &mut self,
+        _call_info: CallInfo,
+        _input: Vec<Tagged<Value>>,
+    ) -> Result<Vec<ReturnValue>, ShellError> {
+        Ok(vec![])
+    }
 
     fn quit(&mut self) {}
 }
 
 pub fn serve_plugin(plugin: &mut dyn Plugin) {
-    let args = std::env::args();
-    if args.len() > 1 {
-        let input = args.skip(1).next();
-
-        let input = match input {
-            Some(arg) => std::fs::read_to_string(arg),
-            None => {
-                send_response(ShellError::string(format!("No input given.")));
-                return;
-            }
-        };
-
-        if let Ok(input) = input {
-            let command = serde_json::from_str::<NuCommand>(&input);
-            match command {
-                Ok(NuCommand::config) => {
-                    send_response(plugin.config());
-                    return;
-                }
-                Ok(NuCommand::begin_filter { params }) => {
-                    send_response(plugin.begin_filter(params));
-                }
-                Ok(NuCommand::filter { params }) => {
-                    send_response(plugin.filter(params));
-                }
-                Ok(NuCommand::end_filter) => {
-                    send_response(plugin.end_filter());
-                    return;
-                }
-
-                Ok(NuCommand::sink { params }) => {
-                    plugin.sink(params.0, params.1);
-                    return;
-                }
-                Ok(NuCommand::quit) => {
-                    plugin.quit();
-                    return;
-                }
-                e => {
-                    send_response(ShellError::string(format!(
-                        "Could not handle plugin message: {} {:?}",
-                        input, e
-                    )));
-                    return;
-                }
-            }
-        }
+    let mut args = std::env::args().skip(1);
+    if let Some(input) = args.next() {
+        serve_from_file(plugin, &input, args.next());
     } else {
         loop {
             let mut input = String::new();
@@ -80,6 +53,7 @@ pub fn serve_plugin(plugin: &mut dyn Plugin) {
                     let command = serde_json::from_str::<NuCommand>(&input);
                     match command {
                         Ok(NuCommand::config) => {
+                            receive_config(plugin, &mut io::stdin().lock(), &mut io::stdout());
                             send_response(plugin.config());
                             break;
                         }
@@ -94,7 +68,7 @@ pub fn serve_plugin(plugin: &mut dyn Plugin) {
                             break;
                         }
                         Ok(NuCommand::sink { params }) => {
-                            plugin.sink(params.0, params.1);
+                            let _ = plugin.sink(params.0, params.1);
                             break;
                         }
                         Ok(NuCommand::quit) => {
@@ -122,6 +96,47 @@ pub fn serve_plugin(plugin: &mut dyn Plugin) {
     }
 }
 
+/// Handle a single request read from a file. Sinks are given a second path to
+/// write their response to, since their stdout belongs to the terminal.
+fn serve_from_file(plugin: &mut dyn Plugin, path: &str, response: Option<String>) {
+    let input = match std::fs::read_to_string(path) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+
+    let command = serde_json::from_str::<NuCommand>(&input);
+    match command {
+        Ok(NuCommand::config) => {
+            receive_config(plugin, &mut io::stdin().lock(), &mut io::stdout());
+            send_response(plugin.config());
+        }
+        Ok(NuCommand::begin_filter { params }) => {
+            send_response(plugin.begin_filter(params));
+        }
+        Ok(NuCommand::filter { params }) => {
+            send_response(plugin.filter(params));
+        }
+        Ok(NuCommand::end_filter) => {
+            send_response(plugin.end_filter());
+        }
+        Ok(NuCommand::sink { params }) => {
+            let result = plugin.sink(params.0, params.1);
+            if let Some(response) = response {
+                write_response(&response, result);
+            }
+        }
+        Ok(NuCommand::quit) => {
+            plugin.quit();
+        }
+        e => {
+            send_response(ShellError::string(format!(
+                "Could not handle plugin message: {} {:?}",
+                input, e
+            )));
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct JsonRpc<T> {
     jsonrpc: String,
@@ -138,6 +153,56 @@ impl<T> JsonRpc<T> {
     }
 }
 
+/// Ask nu for a configuration value while filtering. The request goes out on
+/// stdout in place of a response, and nu answers it on stdin before carrying on.
+pub fn config_value(key: &str) -> Result<Option<Tagged<Value>>, ShellError> {
+    request_config_value(key, &mut io::stdin().lock(), &mut io::stdout())
+}
+
+/// Ask nu for each of the plugin's `config_keys` and hand the ones that are set to `configure`.
+fn receive_config(plugin: &mut dyn Plugin, input: &mut dyn BufRead, output: &mut dyn Write) {
+    let mut config = IndexMap::new();
+
+    for key in plugin.config_keys() {
+        if let Ok(Some(value)) = request_config_value(&key, input, output) {
+            config.insert(key, value);
+        }
+    }
+
+    plugin.configure(Dictionary::new(config));
+}
+
+fn request_config_value(
+    key: &str,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<Option<Tagged<Value>>, ShellError> {
+    let request = JsonRpc::new("config_get", key);
+    let request_raw =
+        serde_json::to_string(&request).map_err(|err| ShellError::string(format!("{}", err)))?;
+    writeln!(output, "{}", request_raw)?;
+    output.flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+
+    match serde_json::from_str::<JsonRpc<Option<Tagged<Value>>>>(&line) {
+        Ok(response) => Ok(response.params),
+        Err(err) => Err(ShellError::string(format!(
+            "Could not read config value for {}: {}",
+            key, err
+        ))),
+    }
+}
+
+fn write_response<T: Serialize>(path: &str, result: T) {
+    let response = JsonRpc::new("response", result);
+
+    if let Ok(response) = serde_json::to_string(&response) {
+        let _ = std::fs::write(path, response);
+    }
+}
+
 fn send_response<T: Serialize>(result: T) {
     let response = JsonRpc::new("response", result);
     let response_raw = serde_json::to_string(&response);
@@ -164,3 +229,113 @@ pub enum NuCommand {
     },
     quit,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{receive_config, serve_from_file, JsonRpc, Plugin};
+    use crate::commands::plugin::sink_response;
+    use crate::context::SourceMap;
+    use crate::data::Dictionary;
+    use crate::prelude::*;
+    use std::io::Write;
+
+    struct Summary;
+
+    impl Plugin for Summary {
+        fn config(&mut self) -> Result<Signature, ShellError> {
+            Ok(Signature::build("summary"))
+        }
+
+        fn sink(
+            &mut self,
+            _call_info: CallInfo,
+            input: Vec<Tagged<Value>>,
+        ) -> Result<Vec<ReturnValue>, ShellError> {
+            Ok(vec![ReturnSuccess::value(
+                Value::int(input.len()).tagged_unknown(),
+            )])
+        }
+    }
+
+    #[test]
+    fn sink_can_return_a_summary_value() {
+        let call_info = CallInfo {
+            args: Default::default(),
+            source_map: SourceMap::new(),
+            name_tag: Tag::unknown(),
+        };
+        let input = vec![
+            Value::string("a").tagged_unknown(),
+            Value::string("b").tagged_unknown(),
+        ];
+
+        let request = JsonRpc::new("sink", (call_info, input));
+        let mut request_file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(request_file, "{}", serde_json::to_string(&request).unwrap()).unwrap();
+
+        let response_file = tempfile::NamedTempFile::new().unwrap();
+        serve_from_file(
+            &mut Summary,
+            request_file.path().to_str().unwrap(),
+            Some(response_file.path().to_string_lossy().to_string()),
+        );
+
+        let raw = std::fs::read_to_string(response_file.path()).unwrap();
+        let values: Vec<_> = sink_response(&raw)
+            .into_iter()
+            .map(|v| match v {
+                Ok(ReturnSuccess::Value(v)) => v.item,
+                other => panic!("unexpected sink response: {:?}", other),
+            })
+            .collect();
+
+        assert_eq!(values, vec![Value::int(2)]);
+    }
+
+    #[derive(Default)]
+    struct Greeter {
+        greeting: Option<String>,
+    }
+
+    impl Plugin for Greeter {
+        fn config(&mut self) -> Result<Signature, ShellError> {
+            Ok(Signature::build("greeter"))
+        }
+
+        fn config_keys(&self) -> Vec<String> {
+            vec!["greeting".to_string()]
+        }
+
+        fn configure(&mut self, config: Dictionary) {
+            self.greeting = config
+                .get_data_by_key("greeting")
+                .and_then(|greeting| greeting.as_string().ok());
+        }
+    }
+
+    #[test]
+    fn plugin_receives_only_the_config_keys_it_asks_for() {
+        let answer = JsonRpc::new("config_value", Some(Value::string("hola").tagged_unknown()));
+        let mut input =
+            std::io::Cursor::new(format!("{}\n", serde_json::to_string(&answer).unwrap()));
+        let mut output = vec![];
+
+        let mut plugin = Greeter::default();
+        receive_config(&mut plugin, &mut input, &mut output);
+
+        let requests: Vec<JsonRpc<String>> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(
+            requests
+                .iter()
+                .map(|request| (request.method.as_str(), request.params.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("config_get", "greeting")]
+        );
+        assert_eq!(plugin.greeting, Some("hola".to_string()));
+    }
+}
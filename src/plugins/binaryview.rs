@@ -1,6 +1,7 @@
 use crossterm::{cursor, terminal, Attribute, RawScreen};
 use nu::{
-    serve_plugin, CallInfo, Plugin, Primitive, ShellError, Signature, SpanSource, Tagged, Value,
+    serve_plugin, CallInfo, Plugin, Primitive, ReturnValue, ShellError, Signature, SpanSource,
+    Tagged, Value,
 };
 use pretty_hex::*;
 
@@ -19,17 +20,23 @@ impl Plugin for BinaryView {
             .switch("lores"))
     }
 
-    fn sink(&mut self, call_info: CallInfo, input: Vec<Tagged<Value>>) {
+    fn sink(
+        &mut self,
+        call_info: CallInfo,
+        input: Vec<Tagged<Value>>,
+    ) -> Result<Vec<ReturnValue>, ShellError> {
         for v in input {
             let value_origin = v.origin();
             match v.item {
                 Value::Primitive(Primitive::Binary(b)) => {
                     let source = call_info.source_map.get(&value_origin);
-                    let _ = view_binary(&b, source, call_info.args.has("lores"));
+                    let _ = view_binary(&b, source.as_ref(), call_info.args.has("lores"));
                 }
                 _ => {}
             }
         }
+
+        Ok(vec![])
     }
 }
 
@@ -1,22 +1,28 @@
 use nu::{
-    serve_plugin, CallInfo, CoerceInto, Plugin, Primitive, ReturnSuccess, ReturnValue, ShellError,
-    Signature, SyntaxShape, Tagged, TaggedItem, Value,
+    percentage, serve_plugin, CallInfo, CoerceInto, Plugin, Primitive, ReturnSuccess, ReturnValue,
+    ShellError, Signature, SyntaxShape, Tagged, TaggedItem, Value,
 };
 
 struct Skip {
     skip_amount: i64,
+    skip_percentage: Option<f64>,
+    buffered: Vec<Tagged<Value>>,
 }
 
 impl Skip {
     fn new() -> Skip {
-        Skip { skip_amount: 0 }
+        Skip {
+            skip_amount: 0,
+            skip_percentage: None,
+            buffered: vec![],
+        }
     }
 }
 
 impl Plugin for Skip {
     fn config(&mut self) -> Result<Signature, ShellError> {
         Ok(Signature::build("skip")
-            .desc("Skip a number of rows")
+            .desc("Skip a number of rows, or a percentage of them (eg. skip 25%)")
             .rest(SyntaxShape::Number)
             .filter())
     }
@@ -30,13 +36,16 @@ impl Plugin for Skip {
                     } => {
                         self.skip_amount = i.tagged(tag).coerce_into("converting for skip")?;
                     }
-                    _ => {
-                        return Err(ShellError::labeled_error(
-                            "Unrecognized type in params",
-                            "expected an integer",
-                            arg.tag(),
-                        ))
-                    }
+                    arg => match percentage(&arg)? {
+                        Some(p) => self.skip_percentage = Some(p),
+                        None => {
+                            return Err(ShellError::labeled_error(
+                                "Unrecognized type in params",
+                                "expected an integer or a percentage",
+                                arg.tag(),
+                            ))
+                        }
+                    },
                 }
             }
         }
@@ -45,13 +54,33 @@ impl Plugin for Skip {
     }
 
     fn filter(&mut self, input: Tagged<Value>) -> Result<Vec<ReturnValue>, ShellError> {
-        if self.skip_amount == 0 {
+        if self.skip_percentage.is_some() {
+            // We need the whole stream to know how many rows a percentage covers
+            self.buffered.push(input);
+            Ok(vec![])
+        } else if self.skip_amount == 0 {
             Ok(vec![ReturnSuccess::value(input)])
         } else {
             self.skip_amount -= 1;
             Ok(vec![])
         }
     }
+
+    fn end_filter(&mut self) -> Result<Vec<ReturnValue>, ShellError> {
+        match self.skip_percentage {
+            Some(percentage) => {
+                let count = (self.buffered.len() as f64 * percentage / 100.0).round() as usize;
+
+                Ok(self
+                    .buffered
+                    .drain(..)
+                    .skip(count)
+                    .map(ReturnSuccess::value)
+                    .collect())
+            }
+            None => Ok(vec![]),
+        }
+    }
 }
 
 fn main() {
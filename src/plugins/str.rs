@@ -8,6 +8,9 @@ enum Action {
     Downcase,
     Upcase,
     ToInteger,
+    Trim,
+    Substring(usize, usize),
+    Replace(String),
 }
 
 struct Str {
@@ -37,6 +40,15 @@ impl Str {
                     Err(_) => Value::string(input),
                 },
             },
+            Some(Action::Trim) => Value::string(input.trim()),
+            Some(Action::Substring(start, end)) => Value::string(
+                input
+                    .chars()
+                    .skip(*start)
+                    .take(end - start)
+                    .collect::<String>(),
+            ),
+            Some(Action::Replace(replacement)) => Value::string(replacement.as_str()),
             None => Value::string(input),
         };
 
@@ -63,6 +75,30 @@ impl Str {
         }
     }
 
+    fn for_trim(&mut self) {
+        if self.permit() {
+            self.action = Some(Action::Trim);
+        } else {
+            self.log_error("can only apply one");
+        }
+    }
+
+    fn for_substring(&mut self, start: usize, end: usize) {
+        if self.permit() {
+            self.action = Some(Action::Substring(start, end));
+        } else {
+            self.log_error("can only apply one");
+        }
+    }
+
+    fn for_replace(&mut self, replacement: &str) {
+        if self.permit() {
+            self.action = Some(Action::Replace(replacement.to_string()));
+        } else {
+            self.log_error("can only apply one");
+        }
+    }
+
     fn for_downcase(&mut self) {
         if self.permit() {
             self.action = Some(Action::Downcase);
@@ -80,7 +116,20 @@ impl Str {
     }
 
     pub fn usage() -> &'static str {
-        "Usage: str field [--downcase|--upcase|--to-int]"
+        "Usage: str field [--downcase|--upcase|--to-int|--trim|--substring \"start,end\"|--replace value]"
+    }
+}
+
+/// Parse the `start,end` range given to `--substring`, where `end` is exclusive.
+fn substring_range(range: &str) -> Option<(usize, usize)> {
+    let mut bounds = range.splitn(2, ',');
+    let start = bounds.next()?.trim().parse::<usize>().ok()?;
+    let end = bounds.next()?.trim().parse::<usize>().ok()?;
+
+    if start > end {
+        None
+    } else {
+        Some((start, end))
     }
 }
 
@@ -90,6 +139,10 @@ impl Str {
             Value::Primitive(Primitive::String(ref s)) => {
                 Ok(Tagged::from_item(self.apply(&s)?, value.tag()))
             }
+            // csv and tsv cells may already have been read as integers
+            Value::Primitive(Primitive::Int(_)) if self.action == Some(Action::ToInteger) => {
+                Ok(value)
+            }
             Value::Row(_) => match self.field {
                 Some(ref f) => {
                     let replacement = match value.item.get_data_by_path(value.tag(), f) {
@@ -127,6 +180,9 @@ impl Plugin for Str {
             .switch("downcase")
             .switch("upcase")
             .switch("to-int")
+            .switch("trim")
+            .named("substring", SyntaxShape::String)
+            .named("replace", SyntaxShape::String)
             .rest(SyntaxShape::Member)
             .filter())
     }
@@ -143,20 +199,45 @@ impl Plugin for Str {
         if args.has("to-int") {
             self.for_to_int();
         }
+        if args.has("trim") {
+            self.for_trim();
+        }
+        if let Some(range) = args.get("substring") {
+            let bounds = match &range.item {
+                Value::Primitive(Primitive::String(s)) => substring_range(s),
+                _ => None,
+            };
+
+            match bounds {
+                Some((start, end)) => self.for_substring(start, end),
+                None => {
+                    return Err(ShellError::labeled_error(
+                        "Invalid substring range",
+                        "expected start,end (eg. 0,3)",
+                        range.tag(),
+                    ))
+                }
+            }
+        }
+        if let Some(replacement) = args.get("replace") {
+            match &replacement.item {
+                Value::Primitive(Primitive::String(s)) => self.for_replace(s),
+                _ => {
+                    return Err(ShellError::labeled_error(
+                        "Invalid replacement",
+                        "expected a string",
+                        replacement.tag(),
+                    ))
+                }
+            }
+        }
 
         if let Some(possible_field) = args.nth(0) {
             match possible_field {
                 Tagged {
                     item: Value::Primitive(Primitive::String(s)),
                     ..
-                } => match self.action {
-                    Some(Action::Downcase)
-                    | Some(Action::Upcase)
-                    | Some(Action::ToInteger)
-                    | None => {
-                        self.for_field(&s);
-                    }
-                },
+                } => self.for_field(&s),
                 _ => {
                     return Err(ShellError::string(format!(
                         "Unrecognized type in params: {:?}",
@@ -226,6 +307,14 @@ mod tests {
             self
         }
 
+        fn with_named_parameter(&mut self, name: &str, value: &str) -> &mut Self {
+            self.flags.insert(
+                name.to_string(),
+                Value::string(value).tagged(Tag::unknown()),
+            );
+            self
+        }
+
         fn with_parameter(&mut self, name: &str) -> &mut Self {
             self.positionals
                 .push(Value::string(name.to_string()).tagged(Tag::unknown()));
@@ -257,7 +346,14 @@ mod tests {
 
         let configured = plugin.config().unwrap();
 
-        for action_flag in &["downcase", "upcase", "to-int"] {
+        for action_flag in &[
+            "downcase",
+            "upcase",
+            "to-int",
+            "trim",
+            "substring",
+            "replace",
+        ] {
             assert!(configured.named.get(*action_flag).is_some());
         }
     }
@@ -291,6 +387,35 @@ mod tests {
             .is_ok());
         assert_eq!(plugin.action.unwrap(), Action::ToInteger);
     }
+    #[test]
+    fn str_plugin_accepts_substring_range() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(
+                CallStub::new()
+                    .with_named_parameter("substring", "1,4")
+                    .create()
+            )
+            .is_ok());
+        assert_eq!(plugin.action.unwrap(), Action::Substring(1, 4));
+    }
+
+    #[test]
+    fn str_plugin_rejects_invalid_substring_range() {
+        for range in &["1", "4,1", "a,b"] {
+            let mut plugin = Str::new();
+
+            assert!(plugin
+                .begin_filter(
+                    CallStub::new()
+                        .with_named_parameter("substring", range)
+                        .create()
+                )
+                .is_err());
+        }
+    }
+
     #[test]
     fn str_plugin_accepts_field() {
         let mut plugin = Str::new();
@@ -343,6 +468,34 @@ mod tests {
         assert_eq!(strutils.apply("9999").unwrap(), Value::int(9999 as i64));
     }
 
+    #[test]
+    fn str_trims() {
+        let mut strutils = Str::new();
+        strutils.for_trim();
+        assert_eq!(
+            strutils.apply("  andres \n").unwrap(),
+            Value::string("andres")
+        );
+    }
+
+    #[test]
+    fn str_substrings() {
+        let mut strutils = Str::new();
+        strutils.for_substring(1, 4);
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("ndr"));
+
+        let mut strutils = Str::new();
+        strutils.for_substring(4, 10);
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("es"));
+    }
+
+    #[test]
+    fn str_replaces() {
+        let mut strutils = Str::new();
+        strutils.for_replace("jonathan");
+        assert_eq!(strutils.apply("andres").unwrap(), Value::string("jonathan"));
+    }
+
     #[test]
     fn str_plugin_applies_upcase_with_field() {
         let mut plugin = Str::new();
@@ -486,4 +639,24 @@ mod tests {
             _ => {}
         }
     }
+
+    #[test]
+    fn str_plugin_keeps_integers_when_applying_to_int() {
+        let mut plugin = Str::new();
+
+        assert!(plugin
+            .begin_filter(CallStub::new().with_long_flag("to-int").create())
+            .is_ok());
+
+        let subject = Tagged::from_item(Value::int(10), Tag::unknown());
+        let output = plugin.filter(subject).unwrap();
+
+        match output[0].as_ref().unwrap() {
+            ReturnSuccess::Value(Tagged {
+                item: Value::Primitive(Primitive::Int(i)),
+                ..
+            }) => assert_eq!(*i, BigInt::from(10)),
+            _ => panic!("expected an integer"),
+        }
+    }
 }
@@ -1,8 +1,8 @@
 use crossterm::{cursor, terminal, RawScreen};
 use crossterm::{InputEvent, KeyEvent};
 use nu::{
-    serve_plugin, CallInfo, Plugin, Primitive, ShellError, Signature, SourceMap, SpanSource,
-    Tagged, Value,
+    serve_plugin, CallInfo, Plugin, Primitive, ReturnValue, ShellError, Signature, SourceMap,
+    SpanSource, Tagged, Value,
 };
 
 use syntect::easy::HighlightLines;
@@ -29,8 +29,14 @@ impl Plugin for TextView {
         Ok(Signature::build("textview").desc("Autoview of text data."))
     }
 
-    fn sink(&mut self, call_info: CallInfo, input: Vec<Tagged<Value>>) {
+    fn sink(
+        &mut self,
+        call_info: CallInfo,
+        input: Vec<Tagged<Value>>,
+    ) -> Result<Vec<ReturnValue>, ShellError> {
         view_text_value(&input[0], &call_info.source_map);
+
+        Ok(vec![])
     }
 }
 
@@ -222,7 +228,7 @@ fn view_text_value(value: &Tagged<Value>, source_map: &SourceMap) {
             let source = source_map.get(&value_origin);
 
             if let Some(source) = source {
-                let extension: Option<String> = match source {
+                let extension: Option<String> = match &source {
                     SpanSource::File(file) => {
                         let path = Path::new(file);
                         path.extension().map(|x| x.to_string_lossy().to_string())
@@ -1,5 +1,5 @@
 use derive_new::new;
-use nu::{serve_plugin, CallInfo, Plugin, ShellError, Signature, Tagged, Value};
+use nu::{serve_plugin, CallInfo, Plugin, ReturnValue, ShellError, Signature, Tagged, Value};
 use ptree::item::StringItem;
 use ptree::output::print_tree_with;
 use ptree::print_config::PrintConfig;
@@ -82,13 +82,19 @@ impl Plugin for TreeViewer {
         Ok(Signature::build("tree").desc("View the contents of the pipeline as a tree."))
     }
 
-    fn sink(&mut self, _call_info: CallInfo, input: Vec<Tagged<Value>>) {
+    fn sink(
+        &mut self,
+        _call_info: CallInfo,
+        input: Vec<Tagged<Value>>,
+    ) -> Result<Vec<ReturnValue>, ShellError> {
         if input.len() > 0 {
             for i in input.iter() {
                 let view = TreeView::from_value(&i);
                 let _ = view.render_view();
             }
         }
+
+        Ok(vec![])
     }
 }
 
@@ -0,0 +1,126 @@
+use crate::cli::run_pipeline;
+use crate::context::Context;
+use crate::data::config;
+use crate::git::current_branch;
+use crate::prelude::*;
+use chrono::Local;
+
+pub(crate) const DEFAULT_PROMPT: &str = "{cwd}{git-branch}> ";
+
+/// What the tokens of a prompt template expand to.
+pub(crate) struct PromptVars {
+    pub(crate) cwd: String,
+    pub(crate) git_branch: Option<String>,
+    pub(crate) exit_code: i32,
+    pub(crate) time: String,
+}
+
+impl PromptVars {
+    fn current(context: &Context, exit_code: i32) -> PromptVars {
+        PromptVars {
+            cwd: context.shell_manager.path(),
+            git_branch: current_branch(),
+            exit_code,
+            time: Local::now().format("%H:%M:%S").to_string(),
+        }
+    }
+}
+
+/// Expand `{cwd}`, `{git-branch}`, `{exit-code}` and `{time}` in a prompt template.
+/// Anything else in braces is left as it was written.
+pub(crate) fn expand_prompt(template: &str, vars: &PromptVars) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+
+        match &rest[1..end] {
+            "cwd" => out.push_str(&vars.cwd),
+            "git-branch" => {
+                if let Some(branch) = &vars.git_branch {
+                    out.push_str(&format!("({})", branch));
+                }
+            }
+            "exit-code" => out.push_str(&vars.exit_code.to_string()),
+            "time" => out.push_str(&vars.time),
+            _ => out.push_str(&rest[..=end]),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Run the configured `prompt_command` pipeline, using what it outputs as the prompt.
+async fn prompt_from_command(command: &str, context: &mut Context) -> Option<String> {
+    let values = run_pipeline(command, context).await.ok()?;
+
+    let prompt = values
+        .iter()
+        .filter_map(|value| value.as_string().ok())
+        .collect::<Vec<_>>()
+        .join("");
+
+    if prompt.is_empty() {
+        None
+    } else {
+        Some(prompt)
+    }
+}
+
+/// The prompt to show before reading the next line. A `prompt_command` set in the config
+/// generates it each time, otherwise the `prompt` template (or the default) is expanded.
+pub(crate) async fn prompt(context: &mut Context, exit_code: i32) -> String {
+    let config = config::config(Tag::unknown()).unwrap_or_default();
+    let setting = |key: &str| config.get(key).and_then(|value| value.as_string().ok());
+
+    if let Some(command) = setting("prompt_command") {
+        if let Some(prompt) = prompt_from_command(&command, context).await {
+            return expand_prompt(&prompt, &PromptVars::current(context, exit_code));
+        }
+    }
+
+    let template = setting("prompt").unwrap_or_else(|| DEFAULT_PROMPT.to_string());
+
+    expand_prompt(&template, &PromptVars::current(context, exit_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_prompt, PromptVars, DEFAULT_PROMPT};
+
+    fn vars(git_branch: Option<&str>) -> PromptVars {
+        PromptVars {
+            cwd: "/home/nu".to_string(),
+            git_branch: git_branch.map(|branch| branch.to_string()),
+            exit_code: 1,
+            time: "12:30:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn default_prompt_shows_cwd_and_branch() {
+        assert_eq!(
+            expand_prompt(DEFAULT_PROMPT, &vars(Some("master"))),
+            "/home/nu(master)> "
+        );
+        assert_eq!(expand_prompt(DEFAULT_PROMPT, &vars(None)), "/home/nu> ");
+    }
+
+    #[test]
+    fn expands_tokens_and_leaves_unknown_ones() {
+        assert_eq!(
+            expand_prompt("[{time}] {exit-code} {user} {cwd", &vars(None)),
+            "[12:30:00] 1 {user} {cwd"
+        );
+    }
+}
@@ -2,6 +2,7 @@ pub(crate) mod completer;
 pub(crate) mod filesystem_shell;
 pub(crate) mod help_shell;
 pub(crate) mod helper;
+pub(crate) mod remote_shell;
 pub(crate) mod shell;
 pub(crate) mod shell_manager;
 pub(crate) mod value_shell;
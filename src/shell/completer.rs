@@ -1,6 +1,6 @@
 use crate::prelude::*;
 use derive_new::new;
-use rustyline::completion::{Completer, FilenameCompleter};
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
 
 #[derive(new)]
 pub(crate) struct NuCompleter {
@@ -14,7 +14,7 @@ impl NuCompleter {
         line: &str,
         pos: usize,
         context: &rustyline::Context,
-    ) -> rustyline::Result<(usize, Vec<rustyline::completion::Pair>)> {
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
         let commands: Vec<String> = self.commands.names();
 
         let mut completions = self.file_completer.complete(line, pos, context)?.1;
@@ -37,39 +37,110 @@ impl NuCompleter {
             }
         }
 
-        let line_chars: Vec<_> = line[..pos].chars().collect();
-        let mut replace_pos = line_chars.len();
-        while replace_pos > 0 {
-            if line_chars[replace_pos - 1] == ' ' {
-                break;
-            }
-            replace_pos -= 1;
-        }
+        let before = &line[..pos];
+        let replace_pos = before.rfind(' ').map(|idx| idx + 1).unwrap_or(0);
+        let word = &before[replace_pos..];
 
-        for command in commands.iter() {
-            let mut pos = replace_pos;
-            let mut matched = true;
-            if pos < line_chars.len() {
-                for chr in command.chars() {
-                    if line_chars[pos] != chr {
-                        matched = false;
-                        break;
-                    }
-                    pos += 1;
-                    if pos == line_chars.len() {
-                        break;
-                    }
-                }
-            }
+        // Flags belong to the command that starts the current pipeline segment
+        if word.starts_with("--") {
+            let segment = before.rsplit('|').next().unwrap_or("");
+            let flags = match segment.split_whitespace().next() {
+                Some(name) => match self.commands.get_command(name) {
+                    Some(command) => flag_candidates(&command.signature(), &word[2..]),
+                    None => vec![],
+                },
+                None => vec![],
+            };
 
-            if matched {
-                completions.push(rustyline::completion::Pair {
-                    display: command.clone(),
-                    replacement: command.clone(),
-                });
-            }
+            return Ok((replace_pos, flags));
         }
 
-        Ok((replace_pos, completions))
+        let mut candidates = command_candidates(&commands, word);
+        candidates.extend(completions);
+
+        Ok((replace_pos, candidates))
+    }
+}
+
+/// Registered command names matching `partial`, with prefix matches first (shortest
+/// first) followed by names that merely contain it.
+fn command_candidates(commands: &[String], partial: &str) -> Vec<Pair> {
+    let mut prefixed: Vec<&String> = commands
+        .iter()
+        .filter(|name| name.starts_with(partial))
+        .collect();
+    prefixed.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+
+    let mut contained: Vec<&String> = commands
+        .iter()
+        .filter(|name| !partial.is_empty() && !name.starts_with(partial))
+        .filter(|name| name.contains(partial))
+        .collect();
+    contained.sort();
+
+    prefixed
+        .into_iter()
+        .chain(contained)
+        .map(|name| Pair {
+            display: name.clone(),
+            replacement: name.clone(),
+        })
+        .collect()
+}
+
+fn flag_candidates(signature: &Signature, partial: &str) -> Vec<Pair> {
+    let mut flags: Vec<&String> = signature
+        .named
+        .keys()
+        .filter(|flag| flag.starts_with(partial))
+        .collect();
+    flags.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+
+    flags
+        .into_iter()
+        .map(|flag| Pair {
+            display: format!("--{}", flag),
+            replacement: format!("--{}", flag),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{command_candidates, flag_candidates};
+    use crate::prelude::*;
+
+    fn names(pairs: Vec<rustyline::completion::Pair>) -> Vec<String> {
+        pairs.into_iter().map(|pair| pair.replacement).collect()
+    }
+
+    #[test]
+    fn ranks_prefix_matches_before_contained_ones() {
+        let commands: Vec<String> = vec!["to-json", "from-json", "from-csv", "from"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        assert_eq!(
+            names(command_candidates(&commands, "from")),
+            vec!["from", "from-csv", "from-json"]
+        );
+        assert_eq!(
+            names(command_candidates(&commands, "json")),
+            vec!["from-json", "to-json"]
+        );
+    }
+
+    #[test]
+    fn completes_flags_from_the_signature() {
+        let signature = Signature::build("autoview")
+            .named("max-rows", SyntaxShape::Int)
+            .switch("all");
+
+        assert_eq!(
+            names(flag_candidates(&signature, "")),
+            vec!["--all", "--max-rows"]
+        );
+        assert_eq!(names(flag_candidates(&signature, "ma")), vec!["--max-rows"]);
     }
 }
@@ -1,10 +1,11 @@
 use crate::commands::command::EvaluatedWholeStreamCommandArgs;
 use crate::commands::cp::CopyArgs;
+use crate::commands::ls::LsArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
 use crate::context::SourceMap;
-use crate::data::dir_entry_dict;
+use crate::data::{dir_entry_dict, TaggedDictBuilder};
 use crate::prelude::*;
 use crate::shell::completer::NuCompleter;
 use crate::shell::shell::Shell;
@@ -83,10 +84,23 @@ impl Shell for FilesystemShell {
 
     fn ls(
         &self,
-        pattern: Option<Tagged<PathBuf>>,
+        LsArgs {
+            path: pattern,
+            follow_symlinks,
+            full,
+        }: LsArgs,
         command_tag: Tag,
     ) -> Result<OutputStream, ShellError> {
-        let cwd = self.path();
+        let cwd = PathBuf::from(self.path());
+
+        // Unless asked to, report symlinks as themselves rather than what they point to
+        let metadata_of = move |path: &Path| {
+            if follow_symlinks {
+                std::fs::metadata(path)
+            } else {
+                std::fs::symlink_metadata(path)
+            }
+        };
         let mut full_path = PathBuf::from(self.path());
 
         match &pattern {
@@ -100,7 +114,9 @@ impl Shell for FilesystemShell {
         let lossy_path = full_path.to_string_lossy();
         if !lossy_path.contains("*") && !lossy_path.contains("?") {
             let entry = Path::new(&full_path);
-            if entry.is_dir() {
+            let traverse =
+                pattern.is_none() || metadata_of(entry).map(|m| m.is_dir()).unwrap_or(false);
+            if entry.is_dir() && traverse {
                 let entries = std::fs::read_dir(&entry);
                 let entries = match entries {
                     Err(e) => {
@@ -128,7 +144,8 @@ impl Shell for FilesystemShell {
                     } else {
                         Path::new(&filepath)
                     };
-                    let value = dir_entry_dict(filename, &entry.metadata()?, command_tag)?;
+                    let value =
+                        dir_entry_dict(filename, &metadata_of(&filepath)?, full, command_tag)?;
                     shell_entries.push_back(ReturnSuccess::value(value))
                 }
                 return Ok(shell_entries.to_output_stream());
@@ -150,21 +167,17 @@ impl Shell for FilesystemShell {
             }
         };
 
-        // Enumerate the entries from the glob and add each
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let filename = if let Ok(fname) = entry.strip_prefix(&cwd) {
-                    fname
-                } else {
-                    Path::new(&entry)
-                };
-                let metadata = std::fs::metadata(&entry)?;
-                let value = dir_entry_dict(filename, &metadata, command_tag)?;
-                shell_entries.push_back(ReturnSuccess::value(value))
-            }
-        }
+        // `**` can reach a lot of files, so entries are produced as the glob walks to them
+        let stream = futures::stream::iter(entries.filter_map(Result::ok)).map(
+            move |entry| -> ReturnValue {
+                let filename = entry.strip_prefix(&cwd).unwrap_or(&entry);
+                let value = dir_entry_dict(filename, &metadata_of(&entry)?, full, command_tag)?;
+
+                ReturnSuccess::value(value)
+            },
+        );
 
-        Ok(shell_entries.to_output_stream())
+        Ok(stream.to_output_stream())
     }
 
     fn cd(&self, args: EvaluatedWholeStreamCommandArgs) -> Result<OutputStream, ShellError> {
@@ -224,6 +237,7 @@ impl Shell for FilesystemShell {
             src,
             dst,
             recursive,
+            dry_run,
         }: CopyArgs,
         name: Tag,
         path: &str,
@@ -247,16 +261,31 @@ impl Shell for FilesystemShell {
             }
         };
 
+        // Checked before a dry run, so that it fails just like the copy would
+        if let [Ok(entry)] = &sources[..] {
+            if entry.is_dir() && !recursive.item {
+                return Err(ShellError::labeled_error(
+                    "is a directory (not copied). Try using \"--recursive\".",
+                    "is a directory (not copied). Try using \"--recursive\".",
+                    src.tag,
+                ));
+            }
+        }
+
+        if sources.len() != 1 && destination.exists() && !all_files(&sources) {
+            return Err(ShellError::labeled_error(
+                "Copy aborted (directories found). Recursive copying in patterns not supported yet (try copying the directory directly)",
+                "Copy aborted (directories found). Recursive copying in patterns not supported yet (try copying the directory directly)",
+                src.tag,
+            ));
+        }
+
+        if dry_run.item {
+            return Ok(planned_operations("copy", sources, &destination, name_tag).into());
+        }
+
         if sources.len() == 1 {
             if let Ok(entry) = &sources[0] {
-                if entry.is_dir() && !recursive.item {
-                    return Err(ShellError::labeled_error(
-                        "is a directory (not copied). Try using \"--recursive\".",
-                        "is a directory (not copied). Try using \"--recursive\".",
-                        src.tag,
-                    ));
-                }
-
                 let mut sources: FileStructure = FileStructure::new();
 
                 sources.walk_decorate(&entry)?;
@@ -435,17 +464,6 @@ impl Shell for FilesystemShell {
             }
         } else {
             if destination.exists() {
-                if !sources.iter().all(|x| match x {
-                    Ok(f) => f.is_file(),
-                    Err(_) => false,
-                }) {
-                    return Err(ShellError::labeled_error(
-                    "Copy aborted (directories found). Recursive copying in patterns not supported yet (try copying the directory directly)",
-                    "Copy aborted (directories found). Recursive copying in patterns not supported yet (try copying the directory directly)",
-                    src.tag,
-                ));
-                }
-
                 for entry in sources {
                     if let Ok(entry) = entry {
                         let mut to = PathBuf::from(&destination);
@@ -540,7 +558,7 @@ impl Shell for FilesystemShell {
 
     fn mv(
         &self,
-        MoveArgs { src, dst }: MoveArgs,
+        MoveArgs { src, dst, dry_run }: MoveArgs,
         name: Tag,
         path: &str,
     ) -> Result<OutputStream, ShellError> {
@@ -576,6 +594,19 @@ impl Shell for FilesystemShell {
             }
         };
 
+        // Checked before a dry run, so that it fails just like the move would
+        if sources.len() != 1 && destination.exists() && !all_files(&sources) {
+            return Err(ShellError::labeled_error(
+                "Rename aborted (directories found). Renaming in patterns not supported yet (try moving the directory directly)",
+                "Rename aborted (directories found). Renaming in patterns not supported yet (try moving the directory directly)",
+                src.tag,
+            ));
+        }
+
+        if dry_run.item {
+            return Ok(planned_operations("move", sources, &destination, name_tag).into());
+        }
+
         if sources.len() == 1 {
             if let Ok(entry) = &sources[0] {
                 let entry_file_name = match entry.file_name() {
@@ -775,20 +806,6 @@ impl Shell for FilesystemShell {
             }
         } else {
             if destination.exists() {
-                if !sources.iter().all(|x| {
-                    if let Ok(entry) = x.as_ref() {
-                        entry.is_file()
-                    } else {
-                        false
-                    }
-                }) {
-                    return Err(ShellError::labeled_error(
-                    "Rename aborted (directories found). Renaming in patterns not supported yet (try moving the directory directly)",
-                    "Rename aborted (directories found). Renaming in patterns not supported yet (try moving the directory directly)",
-                    src.tag,
-                ));
-                }
-
                 for entry in sources {
                     if let Ok(entry) = entry {
                         let entry_file_name = match entry.file_name() {
@@ -843,7 +860,11 @@ impl Shell for FilesystemShell {
 
     fn rm(
         &self,
-        RemoveArgs { target, recursive }: RemoveArgs,
+        RemoveArgs {
+            target,
+            recursive,
+            dry_run,
+        }: RemoveArgs,
         name: Tag,
         path: &str,
     ) -> Result<OutputStream, ShellError> {
@@ -892,6 +913,8 @@ impl Shell for FilesystemShell {
             }
         }
 
+        let mut planned = VecDeque::new();
+
         for entry in entries {
             match entry {
                 Ok(path) => {
@@ -926,7 +949,9 @@ impl Shell for FilesystemShell {
                         ));
                     }
 
-                    if path.is_dir() {
+                    if dry_run.item {
+                        planned.push_back(planned_operation("remove", &path, None, name_tag));
+                    } else if path.is_dir() {
                         std::fs::remove_dir_all(&path)?;
                     } else if path.is_file() {
                         std::fs::remove_file(&path)?;
@@ -942,7 +967,7 @@ impl Shell for FilesystemShell {
             }
         }
 
-        Ok(OutputStream::empty())
+        Ok(planned.into())
     }
 
     fn path(&self) -> String {
@@ -1000,3 +1025,56 @@ impl Shell for FilesystemShell {
         self.hinter.hint(line, pos, ctx)
     }
 }
+
+fn planned_operation(
+    operation: &str,
+    source: &Path,
+    destination: Option<&Path>,
+    tag: Tag,
+) -> ReturnValue {
+    let mut dict = TaggedDictBuilder::new(tag);
+
+    dict.insert("operation", Value::string(operation));
+    dict.insert("source", Value::path(source.to_path_buf()));
+    match destination {
+        Some(destination) => dict.insert("destination", Value::path(destination.to_path_buf())),
+        None => dict.insert("destination", Value::nothing()),
+    }
+
+    ReturnSuccess::value(dict.into_tagged_value())
+}
+
+fn planned_operations(
+    operation: &str,
+    sources: Vec<Result<PathBuf, glob::GlobError>>,
+    destination: &Path,
+    tag: Tag,
+) -> VecDeque<ReturnValue> {
+    sources
+        .into_iter()
+        .map(|entry| match entry {
+            Ok(source) => {
+                let target = match source.file_name() {
+                    Some(file_name) if destination.is_dir() => destination.join(file_name),
+                    _ => destination.to_path_buf(),
+                };
+
+                planned_operation(operation, &source, Some(&target), tag)
+            }
+            // A match that couldn't be read would fail the real operation too
+            Err(err) => Err(ShellError::labeled_error(
+                format!("Could not read {:?}", err.path()),
+                err.error().to_string(),
+                tag,
+            )),
+        })
+        .collect()
+}
+
+/// Whether every matched path is a file, as copying or moving several at once needs.
+fn all_files(sources: &[Result<PathBuf, glob::GlobError>]) -> bool {
+    sources.iter().all(|entry| match entry {
+        Ok(path) => path.is_file(),
+        Err(_) => false,
+    })
+}
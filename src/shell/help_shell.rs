@@ -1,5 +1,6 @@
 use crate::commands::command::EvaluatedWholeStreamCommandArgs;
 use crate::commands::cp::CopyArgs;
+use crate::commands::ls::LsArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
@@ -126,11 +127,7 @@ impl Shell for HelpShell {
         self.path = path.clone();
     }
 
-    fn ls(
-        &self,
-        _pattern: Option<Tagged<PathBuf>>,
-        _command_tag: Tag,
-    ) -> Result<OutputStream, ShellError> {
+    fn ls(&self, _args: LsArgs, _command_tag: Tag) -> Result<OutputStream, ShellError> {
         Ok(self
             .commands()
             .map(|x| ReturnSuccess::value(x))
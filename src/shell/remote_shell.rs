@@ -0,0 +1,393 @@
+use crate::commands::command::EvaluatedWholeStreamCommandArgs;
+use crate::commands::cp::CopyArgs;
+use crate::commands::from_json::from_json_string_to_value;
+use crate::commands::ls::LsArgs;
+use crate::commands::mkdir::MkdirArgs;
+use crate::commands::mv::MoveArgs;
+use crate::commands::rm::RemoveArgs;
+use crate::context::SourceMap;
+use crate::prelude::*;
+use crate::shell::shell::Shell;
+use std::path::PathBuf;
+use url::Url;
+
+/// A shell over an http(s) JSON API. The path is a url, and each resource is fetched as it is
+/// listed or changed into.
+#[derive(Clone)]
+pub struct RemoteShell {
+    pub(crate) path: String,
+    pub(crate) last_path: String,
+    pub(crate) home: String,
+}
+
+impl std::fmt::Debug for RemoteShell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RemoteShell @ {}", self.path)
+    }
+}
+
+impl RemoteShell {
+    pub fn with_location(location: String) -> Result<RemoteShell, ShellError> {
+        match Url::parse(&location) {
+            Ok(ref url) if url.scheme() == "http" || url.scheme() == "https" => Ok(RemoteShell {
+                path: location.clone(),
+                last_path: location.clone(),
+                home: location,
+            }),
+            _ => Err(ShellError::string(format!(
+                "Can not enter {}, expected an http(s) url",
+                location
+            ))),
+        }
+    }
+}
+
+/// Where `target` leads from `current`: a full url replaces it, `-` goes back to `last`, and
+/// anything else is a path, with `..` moving up and a leading `/` starting from the host.
+pub(crate) fn resolve(current: &str, last: &str, target: &str) -> Result<String, String> {
+    if target == "-" {
+        return Ok(last.to_string());
+    }
+
+    if let Ok(url) = Url::parse(target) {
+        return Ok(url.to_string());
+    }
+
+    let mut url = Url::parse(current).map_err(|e| e.to_string())?;
+    url.set_query(None);
+    url.set_fragment(None);
+
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| format!("{} has no path", current))?;
+
+        if target.starts_with('/') {
+            segments.clear();
+        }
+        segments.pop_if_empty();
+
+        for step in target.split('/') {
+            match step {
+                "" | "." => {}
+                ".." => {
+                    segments.pop();
+                }
+                step => {
+                    segments.push(step);
+                }
+            }
+        }
+    }
+
+    Ok(url.to_string())
+}
+
+/// The url a resource links to by `name`: a `name` or `name_url` field holding a url, a `name`
+/// field that is itself a resource with a `url`, or, in a list, the entry with that name.
+pub(crate) fn link(resource: &Value, name: &str) -> Option<String> {
+    let url_of = |value: &Tagged<Value>| match value.item() {
+        Value::Primitive(Primitive::String(s)) => Some(s.clone()),
+        Value::Row(_) => value.get_data_by_key("url")?.as_string().ok(),
+        _ => None,
+    };
+
+    let link = match resource {
+        Value::Row(_) => resource
+            .get_data_by_key(name)
+            .and_then(url_of)
+            .or_else(|| url_of(resource.get_data_by_key(&format!("{}_url", name))?)),
+        Value::Table(entries) => entries
+            .iter()
+            .find(|entry| {
+                ["name", "login", "id"].iter().any(|key| {
+                    entry
+                        .get_data_by_key(key)
+                        .and_then(|value| value.as_string().ok())
+                        .map(|value| value == name)
+                        .unwrap_or(false)
+                })
+            })
+            .and_then(|entry| url_of(entry.get_data_by_key("url")?)),
+        _ => None,
+    }?;
+
+    // Links like GitHub's `issues_url` end in a template for the parts that can follow
+    let link = link.split('{').next().unwrap_or_default();
+
+    match Url::parse(link) {
+        Ok(ref url) if url.scheme() == "http" || url.scheme() == "https" => Some(link.to_string()),
+        _ => None,
+    }
+}
+
+async fn get(location: &str) -> Result<surf::Response, String> {
+    let response = surf::get(location)
+        .set_header("Accept", "application/json")
+        .set_header("User-Agent", "nu")
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(format!("server responded with {}", response.status()))
+    }
+}
+
+async fn resource(location: &str, tag: Tag) -> Result<Tagged<Value>, ShellError> {
+    let mut response = get(location)
+        .await
+        .map_err(|reason| ShellError::labeled_error("Could not load from url", reason, tag))?;
+
+    let body = response.body_string().await.map_err(|_| {
+        ShellError::labeled_error("Could not load from url", "could not load text", tag)
+    })?;
+
+    from_json_string_to_value(body, tag).map_err(|_| {
+        ShellError::labeled_error("Could not load from url", "expected a JSON resource", tag)
+    })
+}
+
+/// Like `resolve`, but a plain name is first looked up as a link in the current resource.
+pub(crate) async fn destination(
+    current: &str,
+    last: &str,
+    target: &str,
+    tag: Tag,
+) -> Result<String, ShellError> {
+    let is_name = !target.contains('/') && target != "." && target != ".." && target != "-";
+
+    if is_name {
+        if let Ok(resource) = resource(current, tag).await {
+            if let Some(link) = link(&resource, target) {
+                return Ok(link);
+            }
+        }
+    }
+
+    resolve(current, last, target)
+        .map_err(|reason| ShellError::labeled_error("Can not change to path inside", reason, tag))
+}
+
+impl Shell for RemoteShell {
+    fn name(&self, _source_map: &SourceMap) -> String {
+        match Url::parse(&self.path) {
+            Ok(url) => format!("{{{}}}", url.host_str().unwrap_or_default()),
+            Err(_) => "remote".to_string(),
+        }
+    }
+
+    fn homedir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn ls(
+        &self,
+        LsArgs { path: target, .. }: LsArgs,
+        command_name: Tag,
+    ) -> Result<OutputStream, ShellError> {
+        let path = self.path.clone();
+        let last_path = self.last_path.clone();
+
+        let stream = async_stream_block! {
+            let location = match &target {
+                Some(target) => {
+                    let name = target.item().to_string_lossy().to_string();
+                    destination(&path, &last_path, &name, target.tag()).await
+                }
+                None => Ok(path),
+            };
+
+            let listing = match location {
+                Ok(location) => resource(&location, command_name).await,
+                Err(e) => Err(e),
+            };
+
+            match listing {
+                Ok(Tagged {
+                    item: Value::Table(entries),
+                    ..
+                }) => {
+                    for entry in entries {
+                        yield ReturnSuccess::value(entry);
+                    }
+                }
+                Ok(value) => yield ReturnSuccess::value(value),
+                Err(e) => yield Err(e),
+            }
+        };
+
+        Ok(stream.to_output_stream())
+    }
+
+    fn cd(&self, args: EvaluatedWholeStreamCommandArgs) -> Result<OutputStream, ShellError> {
+        let target = match args.nth(0) {
+            None => None,
+            Some(v) => Some(v.as_path()?.to_string_lossy().to_string().tagged(v.tag())),
+        };
+        let name_tag = args.call_info.name_tag;
+        let path = self.path.clone();
+        let last_path = self.last_path.clone();
+        let home = self.home.clone();
+
+        let stream = async_stream_block! {
+            let (location, tag) = match target {
+                None => (Ok(home), name_tag),
+                Some(target) => (
+                    destination(&path, &last_path, &target.item, target.tag()).await,
+                    target.tag(),
+                ),
+            };
+
+            match location {
+                Ok(location) => match get(&location).await {
+                    Ok(_) => yield ReturnSuccess::change_cwd(location),
+                    Err(reason) => yield Err(ShellError::labeled_error(
+                        "Can not change to path inside",
+                        reason,
+                        tag,
+                    )),
+                },
+                Err(e) => yield Err(e),
+            }
+        };
+
+        Ok(stream.to_output_stream())
+    }
+
+    fn cp(&self, _args: CopyArgs, name: Tag, _path: &str) -> Result<OutputStream, ShellError> {
+        Err(ShellError::labeled_error(
+            "cp not currently supported on urls",
+            "not currently supported",
+            name,
+        ))
+    }
+
+    fn mv(&self, _args: MoveArgs, name: Tag, _path: &str) -> Result<OutputStream, ShellError> {
+        Err(ShellError::labeled_error(
+            "mv not currently supported on urls",
+            "not currently supported",
+            name,
+        ))
+    }
+
+    fn mkdir(&self, _args: MkdirArgs, name: Tag, _path: &str) -> Result<OutputStream, ShellError> {
+        Err(ShellError::labeled_error(
+            "mkdir not currently supported on urls",
+            "not currently supported",
+            name,
+        ))
+    }
+
+    fn rm(&self, _args: RemoveArgs, name: Tag, _path: &str) -> Result<OutputStream, ShellError> {
+        Err(ShellError::labeled_error(
+            "rm not currently supported on urls",
+            "not currently supported",
+            name,
+        ))
+    }
+
+    fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    fn pwd(&self, args: EvaluatedWholeStreamCommandArgs) -> Result<OutputStream, ShellError> {
+        let mut stream = VecDeque::new();
+        stream.push_back(ReturnSuccess::value(Tagged::from_item(
+            Value::string(self.path()),
+            args.call_info.name_tag,
+        )));
+        Ok(stream.into())
+    }
+
+    fn set_path(&mut self, path: String) {
+        self.last_path = self.path.clone();
+        self.path = path.clone();
+    }
+
+    fn complete(
+        &self,
+        _line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> Result<(usize, Vec<rustyline::completion::Pair>), rustyline::error::ReadlineError> {
+        // Completing would mean a request on every keypress, so there's nothing to offer
+        Ok((pos, vec![]))
+    }
+
+    fn hint(&self, _line: &str, _pos: usize, _ctx: &rustyline::Context<'_>) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{link, resolve};
+    use crate::commands::from_json::from_json_string_to_value;
+    use crate::prelude::*;
+
+    #[test]
+    fn resolves_paths_against_the_current_url() {
+        let current = "https://api.github.com/repos/nushell/nushell";
+        let last = "https://api.github.com/orgs/nushell";
+
+        assert_eq!(
+            resolve(current, last, "issues").unwrap(),
+            "https://api.github.com/repos/nushell/nushell/issues"
+        );
+        assert_eq!(
+            resolve(current, last, "../nu_scripts").unwrap(),
+            "https://api.github.com/repos/nushell/nu_scripts"
+        );
+        assert_eq!(
+            resolve(current, last, "/users/jonathandturner").unwrap(),
+            "https://api.github.com/users/jonathandturner"
+        );
+        assert_eq!(resolve(current, last, "-").unwrap(), last);
+        assert_eq!(
+            resolve(current, last, "https://example.com/api").unwrap(),
+            "https://example.com/api"
+        );
+    }
+
+    #[test]
+    fn follows_links_in_resources() {
+        let repo = from_json_string_to_value(
+            r#"{
+                "name": "nushell",
+                "issues_url": "https://api.github.com/repos/nushell/nushell/issues{/number}",
+                "owner": { "login": "nushell", "url": "https://api.github.com/users/nushell" }
+            }"#
+            .to_string(),
+            Tag::unknown(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            link(&repo, "issues").unwrap(),
+            "https://api.github.com/repos/nushell/nushell/issues"
+        );
+        assert_eq!(
+            link(&repo, "owner").unwrap(),
+            "https://api.github.com/users/nushell"
+        );
+        assert_eq!(link(&repo, "name"), None);
+
+        let repos = from_json_string_to_value(
+            r#"[
+                { "name": "nushell", "url": "https://api.github.com/repos/nushell/nushell" },
+                { "name": "nu_scripts", "url": "https://api.github.com/repos/nushell/nu_scripts" }
+            ]"#
+            .to_string(),
+            Tag::unknown(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            link(&repos, "nu_scripts").unwrap(),
+            "https://api.github.com/repos/nushell/nu_scripts"
+        );
+        assert_eq!(link(&repos, "missing"), None);
+    }
+}
@@ -1,7 +1,9 @@
 use crate::commands::command::{EvaluatedWholeStreamCommandArgs, RunnablePerItemContext};
 use crate::commands::cp::CopyArgs;
+use crate::commands::ls::LsArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
+use crate::commands::remote::Remote;
 use crate::commands::rm::RemoveArgs;
 use crate::errors::ShellError;
 use crate::prelude::*;
@@ -10,27 +12,39 @@ use crate::shell::shell::Shell;
 use crate::stream::OutputStream;
 use std::error::Error;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone, Debug)]
 pub struct ShellManager {
-    pub(crate) current_shell: usize,
+    pub(crate) current_shell: Arc<AtomicUsize>,
     pub(crate) shells: Arc<Mutex<Vec<Box<dyn Shell + Send>>>>,
 }
 
 impl ShellManager {
     pub fn basic(commands: CommandRegistry) -> Result<ShellManager, Box<dyn Error>> {
         Ok(ShellManager {
-            current_shell: 0,
+            current_shell: Arc::new(AtomicUsize::new(0)),
             shells: Arc::new(Mutex::new(vec![Box::new(FilesystemShell::basic(
                 commands,
             )?)])),
         })
     }
 
+    /// The index of the shell commands run in. Clones of the manager share it, so a command
+    /// that enters or leaves a shell part way through a pipeline is seen by the whole line.
+    pub fn current_shell(&self) -> usize {
+        self.current_shell.load(Ordering::SeqCst)
+    }
+
+    fn set_current_shell(&self, index: usize) {
+        self.current_shell.store(index, Ordering::SeqCst);
+    }
+
     pub fn insert_at_current(&mut self, shell: Box<dyn Shell + Send>) {
         self.shells.lock().unwrap().push(shell);
-        self.current_shell = self.shells.lock().unwrap().len() - 1;
+        let index = self.shells.lock().unwrap().len() - 1;
+        self.set_current_shell(index);
         self.set_path(self.path());
     }
 
@@ -38,16 +52,16 @@ impl ShellManager {
         {
             let mut shells = self.shells.lock().unwrap();
             if shells.len() > 0 {
-                if self.current_shell == shells.len() - 1 {
+                if self.current_shell() == shells.len() - 1 {
                     shells.pop();
                     let new_len = shells.len();
                     if new_len > 0 {
-                        self.current_shell = new_len - 1;
+                        self.set_current_shell(new_len - 1);
                     } else {
                         return;
                     }
                 } else {
-                    shells.remove(self.current_shell);
+                    shells.remove(self.current_shell());
                 }
             }
         }
@@ -59,17 +73,28 @@ impl ShellManager {
     }
 
     pub fn path(&self) -> String {
-        self.shells.lock().unwrap()[self.current_shell].path()
+        self.shells.lock().unwrap()[self.current_shell()].path()
+    }
+
+    /// The directory externals run in and files are saved to. A remote shell's path is a url,
+    /// so there it's the directory of the filesystem shell it was entered from.
+    pub fn filesystem_path(&self) -> PathBuf {
+        let path = self.path();
+
+        match Remote::parse(&path) {
+            Some(_) => std::env::current_dir().unwrap_or_else(|_| PathBuf::from("/")),
+            None => PathBuf::from(path),
+        }
     }
 
     pub fn pwd(&self, args: EvaluatedWholeStreamCommandArgs) -> Result<OutputStream, ShellError> {
         let env = self.shells.lock().unwrap();
 
-        env[self.current_shell].pwd(args)
+        env[self.current_shell()].pwd(args)
     }
 
     pub fn set_path(&mut self, path: String) {
-        self.shells.lock().unwrap()[self.current_shell].set_path(path)
+        self.shells.lock().unwrap()[self.current_shell()].set_path(path)
     }
 
     pub fn complete(
@@ -78,20 +103,20 @@ impl ShellManager {
         pos: usize,
         ctx: &rustyline::Context<'_>,
     ) -> Result<(usize, Vec<rustyline::completion::Pair>), rustyline::error::ReadlineError> {
-        self.shells.lock().unwrap()[self.current_shell].complete(line, pos, ctx)
+        self.shells.lock().unwrap()[self.current_shell()].complete(line, pos, ctx)
     }
 
     pub fn hint(&self, line: &str, pos: usize, ctx: &rustyline::Context<'_>) -> Option<String> {
-        self.shells.lock().unwrap()[self.current_shell].hint(line, pos, ctx)
+        self.shells.lock().unwrap()[self.current_shell()].hint(line, pos, ctx)
     }
 
     pub fn next(&mut self) {
         {
             let shell_len = self.shells.lock().unwrap().len();
-            if self.current_shell == (shell_len - 1) {
-                self.current_shell = 0;
+            if self.current_shell() == (shell_len - 1) {
+                self.set_current_shell(0);
             } else {
-                self.current_shell += 1;
+                self.set_current_shell(self.current_shell() + 1);
             }
         }
         self.set_path(self.path());
@@ -100,10 +125,10 @@ impl ShellManager {
     pub fn prev(&mut self) {
         {
             let shell_len = self.shells.lock().unwrap().len();
-            if self.current_shell == 0 {
-                self.current_shell = shell_len - 1;
+            if self.current_shell() == 0 {
+                self.set_current_shell(shell_len - 1);
             } else {
-                self.current_shell -= 1;
+                self.set_current_shell(self.current_shell() - 1);
             }
         }
         self.set_path(self.path());
@@ -112,23 +137,19 @@ impl ShellManager {
     pub fn homedir(&self) -> Option<PathBuf> {
         let env = self.shells.lock().unwrap();
 
-        env[self.current_shell].homedir()
+        env[self.current_shell()].homedir()
     }
 
-    pub fn ls(
-        &self,
-        path: Option<Tagged<PathBuf>>,
-        command_tag: Tag,
-    ) -> Result<OutputStream, ShellError> {
+    pub fn ls(&self, args: LsArgs, command_tag: Tag) -> Result<OutputStream, ShellError> {
         let env = self.shells.lock().unwrap();
 
-        env[self.current_shell].ls(path, command_tag)
+        env[self.current_shell()].ls(args, command_tag)
     }
 
     pub fn cd(&self, args: EvaluatedWholeStreamCommandArgs) -> Result<OutputStream, ShellError> {
         let env = self.shells.lock().unwrap();
 
-        env[self.current_shell].cd(args)
+        env[self.current_shell()].cd(args)
     }
 
     pub fn cp(
@@ -140,8 +161,8 @@ impl ShellManager {
 
         match env {
             Ok(x) => {
-                let path = x[self.current_shell].path();
-                x[self.current_shell].cp(args, context.name, &path)
+                let path = x[self.current_shell()].path();
+                x[self.current_shell()].cp(args, context.name, &path)
             }
             Err(e) => Err(ShellError::labeled_error(
                 format!("Internal error: could not lock {}", e),
@@ -160,8 +181,8 @@ impl ShellManager {
 
         match env {
             Ok(x) => {
-                let path = x[self.current_shell].path();
-                x[self.current_shell].rm(args, context.name, &path)
+                let path = x[self.current_shell()].path();
+                x[self.current_shell()].rm(args, context.name, &path)
             }
             Err(e) => Err(ShellError::labeled_error(
                 format!("Internal error: could not lock {}", e),
@@ -180,8 +201,8 @@ impl ShellManager {
 
         match env {
             Ok(x) => {
-                let path = x[self.current_shell].path();
-                x[self.current_shell].mkdir(args, context.name, &path)
+                let path = x[self.current_shell()].path();
+                x[self.current_shell()].mkdir(args, context.name, &path)
             }
             Err(e) => Err(ShellError::labeled_error(
                 format!("Internal error: could not lock {}", e),
@@ -200,8 +221,8 @@ impl ShellManager {
 
         match env {
             Ok(x) => {
-                let path = x[self.current_shell].path();
-                x[self.current_shell].mv(args, context.name, &path)
+                let path = x[self.current_shell()].path();
+                x[self.current_shell()].mv(args, context.name, &path)
             }
             Err(e) => Err(ShellError::labeled_error(
                 format!("Internal error: could not lock {}", e),
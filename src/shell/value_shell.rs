@@ -1,5 +1,6 @@
 use crate::commands::command::EvaluatedWholeStreamCommandArgs;
 use crate::commands::cp::CopyArgs;
+use crate::commands::ls::LsArgs;
 use crate::commands::mkdir::MkdirArgs;
 use crate::commands::mv::MoveArgs;
 use crate::commands::rm::RemoveArgs;
@@ -89,7 +90,7 @@ impl Shell for ValueShell {
 
     fn ls(
         &self,
-        target: Option<Tagged<PathBuf>>,
+        LsArgs { path: target, .. }: LsArgs,
         command_name: Tag,
     ) -> Result<OutputStream, ShellError> {
         let mut full_path = PathBuf::from(self.path());
@@ -444,10 +444,18 @@ mod tests {
                     loc: fixtures().join("caco3_plastics.tsv"),
                     at: 0
                 },
+                Res {
+                    loc: fixtures().join("calendar.ics"),
+                    at: 0
+                },
                 Res {
                     loc: fixtures().join("cargo_sample.toml"),
                     at: 0
                 },
+                Res {
+                    loc: fixtures().join("contacts.vcf"),
+                    at: 0
+                },
                 Res {
                     loc: fixtures().join("jonathan.xml"),
                     at: 0
@@ -460,14 +468,42 @@ mod tests {
                     loc: fixtures().join("sample.db"),
                     at: 0
                 },
+                Res {
+                    loc: fixtures().join("sample.eml"),
+                    at: 0
+                },
                 Res {
                     loc: fixtures().join("sample.ini"),
                     at: 0
                 },
+                Res {
+                    loc: fixtures().join("sample.parquet"),
+                    at: 0
+                },
+                Res {
+                    loc: fixtures().join("sample.plist"),
+                    at: 0
+                },
+                Res {
+                    loc: fixtures().join("sample.ron"),
+                    at: 0
+                },
                 Res {
                     loc: fixtures().join("sample.url"),
                     at: 0
                 },
+                Res {
+                    loc: fixtures().join("sample_binary.plist"),
+                    at: 0
+                },
+                Res {
+                    loc: fixtures().join("sample_data.ods"),
+                    at: 0
+                },
+                Res {
+                    loc: fixtures().join("sample_data.xlsx"),
+                    at: 0
+                },
                 Res {
                     loc: fixtures().join("sgml_description.json"),
                     at: 0
@@ -113,3 +113,68 @@ fn removes_configuration_value() {
 
     h::delete_file_at(nu::config_path().unwrap().join("test_5.toml"));
 }
+
+#[test]
+fn checks_configuration_reports_invalid_edit_mode() {
+    Playground::setup("config_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "test_6.toml",
+            r#"
+                    edit_mode = "nano"
+                    table_mode = "light"
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "config --load test_6.toml --check | where severity == error | get key | echo $it"
+        );
+
+        assert_eq!(actual, "edit_mode");
+    });
+
+    h::delete_file_at(nu::config_path().unwrap().join("test_6.toml"));
+}
+
+#[test]
+fn checks_configuration_warns_on_unknown_keys() {
+    Playground::setup("config_test_7", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "test_7.toml",
+            r#"
+                    edit_mode = "vi"
+                    edti_mode = "emacs"
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "config --load test_7.toml --check | get severity | echo $it"
+        );
+
+        assert_eq!(actual, "warning");
+    });
+
+    h::delete_file_at(nu::config_path().unwrap().join("test_7.toml"));
+}
+
+#[test]
+fn checks_configuration_reports_invalid_filesize_format() {
+    Playground::setup("config_test_8", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "test_8.toml",
+            r#"
+                    filesize_format = "kilo"
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "config --load test_8.toml --check | get severity | echo $it"
+        );
+
+        assert_eq!(actual, "error");
+    });
+
+    h::delete_file_at(nu::config_path().unwrap().join("test_8.toml"));
+}
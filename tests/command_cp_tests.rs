@@ -46,6 +46,18 @@ fn error_if_attempting_to_copy_a_directory_to_another_directory() {
     });
 }
 
+#[test]
+fn dry_run_fails_like_copying_a_directory_without_recursive_flag() {
+    Playground::setup("cp_test_8", |dirs, _| {
+        let actual = nu_error!(
+            cwd: dirs.formats(),
+            "cp ../formats {} --dry-run", dirs.test()
+        );
+
+        assert!(actual.contains("is a directory (not copied)"));
+    });
+}
+
 #[test]
 fn copies_the_directory_inside_directory_if_path_to_copy_is_directory_and_with_recursive_flag() {
     Playground::setup("cp_test_4", |dirs, sandbox| {
@@ -73,3 +73,19 @@ fn knows_the_filesystems_entered() {
         ));
     })
 }
+
+#[test]
+fn saves_to_the_filesystem_from_a_remote_shell() {
+    Playground::setup("enter_test_2", |dirs, _| {
+        nu!(
+            cwd: dirs.test(),
+            r#"
+                enter http://127.0.0.1:9/api
+                echo hello | save greeting.txt
+                exit
+            "#
+        );
+
+        assert_eq!(h::file_contents(dirs.test().join("greeting.txt")), "hello");
+    })
+}
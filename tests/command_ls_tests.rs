@@ -85,3 +85,73 @@ fn ls_lists_regular_files_using_question_mark_wildcard() {
         assert_eq!(actual, "30");
     })
 }
+
+#[cfg(unix)]
+#[test]
+fn ls_follows_symlinked_directories_only_when_asked() {
+    Playground::setup("ls_test_4", |dirs, sandbox| {
+        sandbox
+            .within("original")
+            .with_files(vec![EmptyFile("andres.txt"), EmptyFile("jonathan.txt")]);
+
+        std::os::unix::fs::symlink(dirs.test().join("original"), dirs.test().join("link"))
+            .expect("can not create symlink");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls link | get type | echo $it"
+        );
+
+        assert_eq!(actual, "Symlink");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls link --follow-symlinks | get type | to-json"
+        );
+
+        assert_eq!(actual, r#"["File","File"]"#);
+    })
+}
+
+#[test]
+fn ls_lists_files_recursively_with_double_asterisk() {
+    Playground::setup("ls_test_5", |dirs, sandbox| {
+        sandbox
+            .with_files(vec![EmptyFile("los.1.txt"), EmptyFile("arepas.1.clu")])
+            .within("tres")
+            .within("amigos")
+            .with_files(vec![EmptyFile("tres.2.txt"), EmptyFile("amigos.3.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                ls **/*.txt
+                | get name
+                | lines
+                | split-column "."
+                | get Column2
+                | str --to-int
+                | sum
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "6");
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn ls_full_adds_permissions_column() {
+    Playground::setup("ls_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("andres.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls --full | get permissions | echo $it"
+        );
+
+        assert_eq!(actual.len(), 9);
+        assert!(actual.starts_with("rw"));
+    })
+}
@@ -181,3 +181,21 @@ fn moves_using_a_glob() {
         ));
     })
 }
+
+#[test]
+fn dry_run_fails_like_moving_directories_using_a_glob() {
+    Playground::setup("mv_test_9", |dirs, sandbox| {
+        sandbox
+            .within("meals")
+            .with_files(vec![EmptyFile("arepa.txt")])
+            .mkdir("meals/sides")
+            .mkdir("expected");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "mv meals/* expected --dry-run"
+        );
+
+        assert!(actual.contains("Rename aborted (directories found)"));
+    })
+}
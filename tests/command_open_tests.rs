@@ -146,6 +146,110 @@ fn open_can_parse_sqlite() {
     assert_eq!(actual, "hello");
 }
 
+#[test]
+fn open_can_parse_xlsx() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample_data.xlsx
+            | get Staff
+            | nth 1
+            | get Column0
+            | echo $it"#
+    ));
+
+    assert_eq!(actual, "Yehuda");
+}
+
+#[test]
+fn from_xlsx_selects_a_sheet() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample_data.xlsx --raw
+            | from-xlsx --sheet Sales
+            | nth 2
+            | get Column1
+            | echo $it"#
+    ));
+
+    assert_eq!(actual, "60");
+}
+
+#[test]
+fn open_can_parse_ods() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample_data.ods
+            | get Staff
+            | nth 1
+            | get Column0
+            | echo $it"#
+    ));
+
+    assert_eq!(actual, "Yehuda");
+}
+
+#[test]
+fn from_ods_selects_a_sheet() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sample_data.ods --raw
+            | from-ods --sheet Sales
+            | nth 2
+            | get Column1
+            | echo $it"#
+    ));
+
+    assert_eq!(actual, "60");
+}
+
+#[test]
+fn open_can_parse_ron() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.ron | get difficulty_options.start_difficulty | echo $it"
+    );
+
+    assert_eq!(actual, "Easy");
+}
+
+#[test]
+fn open_can_parse_plist() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.plist | get RecentFiles | nth 1 | echo $it"
+    );
+
+    assert_eq!(actual, "todo.md");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample_binary.plist | get NSNavLastRootDirectory | echo $it"
+    );
+
+    assert_eq!(actual, "~/Documents");
+}
+
+#[test]
+fn open_can_parse_parquet() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.parquet | nth 2 | get name | echo $it"
+    );
+
+    assert_eq!(actual, "Yehuda");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.parquet | first 1 | get age | echo $it"
+    );
+
+    assert_eq!(actual, "30");
+}
+
 #[test]
 fn open_can_parse_toml() {
     let actual = nu!(
@@ -218,6 +322,50 @@ fn open_can_parse_utf16_ini() {
     assert_eq!(actual, "-236")
 }
 
+#[test]
+fn open_can_parse_ics() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open calendar.ics | first 1 | get summary | echo $it"
+    );
+
+    assert_eq!(actual, "Nu release planning");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open calendar.ics | first 1 | get valarm.trigger | echo $it"
+    );
+
+    assert_eq!(actual, "-PT15M");
+}
+
+#[test]
+fn open_can_parse_vcf() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open contacts.vcf | last 1 | get email | echo $it"
+    );
+
+    assert_eq!(actual, "jonathan@nushell.sh");
+}
+
+#[test]
+fn open_can_parse_eml() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.eml | get subject | echo $it"
+    );
+
+    assert_eq!(actual, "Nu 0.4 release notes");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open sample.eml --raw | from-eml --preview-body 2 | get body | echo $it"
+    );
+
+    assert_eq!(actual, "Hi");
+}
+
 #[test]
 fn errors_if_file_not_found() {
     let actual = nu_error!(
@@ -160,3 +160,20 @@ fn rm_errors_if_attempting_to_delete_two_dot_as_argument() {
         assert!(actual.contains("may not be removed"));
     })
 }
+
+#[test]
+fn rm_dry_run_reports_the_file_without_removing_it() {
+    Playground::setup("rm_test_9", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("i_will_survive.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "rm i_will_survive.txt --dry-run | get operation | echo $it"
+        );
+
+        let path = dirs.test().join("i_will_survive.txt");
+
+        assert!(path.exists());
+        assert_eq!(actual, "remove");
+    })
+}
@@ -31,6 +31,21 @@ fn first_gets_first_rows_by_amount() {
     })
 }
 
+#[test]
+fn first_gets_a_percentage_of_rows() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c d"
+            | split-row " "
+            | first 50%
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["a","b"]"#);
+}
+
 #[test]
 fn first_requires_an_amount() {
     Playground::setup("first_test_2", |dirs, _| {
@@ -42,6 +57,138 @@ fn first_requires_an_amount() {
     })
 }
 
+#[test]
+fn each_binds_the_row_index_when_numbered() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c"
+            | split-row " "
+            | each --numbered { echo $index $it }
+            | split-column " " index item
+            | format "{index}: {item}"
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["0: a","1: b","2: c"]"#);
+}
+
+#[test]
+fn if_runs_the_block_matching_the_condition() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open cargo_sample.toml | get package | if $it.name == nu { yes } { no } | echo $it"
+    );
+
+    assert_eq!(actual, "yes");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open cargo_sample.toml | get package | if $it.name == nushell { yes } { no } | echo $it"
+    );
+
+    assert_eq!(actual, "no");
+}
+
+#[test]
+fn each_flattens_lists_returned_by_the_block() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | each { $it.authors }
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "Yehuda Katz <wycats@gmail.com>");
+}
+
+#[test]
+fn append_adds_each_value_from_a_list() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "1 2"
+            | split-row " "
+            | append [3 4 5]
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["1","2",3,4,5]"#);
+}
+
+#[test]
+fn prepend_adds_a_single_value() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "2 3"
+            | split-row " "
+            | prepend 1
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"[1,"2","3"]"#);
+}
+
+#[test]
+fn prepend_and_append_add_the_output_of_a_block() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "2 3"
+            | split-row " "
+            | prepend { 0 + 1 }
+            | append { 2 * 2 }
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"[1,"2","3",4]"#);
+}
+
+#[test]
+fn append_evaluates_a_block_with_its_variables() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "let last = 3\necho 1 | append { $last } | to-json"
+    );
+
+    assert_eq!(actual, r#"["1",3]"#);
+}
+
+#[test]
+fn reduce_binds_the_accumulator_and_row_index() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c"
+            | split-row " "
+            | reduce --fold 0 { $index }
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "2");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c"
+            | split-row " "
+            | reduce { $acc }
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "a");
+}
+
 #[test]
 fn get() {
     Playground::setup("get_test_1", |dirs, sandbox| {
@@ -180,6 +327,58 @@ fn save_can_write_out_csv() {
     })
 }
 
+#[test]
+fn save_writes_converted_text_as_it_is() {
+    Playground::setup("save_test_5", |dirs, _| {
+        let expected_file = dirs.test().join("cargo_sample.json");
+
+        nu!(
+            cwd: dirs.root(),
+            "open {}/cargo_sample.toml | get package | pick name edition | to-json --pretty 2 | save save_test_5/cargo_sample.json",
+            dirs.formats()
+        );
+
+        let actual = h::file_contents(expected_file);
+        assert_eq!(actual, "{\n  \"name\": \"nu\",\n  \"edition\": \"2018\"\n}");
+    })
+}
+
+#[test]
+fn save_writes_html_converted_once() {
+    Playground::setup("save_test_6", |dirs, _| {
+        let expected_file = dirs.test().join("cargo_sample.html");
+
+        nu!(
+            cwd: dirs.root(),
+            "open {}/cargo_sample.toml | get package | pick name | to-html | save save_test_6/cargo_sample.html",
+            dirs.formats()
+        );
+
+        let actual = h::file_contents(expected_file);
+        assert_eq!(
+            actual,
+            "<table>\n<tr><th>name</th></tr>\n<tr><td>nu</td></tr>\n</table>"
+        );
+    })
+}
+
+#[test]
+fn save_writes_binary_data_as_it_was_read() {
+    Playground::setup("save_test_binary", |dirs, _| {
+        let expected_file = dirs.test().join("copy.bin");
+
+        nu!(
+            cwd: dirs.root(),
+            "open {}/sample.db --raw | save save_test_binary/copy.bin",
+            dirs.formats()
+        );
+
+        let original = std::fs::read(dirs.formats().join("sample.db")).unwrap();
+        let copy = std::fs::read(expected_file).unwrap();
+        assert_eq!(copy, original);
+    })
+}
+
 // This test is more tricky since we are checking for binary output. The output rendered in ASCII is (roughly):
 // �authors+0Yehuda Katz <wycats@gmail.com>descriptionA shell for the GitHub eraedition2018licenseISCnamenuversion0.2.0
 // It is not valid utf-8, so this is just an approximation.
@@ -211,3 +410,500 @@ fn save_can_write_out_bson() {
         );
     })
 }
+
+#[cfg(unix)]
+#[test]
+fn save_can_write_out_msgpack() {
+    Playground::setup("save_test_msgpack", |dirs, _| {
+        nu!(
+            cwd: dirs.root(),
+            "open {}/cargo_sample.toml | get package | save save_test_msgpack/cargo_sample.msgpack",
+            dirs.formats()
+        );
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open cargo_sample.msgpack | get name | echo $it"
+        );
+
+        assert_eq!(actual, "nu");
+    })
+}
+
+#[test]
+fn save_sets_the_requested_file_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    Playground::setup("save_test_4", |dirs, _| {
+        let expected_file = dirs.test().join("run.sh");
+
+        nu!(
+            cwd: dirs.root(),
+            r#"echo "ls" | save save_test_4/run.sh --mode 750"#
+        );
+
+        let mode = std::fs::metadata(expected_file)
+            .unwrap()
+            .permissions()
+            .mode();
+
+        assert_eq!(mode & 0o777, 0o750);
+    })
+}
+
+#[test]
+fn skip_until_drops_rows_until_the_condition_matches() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "1 2 3 4"
+            | split-row " "
+            | skip-until { $it == "3" }
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["3","4"]"#);
+}
+
+#[test]
+fn skip_until_reports_an_error_in_its_condition() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "1 2 3 4"
+            | split-row " "
+            | skip-until { $it / 0 == 1 }
+            | to-json
+        "#
+    ));
+
+    assert!(actual.contains("Division by zero"));
+}
+
+#[test]
+fn update_computes_the_new_value_from_the_row_with_a_block() {
+    Playground::setup("update_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.csv",
+            r#"
+                first_name,last_name,nickname
+                Andrés,Robalino,andres
+                Jonathan,Turner,jt
+                Yehuda,Katz,wycats
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.csv
+                | update first_name { $it.nickname }
+                | get first_name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["andres","jt","wycats"]"#);
+    })
+}
+
+#[test]
+fn update_computes_the_new_value_with_arithmetic() {
+    Playground::setup("update_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "groceries.json",
+            r#"[{"item": "apple", "price": 10}, {"item": "pear", "price": 20}]"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open groceries.json
+                | update price { $it.price * 2 }
+                | get price
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, "[20,40]");
+    })
+}
+
+#[test]
+fn update_replaces_a_nested_column() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | update package.version "0.2.0"
+            | get package.version
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "0.2.0");
+}
+
+#[test]
+fn insert_computes_the_inserted_value_from_the_row_with_a_block() {
+    Playground::setup("insert_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "groceries.csv",
+            r#"
+                item,price,qty
+                apple,2,3
+                pear,4,1
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open groceries.csv
+                | insert total { $it.price }
+                | get total
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, "[2,4]");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open groceries.csv
+                | insert qty { $it.price } --force
+                | get qty
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, "[2,4]");
+    })
+}
+
+#[test]
+fn insert_evaluates_a_block_for_each_row() {
+    Playground::setup("insert_test_2", |dirs, sandbox| {
+        sandbox.mkdir("src").with_files(vec![EmptyFile("los.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                ls
+                | sort-by name
+                | insert label { echo $it.type }
+                | get label
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["File","Directory"]"#);
+    })
+}
+
+#[test]
+fn autoview_shows_a_single_row_as_key_value_pairs() {
+    Playground::setup("autoview_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "package.json",
+            r#"
+                {"name": "nu", "version": "0.3.0"}
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open package.json"
+        );
+
+        assert_eq!(actual, "name    : nuversion : 0.3.0");
+    })
+}
+
+#[test]
+fn autoview_cuts_long_tables_off_with_a_notice() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        r#"echo "a b c" | split-row " " | autoview --max-rows 2"#
+    );
+
+    assert!(actual.contains("b"));
+    assert!(!actual.contains("c"));
+    assert!(actual.ends_with("... and 1 more row"));
+}
+
+#[test]
+fn alias_expands_with_parameters_substituted() {
+    Playground::setup("alias_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("los.txt"),
+            EmptyFile("tres.txt"),
+            EmptyFile("amigos.csv"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "alias names [n] = ls | sort-by name | get name | first $n\nnames 2 | to-json"
+        );
+
+        assert_eq!(actual, r#"["amigos.csv","los.txt"]"#);
+    })
+}
+
+#[test]
+fn alias_expands_every_use_in_a_pipeline() {
+    Playground::setup("alias_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            EmptyFile("los.txt"),
+            EmptyFile("tres.txt"),
+            EmptyFile("amigos.csv"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "alias firsts = first 2\nls | sort-by name | get name | firsts | firsts | to-json"
+        );
+
+        assert_eq!(actual, r#"["amigos.csv","los.txt"]"#);
+    })
+}
+
+#[test]
+fn nu_variable_exposes_the_environment() {
+    std::env::set_var("NU_ENV_VARIABLE_TEST", "arepas");
+
+    let actual = nu!(cwd: ".", "echo $nu.env.NU_ENV_VARIABLE_TEST");
+
+    assert_eq!(actual, "arepas");
+}
+
+#[test]
+fn let_binds_a_variable_for_later_lines_and_blocks() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "let package = nu\nopen cargo_sample.toml | get package | where name == $package | get edition | echo $it"
+    );
+
+    assert_eq!(actual, "2018");
+}
+
+#[test]
+fn du_lists_directories_except_excluded_ones() {
+    Playground::setup("du_test_1", |dirs, sandbox| {
+        sandbox.mkdir("src/skipped").mkdir("src/nested");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "du src --exclude skip* | get directories | get path | echo $it"
+        );
+
+        assert!(actual.contains("nested"));
+        assert!(!actual.contains("skipped"));
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn du_follows_symlinks_around_a_cycle_once() {
+    Playground::setup("du_test_2", |dirs, sandbox| {
+        sandbox.mkdir("src/nested");
+        std::os::unix::fs::symlink("..", dirs.test().join("src/nested/parent")).unwrap();
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "du src --follow-symlinks | get directories | get path | echo $it"
+        );
+
+        assert!(actual.contains("nested"));
+        assert!(!actual.contains("parent"));
+    })
+}
+
+#[test]
+fn echo_evaluates_arithmetic_with_precedence() {
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "echo 2 + 2 * 3 | echo $it"
+    );
+
+    assert_eq!(actual, "8");
+
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "echo 7 mod 4 - 10 / 4 | echo $it"
+    );
+
+    assert_eq!(actual, "0.5");
+}
+
+#[test]
+fn where_compares_against_arithmetic() {
+    Playground::setup("where_math_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContent("small.txt", "hello"),
+            FileWithContent("large.txt", "hello, this is a much longer file"),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls | where size > 10 + 5 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "large.txt");
+    })
+}
+
+#[test]
+fn where_compares_against_the_output_of_a_command() {
+    Playground::setup("where_math_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("recent.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls | where modified > (date now) - 7day | get name | echo $it"
+        );
+
+        assert_eq!(actual, "recent.txt");
+    })
+}
+
+#[test]
+fn parenthesized_commands_see_the_row_of_the_block_around_them() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b"
+            | split-row " "
+            | each { echo (echo $it) }
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["a","b"]"#);
+}
+
+#[test]
+fn echo_evaluates_parenthesized_arithmetic_first() {
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "echo (1 + 2) * 3 | echo $it"
+    );
+
+    assert_eq!(actual, "9");
+}
+
+#[test]
+fn date_arithmetic_reports_dates_out_of_range() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "echo (date now) + 100000000000000"
+    );
+
+    assert!(actual.contains("date out of range"));
+}
+
+#[test]
+fn duration_arithmetic_reports_durations_out_of_range() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "echo 100000000000000day + 100000000000000day"
+    );
+
+    assert!(actual.contains("duration out of range"));
+}
+
+#[test]
+fn date_accepts_only_one_timezone() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "date --utc --local"
+    );
+
+    assert!(actual.contains("Can only use one timezone"));
+}
+
+#[test]
+fn echo_leaves_operators_between_words_alone() {
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "echo a - b | echo $it"
+    );
+
+    assert_eq!(actual, "a - b");
+}
+
+#[cfg(unix)]
+#[test]
+fn cp_reads_a_slash_as_the_destination() {
+    Playground::setup("cp_slash_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("notes.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "cp notes.txt / --dry-run | get destination | echo $it"
+        );
+
+        assert_eq!(actual, "/notes.txt");
+    })
+}
+
+#[test]
+fn watch_shows_the_output_of_each_run() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::{Command, Stdio};
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    Playground::setup("watch_test_1", |dirs, sandbox| {
+        sandbox.mkdir("src");
+
+        let mut process = Command::new(h::executable_path())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("couldn't run nu");
+
+        let commands = format!(
+            "cd {}\nwatch src {{ ls src | get name | str --upcase }}\n",
+            h::in_directory(dirs.test())
+        );
+
+        process
+            .stdin
+            .as_mut()
+            .expect("couldn't open stdin")
+            .write_all(commands.as_bytes())
+            .expect("couldn't write to stdin");
+
+        // The watch never ends, so read its output as it comes
+        let stdout = process.stdout.take().expect("couldn't open stdout");
+        let (lines_tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if lines_tx.send(line.unwrap_or_default()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let started = Instant::now();
+        let mut seen = false;
+
+        while !seen && started.elapsed() < Duration::from_secs(20) {
+            std::fs::write(dirs.test().join("src/changed.txt"), "changed").expect("couldn't write");
+
+            while let Ok(line) = lines.recv_timeout(Duration::from_secs(1)) {
+                if line.contains("CHANGED.TXT") {
+                    seen = true;
+                    break;
+                }
+            }
+        }
+
+        let _ = process.kill();
+
+        assert!(seen);
+    })
+}
@@ -1,5 +1,7 @@
 mod helpers;
 
+use helpers::Playground;
+
 #[test]
 fn external_command() {
     let actual = nu!(
@@ -9,3 +11,41 @@ fn external_command() {
 
     assert!(actual.contains("1"));
 }
+
+#[cfg(unix)]
+#[test]
+fn background_job_output_is_retrieved_with_fg() {
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "echo $((20 + 22)) &\nfg 1 | trim | echo $it"
+    );
+
+    assert!(actual.ends_with("42"));
+}
+
+#[cfg(unix)]
+#[test]
+fn internal_commands_stop_reading_external_output_early() {
+    let actual = nu!(
+        cwd: "tests/fixtures",
+        "yes | lines | first 3 | to-json"
+    );
+
+    assert_eq!(actual, r#"["y","y","y"]"#);
+}
+
+#[cfg(unix)]
+#[test]
+fn external_output_that_is_not_utf8_is_an_error() {
+    Playground::setup("external_test_1", |dirs, _| {
+        std::fs::write(dirs.test().join("latin1.txt"), b"first\ncaf\xe9\nlast\n")
+            .expect("can not create file");
+
+        let actual = nu_error!(
+            cwd: dirs.test(),
+            "cat latin1.txt | lines | echo $it"
+        );
+
+        assert!(actual.contains("Could not read the output of an external command"));
+    })
+}
@@ -90,3 +90,63 @@ fn converts_to_int() {
 
     assert_eq!(actual, "2509000000");
 }
+
+#[test]
+fn trims() {
+    Playground::setup("plugin_str_test_4", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sample.toml",
+            r#"
+                    [dependency]
+                    name = "   nu   "
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sample.toml | str dependency.name --trim | get dependency.name | echo $it"
+        );
+
+        assert_eq!(actual, "nu");
+    })
+}
+
+#[test]
+fn substrings() {
+    Playground::setup("plugin_str_test_5", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sample.toml",
+            r#"
+                    [package]
+                    name = "nushell"
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sample.toml | str package.name --substring \"0,2\" | get package.name | echo $it"
+        );
+
+        assert_eq!(actual, "nu");
+    })
+}
+
+#[test]
+fn replaces() {
+    Playground::setup("plugin_str_test_6", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "sample.toml",
+            r#"
+                    [package]
+                    name = "nushell"
+                "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sample.toml | str package.name --replace wykittenshell | get package.name | echo $it"
+        );
+
+        assert_eq!(actual, "wykittenshell");
+    })
+}
@@ -43,6 +43,27 @@ fn converts_structured_table_to_csv_text() {
     })
 }
 
+#[test]
+fn converts_csv_rows_only_as_far_as_they_are_needed() {
+    Playground::setup("filter_from_csv_test_streaming", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.csv",
+            r#"
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,1,too,many,fields
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open los_tres_caballeros.csv | first 1 | get first_name | echo $it"
+        );
+
+        assert_eq!(actual, "Andrés");
+    })
+}
+
 #[test]
 fn converts_structured_table_to_csv_text_skipping_headers_after_conversion() {
     Playground::setup("filter_to_csv_test_2", |dirs, sandbox| {
@@ -129,6 +150,164 @@ fn converts_from_csv_text_skipping_headers_to_structured_table() {
     })
 }
 
+#[test]
+fn converts_from_csv_text_with_custom_separator_and_preamble() {
+    Playground::setup("filter_from_csv_test_options", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_amigos.txt",
+            r#"
+                exported from the spreadsheet
+                first_name;last_name;rusty_luck
+                Andrés;Robalino;1
+                Jonathan;Turner;2
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-csv --separator ';' --skip 1 --no-infer
+                | describe
+                | where column == rusty_luck
+                | get type
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "string");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-csv --separator ';' --skip 1
+                | last 1
+                | to-csv --separator ';' --headerless
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Jonathan;Turner;2");
+    })
+}
+
+#[test]
+fn converts_from_csv_text_inferring_integer_columns() {
+    Playground::setup("filter_from_csv_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_amigos.txt",
+            r#"
+                first_name,last_name,rusty_luck
+                Andrés,Robalino,1
+                Jonathan,Turner,2
+                Yehuda,Katz,3
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-csv
+                | where rusty_luck > 1
+                | get rusty_luck
+                | sum
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "5");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-csv
+                | describe
+                | where column == rusty_luck
+                | get type
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "int");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_amigos.txt
+                | from-csv --infer-types
+                | get rusty_luck
+                | sum
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "6");
+    })
+}
+
+#[test]
+fn detects_columns_from_aligned_text() {
+    Playground::setup("filter_detect_columns_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "sizes.txt",
+            r#"
+                name        size
+                cli.rs        10
+                prelude.rs  1200
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open sizes.txt
+                | detect-columns
+                | nth 0
+                | get size
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "10");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "open sizes.txt | detect-columns | nth 1 | get name | echo $it"
+        );
+
+        assert_eq!(actual, "prelude.rs");
+    })
+}
+
+#[test]
+fn detects_columns_with_headers_of_several_words() {
+    Playground::setup("filter_detect_columns_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "dates.txt",
+            r#"
+                name        created at
+                cli.rs      Sep 1
+                prelude.rs  Oct 10
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open dates.txt
+                | detect-columns
+                | nth 1
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"{"name":"prelude.rs","created at":"Oct 10"}"#);
+    })
+}
+
 #[test]
 fn can_convert_table_to_json_text_and_from_json_text_back_into_table() {
     let actual = nu!(
@@ -171,6 +350,67 @@ fn converts_from_json_text_to_structured_table() {
     })
 }
 
+#[test]
+fn serializers_can_be_pretty_or_raw() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | pick name
+            | to-json --pretty 2
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, r#"{  "name": "nu"}"#);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | pick name edition
+            | to-yaml --raw
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, r#"{"name":"nu","edition":"2018"}"#);
+}
+
+#[test]
+fn converts_table_to_json_lines_and_back() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | pick origin
+            | first 2
+            | to-json --lines
+            | from-json --objects
+            | nth 1
+            | get origin
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "COLOMBIA");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | pick origin
+            | first 2
+            | to-json --lines
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["{\"origin\":\"SPAIN\"}","{\"origin\":\"COLOMBIA\"}"]"#);
+}
+
 #[test]
 fn converts_from_json_text_recognizing_objects_independendtly_to_structured_table() {
     Playground::setup("filter_from_json_test_2", |dirs, sandbox| {
@@ -374,211 +614,1404 @@ fn can_convert_table_to_bson_and_back_into_table() {
 }
 
 #[test]
-fn can_convert_table_to_sqlite_and_back_into_table() {
+fn can_convert_table_to_ron_and_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open sample.db
-            | to-sqlite
-            | from-sqlite
-            | get table_values
-            | nth 2
-            | get x
+            open sample.ron
+            | to-ron
+            | from-ron
+            | get window_title
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "hello");
+    assert_eq!(actual, "PAC-MAN");
 }
 
 #[test]
-fn can_convert_table_to_toml_text_and_from_toml_text_back_into_table() {
+fn can_convert_table_to_cbor_and_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open cargo_sample.toml
-            | to-toml
-            | from-toml
-            | get package.name
+            open sample.bson
+            | to-cbor
+            | from-cbor
+            | get root
+            | nth 1
+            | get b
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "nu");
+    assert_eq!(actual, "whel");
 }
 
 #[test]
-fn can_convert_table_to_yaml_text_and_from_yaml_text_back_into_table() {
+fn can_convert_table_to_msgpack_and_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open appveyor.yml
-            | to-yaml
-            | from-yaml
-            | get environment.global.PROJECT_NAME
+            open caco3_plastics.csv
+            | to-msgpack
+            | from-msgpack
+            | first 1
+            | get origin
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "nushell");
+    assert_eq!(actual, "SPAIN");
 }
 
 #[test]
-fn can_encode_and_decode_urlencoding() {
+fn can_query_sqlite_with_parameters() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
-            r#"
-                open sample.url
-                | to-url
-                | from-url
-                | get cheese
-                | echo $it
-            "#
+        r#"
+            open sample.db --raw
+            | query "select z from ints where z > ? and z < ? order by z" 40 1000
+            | get z
+            | to-json
+        "#
     ));
 
-    assert_eq!(actual, "comté");
-}
+    assert_eq!(actual, "[42,425]");
 
-#[test]
-fn can_sort_by_column() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open cargo_sample.toml --raw
-            | lines
-            | skip 1
-            | first 4
-            | split-column "="
-            | sort-by Column1
-            | skip 1
-            | first 1
-            | get Column1
-            | trim
+            open sample.db
+            | query "select count(*) as total from floats where f < ?" 4
+            | get total
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "description");
+    assert_eq!(actual, "2");
 }
 
 #[test]
-fn can_split_by_column() {
+fn can_convert_table_to_sqlite_and_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open cargo_sample.toml --raw
-            | lines
-            | skip 1
-            | first 1
-            | split-column "="
-            | get Column1
-            | trim
+            open sample.db
+            | to-sqlite
+            | from-sqlite
+            | get table_values
+            | nth 2
+            | get x
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "name");
+    assert_eq!(actual, "hello");
 }
 
 #[test]
-fn can_sum() {
+fn can_convert_table_to_toml_text_and_from_toml_text_back_into_table() {
     let actual = nu!(
         cwd: "tests/fixtures/formats", h::pipeline(
         r#"
-            open sgml_description.json
-            | get glossary.GlossDiv.GlossList.GlossEntry.Sections
-            | sum
+            open cargo_sample.toml
+            | to-toml
+            | from-toml
+            | get package.name
             | echo $it
         "#
     ));
 
-    assert_eq!(actual, "203")
+    assert_eq!(actual, "nu");
 }
 
 #[test]
-fn can_filter_by_unit_size_comparison() {
+fn can_convert_table_to_yaml_text_and_from_yaml_text_back_into_table() {
     let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | where size > 1kb | sort-by size | get name | first 1 | trim | echo $it"
-    );
-
-    assert_eq!(actual, "cargo_sample.toml");
-}
-
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open appveyor.yml
+            | to-yaml
+            | from-yaml
+            | get environment.global.PROJECT_NAME
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "nushell");
+}
+
+#[test]
+fn can_convert_yaml_documents_to_yaml_text_and_back_into_documents() {
+    Playground::setup("filter_yaml_documents_test", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "caballeros.yaml",
+            r#"
+                ---
+                name: Andrés
+                ---
+                name: Jonathan
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open caballeros.yaml
+                | to-yaml --documents
+                | from-yaml
+                | nth 1
+                | get name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Jonathan");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open caballeros.yaml
+                | to-yaml --documents --raw
+                | from-yaml
+                | get name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés","Jonathan"]"#);
+    })
+}
+
+#[test]
+fn from_yaml_splits_documents_only_where_the_yaml_parser_does() {
+    Playground::setup("filter_yaml_documents_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "notes.yaml",
+            "name: Andrés\nnote: |\n  ---\n  not a new document\n...\n---\nname: Jonathan\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open notes.yaml --raw
+                | from-yaml
+                | get name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés","Jonathan"]"#);
+    })
+}
+
+#[test]
+fn from_yaml_names_columns_after_scalar_keys() {
+    Playground::setup("filter_yaml_documents_test_3", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "ranks.yaml",
+            "1: Andrés\ntrue: Jonathan\n---\n2: Yehuda\n",
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open ranks.yaml --raw
+                | from-yaml
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"[{"1":"Andrés","true":"Jonathan"},{"2":"Yehuda"}]"#);
+    })
+}
+
+#[test]
+fn can_encode_and_decode_urlencoding() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+            r#"
+                open sample.url
+                | to-url
+                | from-url
+                | get cheese
+                | echo $it
+            "#
+    ));
+
+    assert_eq!(actual, "comté");
+}
+
+#[test]
+fn can_sort_by_column() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw
+            | lines
+            | skip 1
+            | first 4
+            | split-column "="
+            | sort-by Column1
+            | skip 1
+            | first 1
+            | get Column1
+            | trim
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "description");
+}
+
+#[test]
+fn can_split_by_column() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw
+            | lines
+            | skip 1
+            | first 1
+            | split-column "="
+            | get Column1
+            | trim
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "name");
+}
+
+#[test]
+fn can_parse_lines_with_a_pattern() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw
+            | lines
+            | skip 1
+            | first 1
+            | parse '{key} = "{value}"'
+            | get value
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "nu");
+}
+
+#[test]
+fn can_parse_lines_with_a_regex() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml --raw
+            | lines
+            | parse --regex '^(?P<key>\w+) = "(?P<value>[\d.]+)"$'
+            | first 1
+            | get value
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "0.1.1");
+}
+
+#[test]
+fn can_format_rows_into_strings() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | format "{name} has license {license}"
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "nu has license ISC");
+}
+
+#[test]
+fn can_pivot_a_row_into_named_columns() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | pick name version
+            | pivot field value
+            | nth 1
+            | format "{field}={value}"
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "version=0.1.1");
+}
+
+#[test]
+fn can_pivot_using_the_first_column_as_headers() {
+    Playground::setup("pivot_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.csv",
+            r#"
+                first_name,last_name,rusty_at
+                Andrés,Robalino,10/11/2013
+                Jonathan,Turner,10/12/2013
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.csv
+                | pivot --header-row
+                | nth 0
+                | get Jonathan
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Turner");
+    })
+}
+
+#[test]
+fn can_flatten_nested_rows_and_tables() {
+    Playground::setup("flatten_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "crates.json",
+            r#"
+                [
+                    {"name": "nu", "meta": {"version": "0.3.0"}, "authors": ["jt", "wycats"]},
+                    {"name": "nu-source", "meta": {"version": "0.1.0"}, "authors": ["andres"]}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open crates.json
+                | flatten --all
+                | format "{name}@{version}:{authors}"
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["nu@0.3.0:jt","nu@0.3.0:wycats","nu-source@0.1.0:andres"]"#);
+    })
+}
+
+#[test]
+fn can_select_an_inclusive_range_of_rows() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c d e"
+            | split-row " "
+            | range 1..3
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["b","c","d"]"#);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c d e"
+            | split-row " "
+            | range ..1
+            | append [z]
+            | range 2..
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "z");
+}
+
+#[test]
+fn can_drop_the_last_rows() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c d e"
+            | split-row " "
+            | drop 2
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["a","b","c"]"#);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c"
+            | split-row " "
+            | drop
+            | to-json
+        "#
+    ));
+
+    assert_eq!(actual, r#"["a","b"]"#);
+}
+
+#[test]
+fn can_compact_rows_with_empty_columns() {
+    Playground::setup("compact_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "crates.json",
+            r#"
+                [
+                    {"name": "nu", "license": "MIT"},
+                    {"name": "nu-source", "license": ""},
+                    {"name": "nu-parser"}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open crates.json
+                | compact license
+                | get name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "nu");
+    })
+}
+
+#[test]
+fn can_default_missing_column_values() {
+    Playground::setup("default_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "crates.json",
+            r#"
+                [
+                    {"name": "nu", "owner": "jt"},
+                    {"name": "nu-source"}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open crates.json
+                | default owner unknown
+                | get owner
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["jt","unknown"]"#);
+    })
+}
+
+#[test]
+fn can_use_the_first_row_as_headers() {
+    Playground::setup("headers_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.txt",
+            r#"
+                first_name,last_name
+                Andrés,Robalino
+                Jonathan,Turner
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.txt
+                | lines
+                | split-column ","
+                | headers
+                | get last_name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Robalino","Turner"]"#);
+    })
+}
+
+#[test]
+fn can_wrap_values_into_a_named_column() {
+    Playground::setup("wrap_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("jonathan.txt"), EmptyFile("yehuda.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                ls
+                | sort-by name
+                | get name
+                | wrap filename
+                | get filename
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["jonathan.txt","yehuda.txt"]"#);
+    })
+}
+
+#[test]
+fn can_merge_the_columns_of_a_block_row_by_row() {
+    Playground::setup("merge_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContentToBeTrimmed(
+                "names.csv",
+                r#"
+                    first_name
+                    Andrés
+                    Jonathan
+                "#,
+            ),
+            FileWithContentToBeTrimmed(
+                "nicknames.csv",
+                r#"
+                    nickname
+                    andres
+                    jt
+                "#,
+            ),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open names.csv
+                | merge { (open nicknames.csv) }
+                | format "{first_name}={nickname}"
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés=andres","Jonathan=jt"]"#);
+    })
+}
+
+#[test]
+fn can_join_tables_on_a_key_column() {
+    Playground::setup("join_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![
+            FileWithContentToBeTrimmed(
+                "names.csv",
+                r#"
+                    id,first_name
+                    1,Andrés
+                    2,Jonathan
+                    3,Yehuda
+                "#,
+            ),
+            FileWithContentToBeTrimmed(
+                "nicknames.csv",
+                r#"
+                    id,nickname
+                    3,wycats
+                    1,andres
+                "#,
+            ),
+        ]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open names.csv
+                | join { (open nicknames.csv) } id
+                | format "{first_name}={nickname}"
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés=andres","Yehuda=wycats"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open names.csv
+                | join { (open nicknames.csv) } id --left
+                | get first_name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés","Jonathan","Yehuda"]"#);
+    })
+}
+
+#[test]
+fn can_generate_sequences_of_numbers_and_dates() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "seq 8 12 --step 2 --width | to-json"
+    );
+
+    assert_eq!(actual, r#"["08","10","12"]"#);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        r#"seq-date "2019-12-31" "2020-01-02" --format "%d/%m" | to-json"#
+    );
+
+    assert_eq!(actual, r#"["31/12","01/01","02/01"]"#);
+}
+
+#[test]
+fn can_generate_random_values_in_a_range() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "random 7..7 | echo $it"
+    );
+
+    assert_eq!(actual, "7");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "random --dice 3 --sides 1 | to-json"
+    );
+
+    assert_eq!(actual, "[1,1,1]");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "random --uuid | size | get chars | echo $it"
+    );
+
+    assert_eq!(actual, "36");
+}
+
+#[test]
+fn can_format_and_convert_dates() {
+    Playground::setup("date_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![EmptyFile("jonathan.txt")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"ls | get modified | date format "%Y" | size | get chars | echo $it"#
+        );
+
+        assert_eq!(actual, "4");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            r#"date to-timezone "+09:00" | get timezone | echo $it"#
+        );
+
+        assert_eq!(actual, "+09:00");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls | where modified - 1day < $it.modified | get name | echo $it"
+        );
+
+        assert_eq!(actual, "jonathan.txt");
+    })
+}
+
+#[test]
+fn durations_read_humanely_and_save_as_seconds() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open caco3_plastics.csv
+            | first 1
+            | insert wait 90min
+            | format-duration wait
+            | get wait
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "1h 30m");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "open caco3_plastics.csv | first 1 | insert wait 2day | get wait | to-json"
+    );
+
+    assert_eq!(actual, "172800");
+}
+
+#[test]
+fn hashes_strings_and_files() {
+    Playground::setup("hash_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent("hello.txt", "hello")]);
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "echo nushell | hash --md5"
+        );
+
+        assert_eq!(actual, "93769d01cf78fc2f42cd9107d20457ac");
+
+        let actual = nu!(
+            cwd: dirs.test(),
+            "ls | hash name --sha1 | get sha1 | echo $it"
+        );
+
+        assert_eq!(actual, "aaf4c61ddcc5e8a2dabede0f3b482cd9aea9434d");
+    })
+}
+
+#[test]
+fn encodes_and_decodes_base64_and_urls() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "echo 'nu shell?' | to-base64"
+    );
+
+    assert_eq!(actual, "bnUgc2hlbGw/");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "echo 'bnUgc2hlbGw/' | from-base64"
+    );
+
+    assert_eq!(actual, "nu shell?");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "echo 'nu shell?' | url-encode"
+    );
+
+    assert_eq!(actual, "nu%20shell%3F");
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "echo 'nu%20shell%3F' | url-decode"
+    );
+
+    assert_eq!(actual, "nu shell?");
+}
+
+#[test]
+fn reads_the_query_string_of_a_url() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "echo 'https://www.nushell.sh/search?q=shell&page=2' | from-url | get page | echo $it"
+    );
+
+    assert_eq!(actual, "2");
+}
+
+#[test]
+fn converts_from_ssv_text_to_structured_table() {
+    Playground::setup("filter_from_ssv_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "disks.txt",
+            r#"
+                Filesystem      Size  Used  Mounted on
+                /dev/sda1       100G   40G  /
+                /dev/sdb1       1.8T  900G  /mnt/backup disk
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open disks.txt
+                | from-ssv --aligned-columns --minimum-spaces 2
+                | last 1
+                | get "Mounted on"
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "/mnt/backup disk");
+    })
+}
+
+#[test]
+fn can_sum() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open sgml_description.json
+            | get glossary.GlossDiv.GlossList.GlossEntry.Sections
+            | sum
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, "203")
+}
+
+#[test]
+fn can_filter_by_unit_size_comparison() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | where size > 1kb | sort-by size | get name | first 1 | trim | echo $it"
+    );
+
+    assert_eq!(actual, "cargo_sample.toml");
+}
+
+#[test]
+fn can_get_last() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | sort-by name | last 1 | get name | trim | echo $it"
+    );
+
+    assert_eq!(actual, "utf16.ini");
+}
+
+#[test]
+fn can_get_reverse_first() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        "ls | sort-by name | reverse | first 1 | get name | trim | echo $it"
+    );
+
+    assert_eq!(actual, "utf16.ini");
+}
+
+#[test]
+fn can_flatten_a_table_into_separate_rows() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats",
+        r#"echo "[1, 2, 3]" | from-json --objects | flatten-stream | nth 2 | echo $it"#
+    );
+
+    assert_eq!(actual, "3");
+}
+
+#[test]
+fn embed() {
+    Playground::setup("embed_test", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.txt",
+            r#"
+                first_name,last_name
+                Andrés,Robalino
+                Jonathan,Turner
+                Yehuda,Katz
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.txt
+                | from-csv
+                | embed caballeros
+                | get caballeros
+                | nth 0
+                | get last_name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Robalino");
+    })
+}
+
+#[test]
+fn get() {
+    Playground::setup("get_test", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.txt",
+            r#"
+                first_name,last_name
+                Andrés,Robalino
+                Jonathan,Turner
+                Yehuda,Katz
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.txt
+                | from-csv
+                | nth 1
+                | get last_name
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "Turner");
+    })
+}
+
+#[test]
+fn picks_columns_whose_names_match_a_block() {
+    Playground::setup("filter_pick_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContent(
+            "los_tres_caballeros.json",
+            r#"{"meta_author": "Andrés", "meta_year": 2019, "title": "nu"}"#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.json
+                | pick { $it == meta_* }
+                | to-json
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, r#"{"meta_author":"Andrés","meta_year":2019}"#);
+    })
+}
+
+#[test]
+fn group_by_to_table_tags_each_row_with_its_group() {
+    Playground::setup("filter_group_by_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.csv",
+            r#"
+                first_name,last_name,rusty_at,type
+                Andrés,Robalino,10/11/2013,A
+                Jonathan,Turner,10/12/2013,B
+                Yehuda,Katz,10/11/2013,A
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.csv
+                | group-by rusty_at --to-table
+                | get group
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["10/11/2013","10/12/2013","10/11/2013"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.csv
+                | group-by type --to-table
+                | where group == A
+                | get last_name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Robalino","Katz"]"#);
+    })
+}
+
 #[test]
-fn can_get_last() {
-    let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | sort-by name | last 1 | get name | trim | echo $it"
-    );
+fn describe_detailed_profiles_a_nullable_column() {
+    Playground::setup("filter_describe_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "los_tres_caballeros.json",
+            r#"
+                [
+                    {"name": "Andrés", "rusty_luck": 1},
+                    {"name": "Jonathan", "rusty_luck": null},
+                    {"name": "Yehuda", "rusty_luck": 3}
+                ]
+            "#,
+        )]);
 
-    assert_eq!(actual, "utf16.ini");
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.json
+                | describe --detailed
+                | where column == rusty_luck
+                | get nulls
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open los_tres_caballeros.json
+                | describe --detailed
+                | where column == rusty_luck
+                | get type
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "int");
+    })
 }
 
 #[test]
-fn can_get_reverse_first() {
+fn where_with_prev_compares_against_the_preceding_row() {
+    Playground::setup("filter_where_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "readings.json",
+            r#"
+                [
+                    {"day": "mon", "reading": 3},
+                    {"day": "tue", "reading": 5},
+                    {"day": "wed", "reading": 4},
+                    {"day": "thu", "reading": 7}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open readings.json
+                | where --with-prev { $it.reading > $prev.reading }
+                | get day
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["tue","thu"]"#);
+    })
+}
+
+#[test]
+fn where_with_prev_sees_nothing_before_the_first_row() {
+    Playground::setup("filter_where_test_2", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "readings.json",
+            r#"
+                [
+                    {"day": "mon", "reading": 3},
+                    {"day": "tue", "reading": 5},
+                    {"day": "wed", "reading": 4},
+                    {"day": "thu", "reading": 7}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open readings.json
+                | where --with-prev { $prev.reading != 5 }
+                | get day
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["mon","tue","thu"]"#);
+    })
+}
+
+#[test]
+fn reverse_columns_flips_the_key_order_of_each_row() {
+    Playground::setup("filter_reverse_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "row.json",
+            r#"
+                {"first": 1, "second": 2, "third": 3}
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open row.json
+                | reverse --columns
+                | to-json
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, r#"{"third":3,"second":2,"first":1}"#);
+    })
+}
+
+#[test]
+fn converts_a_stream_to_a_json_array() {
     let actual = nu!(
-        cwd: "tests/fixtures/formats",
-        "ls | sort-by name | reverse | first 1 | get name | trim | echo $it"
-    );
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a b c"
+            | split-row " "
+            | to-json
+            | echo $it
+        "#
+    ));
 
-    assert_eq!(actual, "utf16.ini");
+    assert_eq!(actual, r#"["a","b","c"]"#);
+
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            echo "a"
+            | to-json --array
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(actual, r#"["a"]"#);
 }
 
 #[test]
-fn embed() {
-    Playground::setup("embed_test", |dirs, sandbox| {
+fn formats_a_byte_count_as_a_file_size() {
+    Playground::setup("filter_format_filesize_test_1", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_caballeros.txt",
+            "downloads.txt",
             r#"
-                first_name,last_name
-                Andrés,Robalino
-                Jonathan,Turner
-                Yehuda,Katz
+                name,size
+                nu.tar.gz,1048576
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_caballeros.txt
+                open downloads.txt
                 | from-csv
-                | embed caballeros
-                | get caballeros
-                | nth 0
-                | get last_name
+                | format-filesize size --unit MB
+                | get size
                 | echo $it
             "#
         ));
 
-        assert_eq!(actual, "Robalino");
+        assert_eq!(actual, "1.0 MB");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open downloads.txt
+                | from-csv
+                | format-filesize size --unit binary
+                | get size
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1.0 MiB");
     })
 }
 
 #[test]
-fn get() {
-    Playground::setup("get_test", |dirs, sandbox| {
+fn formats_file_sizes_with_units_that_match_their_labels() {
+    Playground::setup("filter_format_filesize_test_2", |dirs, sandbox| {
         sandbox.with_files(vec![FileWithContentToBeTrimmed(
-            "los_tres_caballeros.txt",
+            "downloads.txt",
             r#"
-                first_name,last_name
-                Andrés,Robalino
-                Jonathan,Turner
-                Yehuda,Katz
+                name,size
+                nu.tar.gz,1500000
             "#,
         )]);
 
         let actual = nu!(
             cwd: dirs.test(), h::pipeline(
             r#"
-                open los_tres_caballeros.txt
+                open downloads.txt
+                | from-csv
+                | format-filesize size --unit MB
+                | get size
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1.5 MB");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open downloads.txt
+                | from-csv
+                | format-filesize size --unit decimal
+                | get size
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1.5 MB");
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open downloads.txt
                 | from-csv
+                | format-filesize size --unit binary
+                | get size
+                | echo $it
+            "#
+        ));
+
+        assert_eq!(actual, "1.4 MiB");
+    })
+}
+
+#[test]
+fn formats_a_number_of_seconds_as_a_duration() {
+    Playground::setup("filter_format_duration_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "jobs.json",
+            r#"
+                [
+                    {"job": "build", "elapsed": 3723},
+                    {"job": "test", "elapsed": 90}
+                ]
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open jobs.json
+                | format-duration elapsed
+                | get elapsed
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["1h 2m 3s","1m 30s"]"#);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open jobs.json
+                | format-duration elapsed --unit min
+                | get elapsed
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["62 min","1.5 min"]"#);
+    })
+}
+
+#[test]
+fn get_collects_a_field_across_a_list_with_a_wildcard() {
+    Playground::setup("filter_get_wildcard_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "caballeros.json",
+            r#"
+                {
+                    "items": [
+                        {"name": "Andrés", "lang": "rust"},
+                        {"name": "Jonathan", "lang": "rust"},
+                        {"name": "Yehuda", "lang": "ruby"}
+                    ]
+                }
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open caballeros.json
+                | get items.*.name
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["Andrés","Jonathan","Yehuda"]"#);
+    })
+}
+
+#[test]
+fn uniq_counts_repeated_values() {
+    Playground::setup("filter_uniq_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "words.txt",
+            r#"
+                arepa
+                taco
+                arepa
+                arepa
+                taco
+                empanada
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open words.txt
+                | lines
+                | uniq --count
+                | sort-by count
+                | get value
+                | to-json
+            "#
+        ));
+
+        assert_eq!(actual, r#"["empanada","taco","arepa"]"#);
+    })
+}
+
+#[test]
+fn can_convert_table_to_html() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | pick name edition
+            | to-html
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(
+        actual,
+        "<table><tr><th>name</th><th>edition</th></tr><tr><td>nu</td><td>2018</td></tr></table>"
+    );
+}
+
+#[test]
+fn can_convert_table_to_markdown() {
+    let actual = nu!(
+        cwd: "tests/fixtures/formats", h::pipeline(
+        r#"
+            open cargo_sample.toml
+            | get package
+            | pick name edition
+            | to-md --pretty
+            | echo $it
+        "#
+    ));
+
+    assert_eq!(
+        actual,
+        "| name | edition || ---- | ------- || nu   | 2018    |"
+    );
+}
+
+#[test]
+fn converts_html_tables_to_structured_table() {
+    Playground::setup("filter_from_html_test_1", |dirs, sandbox| {
+        sandbox.with_files(vec![FileWithContentToBeTrimmed(
+            "stats.txt",
+            r#"
+                <html><body>
+                <table>
+                    <tr><th>player</th><th>goals</th></tr>
+                    <tr><td>Andrés</td><td>3</td></tr>
+                    <tr><td>Jonathan &amp; Yehuda</td><td>5</td></tr>
+                </table>
+                </body></html>
+            "#,
+        )]);
+
+        let actual = nu!(
+            cwd: dirs.test(), h::pipeline(
+            r#"
+                open stats.txt
+                | from-html
                 | nth 1
-                | get last_name
+                | get player
                 | echo $it
             "#
         ));
 
-        assert_eq!(actual, "Turner");
+        assert_eq!(actual, "Jonathan & Yehuda");
     })
 }
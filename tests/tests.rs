@@ -70,3 +70,82 @@ fn edit_plugin() {
 
     assert_eq!(actual, "7");
 }
+
+#[test]
+fn runs_a_script_file_and_exits_with_a_status() {
+    use std::process::Command;
+
+    let script = std::env::temp_dir().join("nu_script_test.nu");
+    std::fs::write(
+        &script,
+        "cd tests/fixtures/formats\nopen cargo_sample.toml | get package.edition | echo $it\n",
+    )
+    .unwrap();
+
+    let output = Command::new(h::executable_path())
+        .arg(&script)
+        .output()
+        .expect("couldn't run nu");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2018");
+
+    let output = Command::new(h::executable_path())
+        .args(&["-c", "open does_not_exist.toml"])
+        .output()
+        .expect("couldn't run nu");
+
+    assert!(!output.status.success());
+}
+
+#[cfg(unix)]
+#[test]
+fn script_fails_when_its_last_external_command_fails() {
+    use std::process::Command;
+
+    let output = Command::new(h::executable_path())
+        .args(&["-c", "false"])
+        .output()
+        .expect("couldn't run nu");
+
+    assert!(!output.status.success());
+
+    let output = Command::new(h::executable_path())
+        .args(&["--no-color", "-c", "false"])
+        .output()
+        .expect("couldn't run nu");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("External command failed"));
+    assert!(!stderr.contains('\u{1b}'));
+}
+
+#[cfg(unix)]
+#[test]
+fn script_fails_when_any_external_command_in_a_pipeline_fails() {
+    use std::process::Command;
+
+    for pipeline in &[
+        "^false | lines",
+        "^false | ^cat",
+        "echo a | ^false | ^cat | lines",
+    ] {
+        let output = Command::new(h::executable_path())
+            .args(&["-c", pipeline])
+            .output()
+            .expect("couldn't run nu");
+
+        assert!(!output.status.success(), "{} succeeded", pipeline);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn prompt_carries_on_past_a_failed_external_command() {
+    let actual = nu_error!(
+        cwd: "tests/fixtures",
+        "^false"
+    );
+
+    assert!(!actual.contains("External command failed"));
+}